@@ -1,5 +1,6 @@
 use helpv::app::App;
 use helpv::config::Config;
+use helpv::paths::Paths;
 use serde_json::json;
 use std::process::Command;
 use std::time::Instant;
@@ -9,7 +10,7 @@ const SAMPLES: usize = 3;
 
 fn time_helpv(cmd: &str) -> f64 {
     let command = vec![cmd.to_string()];
-    let config = Config::load().expect("failed to load config");
+    let config = Config::load(&Paths::from_env()).expect("failed to load config");
     let start = Instant::now();
     let _ = App::new(command, config);
     start.elapsed().as_secs_f64() * 1000.0
@@ -1,27 +1,78 @@
 use anyhow::Result;
-use crossterm::event::{self, Event, KeyCode, KeyEvent};
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyModifiers};
 use ratatui::{
     Frame,
+    backend::TestBackend,
     buffer::Buffer,
     layout::Rect,
     style::Color,
     widgets::{Clear, Widget},
 };
+use std::collections::{HashMap, VecDeque};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::mpsc;
-use std::time::Duration;
+use std::time::{Duration, Instant, SystemTime};
 
 use crate::{
+    clipboard::copy_to_clipboard,
     config::Config,
-    fetcher::{ContentSource, fetch_best_content, fetch_help_with_invoke},
+    debug_log::DebugLogger,
+    fetcher::{
+        ContentSource, FetchMetadata, HelpStream, child_command, fetch_alternate_content,
+        fetch_help_stream, fetch_help_with_invoke, fetch_man_formatting, fetch_with_metadata,
+        fetch_with_source_hint, format_age, read_help_file, strip_man_formatting,
+    },
     finder::{Finder, FinderAction, FinderWidget},
-    history::History,
-    keys::{Action, KeyHandler},
-    pager::{HelpOverlay, Pager, PagerWidget, SearchInput},
-    parser::{Subcommand, parse_subcommands},
+    history::{History, HistoryEntry, Invocation},
+    keys::{Action, KeyHandler, key_to_string},
+    opener::open_url,
+    pager::{
+        ContentStats, DiffView, DiffWidget, HelpOverlay, Pager, PagerWidget, SearchInput,
+        StatsOverlay, match_man_formatting,
+    },
+    parser::{ParseMethod, ParseStats, Subcommand, confirm_with_man_pages, parse_tool_subcommands},
+    paths::{check_file_name, tilde_path},
     switcher::{CommandSwitcher, SwitcherAction, SwitcherWidget},
-    toolpacks::ToolPacks,
+    toolpacks::{DedupeStrategy, DiscoveredItem, ToolPacks},
+    watcher::ConfigWatcher,
 };
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorLevel {
+    /// Dismissed automatically after `Config::error_display_ms`
+    Transient,
+    /// Stays visible until the next keypress
+    Critical,
+}
+
+/// How long an info message stays on screen
+const INFO_DISPLAY_TIME: Duration = Duration::from_secs(2);
+/// Title of the divider above appended examples
+const EXAMPLES_TITLE: &str = "Examples";
+/// How long the notice for a page shown from cache stays on screen
+const CACHE_NOTICE_TIME: Duration = Duration::from_secs(4);
+/// Shown when a help command fills both stdout and stderr
+const AMBIGUOUS_STDOUT_NOTICE: &str =
+    "[Ambiguous help output — showing stdout. Press F5 to try stderr]";
+const AMBIGUOUS_STDERR_NOTICE: &str =
+    "[Ambiguous help output — showing stderr. Press F5 to try stdout]";
+const AMBIGUOUS_NOTICE_TIME: Duration = Duration::from_secs(4);
+/// Shown when fetched help was longer than `max_content_bytes`
+const TRUNCATED_NOTICE: &str = "[Help output cut short — raise max_content_bytes to see all of it]";
+/// How long the notice for newly discovered subcommands stays on screen
+const DISCOVERY_NOTICE_TIME: Duration = Duration::from_secs(3);
+/// Window for the second Escape that quits when `escape_quits` is off
+const DOUBLE_ESCAPE_TIME: Duration = Duration::from_millis(500);
+
+#[derive(Debug, Clone)]
+pub struct ErrorNotice {
+    pub message: String,
+    pub level: ErrorLevel,
+    pub shown_at: Instant,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum AppState {
     Paging,
@@ -29,8 +80,14 @@ pub enum AppState {
     Finding,
     Switching,
     Help,
+    Stats,
+    Command,
+    /// Waiting for y/N before clearing history
+    Confirm,
 }
 
+const CLEAR_HISTORY_PROMPT: &str = "Clear all history? (y/N) ";
+
 pub struct App {
     pub state: AppState,
     prev_state: AppState,
@@ -41,44 +98,232 @@ pub struct App {
     pub command_history: Vec<String>,
     pub config: Config,
     pub current_command: Vec<String>,
+    /// The item whose `invoke_command` produced the page (a man page or
+    /// guide), when it isn't the help for `current_command`
+    invoked_item: Option<Invocation>,
     pub subcommands: Vec<Subcommand>,
     pub search_input: String,
+    /// Text typed after `:` in command mode
+    command_input: String,
     pub key_handler: KeyHandler,
     pub should_quit: bool,
-    pub error_message: Option<String>,
+    /// Set from a signal handler (SIGHUP/SIGTERM) to request a clean exit
+    pub signal_quit: Arc<AtomicBool>,
+    pub error_message: Option<ErrorNotice>,
+    /// Status notice (not an error) and when it goes away
+    pub info_message: Option<(String, Instant)>,
     pub content_source: ContentSource,
+    /// How the current page's subcommands were parsed
+    pub parse_method: ParseMethod,
+    /// How long fetching the current page took
+    pub fetch_time: Duration,
+    /// Statistics for the current page, computed when first shown
+    content_stats: Option<ContentStats>,
+    /// Whether keystrokes are currently being captured into `macro_buffer`
+    pub recording: bool,
+    /// The recorded macro, kept for the session only
+    pub macro_buffer: Vec<KeyEvent>,
+    /// Keys queued for replay, handled one per frame
+    playback_buffer: VecDeque<KeyEvent>,
+    replaying: bool,
+    /// Line where visual-line selection started; `None` outside visual mode
+    pub visual_start: Option<usize>,
+    /// The finder item highlighted when the finder was last closed or used
+    pub finder_last_selected: Option<Subcommand>,
+    /// Shell command to run after the TUI exits, from `exec_on_select`
+    pub exec_command: Option<String>,
     discovery_receiver: Option<mpsc::Receiver<Vec<Subcommand>>>,
+    /// The current page from the other of `--help` and `man`, fetched in the background
+    pub alt_content: Option<(String, ContentSource)>,
+    alt_receiver: Option<mpsc::Receiver<Option<(String, ContentSource)>>>,
+    /// Raw `man` output for a man page, for its bold and underlined text
+    man_formatting_receiver: Option<mpsc::Receiver<HashMap<usize, String>>>,
+    /// Diff of `--help` against `man`, shown instead of the page while set
+    pub diff: Option<DiffView>,
+    /// Present when `watch_config` is on and the watcher started
+    config_watcher: Option<ConfigWatcher>,
+    /// Most recent key press, for crash reports and the debug log
+    last_key: Option<KeyEvent>,
+    /// State transitions log, active with `debug_log`
+    debug_log: DebugLogger,
+    /// When Escape last went unheeded with `escape_quits` off
+    last_escape_time: Option<Instant>,
+    /// Finder export waiting to be printed outside the TUI
+    pending_export: Option<String>,
+    /// File to open in the user's editor once the TUI is suspended
+    pending_edit: Option<PathBuf>,
+    /// Stop the process (Ctrl-Z) after this frame
+    pending_suspend: bool,
+    /// Clear the terminal before the next frame so stray output is painted over
+    force_clear: bool,
+    /// Last drawn frame was below `min_width` x `min_height`; only quit works
+    terminal_too_small: bool,
 }
 
 impl App {
     pub fn new(command: Vec<String>, config: Config) -> Result<Self> {
-        let (content, source) = fetch_best_content(&command, &config)?;
-        let subcommands = parse_subcommands(&content, &config);
+        let fetch_started = Instant::now();
+        let (content, source, metadata) = fetch_with_metadata(&command, &config)?;
+        Ok(Self::with_fetched(
+            command,
+            config,
+            (content, source, metadata),
+            fetch_started.elapsed(),
+        ))
+    }
+
+    /// Reopen a snapshot saved with `ms` (`--restore`): its deepest page is
+    /// fetched again, or shown from its saved text if that fails, and the
+    /// rest becomes the back history
+    pub fn restore(mut snapshot: History, config: Config) -> Result<Self> {
+        let entry = snapshot
+            .pop()
+            .ok_or_else(|| anyhow::anyhow!("Snapshot is empty"))?;
+        let fetch_started = Instant::now();
+        let (content, source, metadata) = match fetch_entry(&entry, &config) {
+            Ok(found) => found,
+            Err(_) => {
+                let age = entry.pushed_at.elapsed().unwrap_or_default();
+                let cached = entry.cached_content.clone();
+                (
+                    cached,
+                    ContentSource::Cache { age },
+                    FetchMetadata::default(),
+                )
+            }
+        };
+
+        let man_raw = metadata.man_raw.clone();
+        let fetched = (content.clone(), source, metadata);
+        let mut app = Self::with_fetched(
+            entry.command.clone(),
+            config,
+            fetched,
+            fetch_started.elapsed(),
+        );
+        if let Some(invocation) = entry.invoked {
+            app.subcommands = app.parse_page(&content, &app.content_source, true).0;
+            app.show_invoked(invocation, man_raw);
+        }
+        app.history = snapshot;
+        app.pager.scroll = entry.scroll_position;
+        if let Some(notice) = cache_notice(&app.content_source) {
+            app.clear_alt_content();
+            app.show_info_for(notice, CACHE_NOTICE_TIME);
+        }
+        Ok(app)
+    }
+
+    /// Show help saved in a file (`--file`) instead of running any fetcher
+    pub fn from_file(command: Vec<String>, path: &Path, config: Config) -> Result<Self> {
+        let fetch_started = Instant::now();
+        let content = read_help_file(path)?;
+        Ok(Self::with_content(
+            command,
+            config,
+            content,
+            ContentSource::File(path.to_path_buf()),
+            fetch_started.elapsed(),
+        ))
+    }
+
+    fn with_content(
+        command: Vec<String>,
+        config: Config,
+        content: String,
+        source: ContentSource,
+        fetch_time: Duration,
+    ) -> Self {
+        let fetched = (content, source, FetchMetadata::default());
+        Self::with_fetched(command, config, fetched, fetch_time)
+    }
 
-        let key_handler = KeyHandler::new(config.keys.clone());
-        let initial_cmd = command[0].clone();
+    fn with_fetched(
+        command: Vec<String>,
+        config: Config,
+        (content, source, metadata): (String, ContentSource, FetchMetadata),
+        fetch_time: Duration,
+    ) -> Self {
+        let (subcommands, stats) = parse_tool_subcommands(&command[0], &content, &config);
+
+        let mut key_handler = KeyHandler::new(config.keys.clone());
+        key_handler.set_dead_key_timeout(Duration::from_millis(config.pending_key_timeout_ms));
+        let mut command_history = Self::load_command_history(&config);
+        push_recent(
+            &mut command_history,
+            &command.join(" "),
+            config.command_history_max,
+        );
 
         // Spawn background discovery (man -k + toolpacks) — results arrive via channel
-        let receiver = spawn_discovery(&command[0], &config.toolpacks);
+        let receiver = spawn_discovery(&command[0], &config);
+
+        let (config_watcher, watch_error) = if config.watch_config {
+            match ConfigWatcher::new(&config.paths.config_file()) {
+                Ok(watcher) => (Some(watcher), None),
+                Err(e) => (None, Some(format!("Could not watch config: {}", e))),
+            }
+        } else {
+            (None, None)
+        };
+
+        let debug_log = DebugLogger::new(config.debug_log.then(|| config.paths.debug_log_file()));
 
-        Ok(Self {
+        let mut app = Self {
             state: AppState::Paging,
             prev_state: AppState::Paging,
-            pager: Pager::new(content),
+            pager: Pager::new(content)
+                .with_search_context(config.search_context_lines)
+                .with_case_sensitive(config.search_case_sensitive),
             finder: None,
             switcher: None,
             history: History::new(),
-            command_history: vec![initial_cmd],
+            command_history,
             config,
             current_command: command,
+            invoked_item: None,
             subcommands,
             search_input: String::new(),
+            command_input: String::new(),
             key_handler,
             should_quit: false,
+            signal_quit: Arc::new(AtomicBool::new(false)),
             error_message: None,
+            info_message: None,
             content_source: source,
+            parse_method: stats.match_method,
+            fetch_time,
+            content_stats: None,
+            recording: false,
+            macro_buffer: Vec::new(),
+            playback_buffer: VecDeque::new(),
+            replaying: false,
+            visual_start: None,
+            finder_last_selected: None,
+            exec_command: None,
             discovery_receiver: Some(receiver),
-        })
+            alt_content: None,
+            alt_receiver: None,
+            man_formatting_receiver: None,
+            diff: None,
+            config_watcher,
+            last_key: None,
+            debug_log,
+            last_escape_time: None,
+            pending_export: None,
+            pending_edit: None,
+            pending_suspend: false,
+            force_clear: false,
+            terminal_too_small: false,
+        };
+        app.debug_log.log_parse(&app.current_command[0], &stats);
+        app.start_alt_fetch(metadata.man_raw.clone());
+        if let Some(message) = watch_error {
+            app.show_error(message);
+        }
+        app.show_key_warnings();
+        app.report_fetch(&app.content_source.clone(), &metadata);
+        app
     }
 
     pub fn run(
@@ -86,15 +331,121 @@ impl App {
         terminal: &mut ratatui::Terminal<impl ratatui::backend::Backend>,
     ) -> Result<()> {
         while !self.should_quit {
+            if self.signal_quit.load(Ordering::Relaxed) {
+                break;
+            }
+            if std::mem::take(&mut self.force_clear) {
+                terminal.clear()?;
+            }
             terminal.draw(|frame| self.draw(frame))?;
             self.handle_events()?;
+            if let Some(text) = self.pending_export.take() {
+                print_outside_tui(&text)?;
+                terminal.clear()?;
+            }
+            if std::mem::take(&mut self.pending_suspend) {
+                suspend_outside_tui()?;
+                terminal.clear()?;
+            }
+            if let Some(path) = self.pending_edit.take() {
+                let edited = edit_outside_tui(&path);
+                terminal.clear()?;
+                match edited {
+                    Ok(()) => self.reload_config(),
+                    Err(e) => {
+                        self.show_error(format!("Could not edit {}: {}", tilde_path(&path), e))
+                    }
+                }
+            }
+        }
+        // Best effort: a failed save only loses the recent commands
+        let _ = Self::save_command_history(&self.config, &self.command_history);
+        Ok(())
+    }
+
+    /// Recent commands saved by an earlier session, most recent first, capped at
+    /// `command_history_max`. A missing or unreadable file gives an empty list.
+    pub fn load_command_history(config: &Config) -> Vec<String> {
+        let mut history: Vec<String> = config
+            .paths
+            .command_history_file()
+            .and_then(|path| std::fs::read_to_string(path).ok())
+            .and_then(|text| serde_json::from_str(&text).ok())
+            .unwrap_or_default();
+        history.truncate(config.command_history_max.max(1));
+        history
+    }
+
+    /// Write the recent commands as a JSON array for the next session
+    pub fn save_command_history(config: &Config, history: &[String]) -> Result<()> {
+        let Some(path) = config.paths.command_history_file() else {
+            return Ok(());
+        };
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
         }
+        let history = &history[..history.len().min(config.command_history_max.max(1))];
+        std::fs::write(&path, serde_json::to_string_pretty(history)? + "\n")?;
         Ok(())
     }
 
+    /// Save the recent commands now, reporting failure without interrupting
+    fn persist_command_history(&mut self) {
+        if let Err(e) = Self::save_command_history(&self.config, &self.command_history) {
+            self.show_error(format!("Could not save command history: {}", e));
+        }
+    }
+
+    /// Draw one frame into a `width` x `height` test terminal and return it as
+    /// text, one line per row with trailing spaces trimmed
+    pub fn render_to_string(&mut self, width: u16, height: u16) -> String {
+        let mut terminal = ratatui::Terminal::new(TestBackend::new(width, height))
+            .expect("test backend never fails");
+        terminal
+            .draw(|frame| self.draw(frame))
+            .expect("test backend never fails");
+
+        let buffer = terminal.backend().buffer();
+        (0..height)
+            .map(|y| {
+                let row: String = (0..width).map(|x| buffer[(x, y)].symbol()).collect();
+                row.trim_end().to_string() + "\n"
+            })
+            .collect()
+    }
+
+    /// Whether `area` is at least `min_width` x `min_height`
+    fn check_terminal_size(&self, area: Rect) -> bool {
+        area.width >= self.config.min_width && area.height >= self.config.min_height
+    }
+
     fn draw(&mut self, frame: &mut Frame) {
         let area = frame.area();
 
+        self.debug_log.next_frame();
+        if self.prev_state != self.state {
+            let key = self.last_key.as_ref().map(key_to_string);
+            self.debug_log.log_transition(
+                &format!("{:?}", self.prev_state),
+                &format!("{:?}", self.state),
+                key.as_deref(),
+                &self.current_command,
+            );
+        }
+
+        // Layouts assume a minimum size, so draw nothing else below it
+        self.terminal_too_small = !self.check_terminal_size(area);
+        if self.terminal_too_small {
+            let message = format!(
+                "Terminal too small ({}x{} min required)",
+                self.config.min_width, self.config.min_height
+            );
+            frame.render_widget(Clear, area);
+            frame.render_widget(CenteredNotice(&message), area);
+            self.prev_state = self.state;
+            return;
+        }
+
         // Clear entire screen on any state transition to prevent artifacts
         if self.prev_state != self.state {
             frame.render_widget(Clear, area);
@@ -105,21 +456,54 @@ impl App {
         self.pager
             .clamp_scroll(area.height.saturating_sub(1) as usize);
 
+        if let Some(ref mut diff) = self.diff {
+            diff.clamp_scroll(area.height.saturating_sub(1) as usize);
+            frame.render_widget(DiffWidget::new(diff, "diff: --help → man"), area);
+            self.draw_messages(frame, area);
+            return;
+        }
+
         // Draw the pager
         let breadcrumb = self.history.full_breadcrumb(&self.current_command);
         let pager_widget = PagerWidget::new(
             &self.pager,
             &breadcrumb,
             self.subcommands.len(),
-            self.content_source,
-        );
+            self.content_source.clone(),
+        )
+        .show_scrollbar(self.config.show_scrollbar)
+        .tab_width(self.config.tab_width)
+        .smart_wrap(self.config.smart_wrap)
+        .callout_highlighting(self.config.callout_highlighting)
+        .cursor_line(self.config.cursor_line)
+        .word_count(self.config.show_word_count)
+        .parse_method(self.parse_method)
+        .recording(self.recording.then(|| self.play_macro_key()))
+        .selection(self.visual_selection());
         frame.render_widget(pager_widget, area);
 
         // Draw overlays based on state
         match self.state {
             AppState::Searching => {
                 let status_area = Rect::new(area.x, area.bottom() - 1, area.width, 1);
-                frame.render_widget(SearchInput::new(&self.search_input), status_area);
+                frame.render_widget(
+                    SearchInput::new(&self.search_input).case_sensitive(self.pager.case_sensitive),
+                    status_area,
+                );
+            }
+            AppState::Command => {
+                let status_area = Rect::new(area.x, area.bottom() - 1, area.width, 1);
+                frame.render_widget(
+                    SearchInput::new(&self.command_input).prompt(":"),
+                    status_area,
+                );
+            }
+            AppState::Confirm => {
+                let status_area = Rect::new(area.x, area.bottom() - 1, area.width, 1);
+                frame.render_widget(
+                    SearchInput::new("").prompt(CLEAR_HISTORY_PROMPT),
+                    status_area,
+                );
             }
             AppState::Finding => {
                 frame.render_widget(Dim, area);
@@ -137,28 +521,98 @@ impl App {
                 frame.render_widget(Dim, area);
                 frame.render_widget(HelpOverlay, area);
             }
+            AppState::Stats => {
+                frame.render_widget(Dim, area);
+                let stats = self.content_statistics();
+                let overlay = StatsOverlay::new(&stats)
+                    .row("Subcommands found", self.subcommands.len().to_string())
+                    .row("Content source", self.content_source.to_string())
+                    .row("Fetch time", format!("{}ms", self.fetch_time.as_millis()))
+                    .row("Parse method", self.parse_method.label());
+                frame.render_widget(overlay, area);
+            }
             AppState::Paging => {}
         }
 
-        // Show error message if any
-        if let Some(ref msg) = self.error_message {
+        self.draw_messages(frame, area);
+    }
+
+    fn draw_messages(&mut self, frame: &mut Frame, area: Rect) {
+        if self
+            .info_message
+            .as_ref()
+            .is_some_and(|(_, expires_at)| Instant::now() >= *expires_at)
+        {
+            self.info_message = None;
+        }
+        if let Some((ref message, _)) = self.info_message {
+            let info_area = Rect::new(area.x, area.bottom() - 1, area.width, 1);
+            frame.render_widget(InfoMessage(message), info_area);
+        }
+
+        // Show error message if any, dropping transient ones once they expire
+        let display_time = Duration::from_millis(self.config.error_display_ms);
+        if self
+            .error_message
+            .as_ref()
+            .is_some_and(|e| e.level != ErrorLevel::Critical && e.shown_at.elapsed() > display_time)
+        {
+            self.error_message = None;
+        }
+        if let Some(ref notice) = self.error_message {
+            let remaining = match notice.level {
+                ErrorLevel::Transient => {
+                    Some(display_time.saturating_sub(notice.shown_at.elapsed()))
+                }
+                ErrorLevel::Critical => None,
+            };
             let error_area = Rect::new(area.x, area.bottom() - 1, area.width, 1);
-            frame.render_widget(ErrorMessage(msg), error_area);
+            frame.render_widget(ErrorMessage(&notice.message, remaining), error_area);
         }
     }
 
     fn handle_events(&mut self) -> Result<()> {
         self.poll_discovery();
+        self.poll_alt_content();
+        self.poll_man_formatting();
+
+        if self.config_watcher.as_ref().is_some_and(|w| w.poll()) {
+            self.reload_config();
+        }
+
+        if let Some(ref mut finder) = self.finder {
+            finder.tick();
+        }
+
+        // Replay one macro key per frame so each step gets drawn
+        if let Some(key) = self.playback_buffer.pop_front() {
+            self.replaying = true;
+            let result = self.handle_key(key);
+            self.replaying = !self.playback_buffer.is_empty();
+            return result;
+        }
 
         if event::poll(Duration::from_millis(100))? {
             match event::read()? {
                 Event::Key(key) => {
-                    self.error_message = None; // Clear error on any key press
-                    self.handle_key(key)?;
+                    // Clear messages on any key press
+                    self.error_message = None;
+                    self.info_message = None;
+                    if self.recording {
+                        self.macro_buffer.push(key);
+                    }
+                    self.last_key = Some(key);
+                    if self.terminal_too_small {
+                        if self.key_handler.handle(key) == Some(Action::Quit) {
+                            self.should_quit = true;
+                        }
+                    } else {
+                        self.handle_key(key)?;
+                    }
                 }
                 Event::Resize(_, _) => {
-                    // Terminal resize: redraw will happen automatically on next frame
-                    // Just need to handle the event to trigger the redraw cycle
+                    // Some terminals leave stale cells behind after a resize
+                    self.force_clear = true;
                 }
                 _ => {}
             }
@@ -166,17 +620,164 @@ impl App {
         Ok(())
     }
 
+    /// Block until background discovery finishes (or `timeout` elapses) and merge
+    /// the results. Used by non-interactive modes that print and exit.
+    pub fn wait_for_discovery(&mut self, timeout: Duration) {
+        if let Some(rx) = self.discovery_receiver.take()
+            && let Ok(discovered) = rx.recv_timeout(timeout)
+        {
+            self.merge_discovery(discovered);
+        }
+    }
+
+    /// Add background discovery results to the parsed subcommands. Man pages
+    /// among them back up entries the aggressive parser only guessed at.
+    fn merge_discovery(&mut self, discovered: Vec<Subcommand>) {
+        if self.parse_method == ParseMethod::Aggressive {
+            confirm_with_man_pages(&mut self.subcommands, &self.current_command[0], &discovered);
+        }
+        merge_discovered_items(&mut self.subcommands, discovered);
+    }
+
+    /// Fetch the alternate source for the current page on a background thread,
+    /// along with the formatting of a man page. `man_raw` is the raw man output
+    /// when the page's fetch kept it.
+    fn start_alt_fetch(&mut self, man_raw: Option<String>) {
+        self.alt_content = None;
+        self.diff = None;
+
+        let (tx, rx) = mpsc::channel();
+        let cmd = self.current_command.clone();
+        let source = self.content_source.clone();
+        let config = self.config.clone();
+        std::thread::spawn(move || {
+            let _ = tx.send(fetch_alternate_content(&cmd, &source, &config));
+        });
+        self.alt_receiver = Some(rx);
+        self.start_man_formatting(man_raw);
+    }
+
+    /// Match a man page's bold and underlined text to its lines on a background
+    /// thread, running `man` for the raw output unless `raw` already has it
+    fn start_man_formatting(&mut self, raw: Option<String>) {
+        self.man_formatting_receiver = None;
+        if self.content_source != ContentSource::Man {
+            return;
+        }
+
+        let (tx, rx) = mpsc::channel();
+        let cmd = self.current_command.clone();
+        let config = self.config.clone();
+        let lines = self.pager.content.clone();
+        std::thread::spawn(move || {
+            if let Some(raw) = raw.or_else(|| fetch_man_formatting(&cmd, &config)) {
+                let _ = tx.send(match_man_formatting(&lines, &raw));
+            }
+        });
+        self.man_formatting_receiver = Some(rx);
+    }
+
+    /// Drop any alternate content, for pages that have no `--help`/`man` pair
+    fn clear_alt_content(&mut self) {
+        self.alt_content = None;
+        self.alt_receiver = None;
+        self.man_formatting_receiver = None;
+        self.diff = None;
+    }
+
+    fn poll_alt_content(&mut self) {
+        if let Some(ref rx) = self.alt_receiver {
+            match rx.try_recv() {
+                Ok(alt) => {
+                    self.alt_content = alt;
+                    self.alt_receiver = None;
+                }
+                Err(mpsc::TryRecvError::Disconnected) => {
+                    self.alt_receiver = None;
+                }
+                Err(mpsc::TryRecvError::Empty) => {}
+            }
+        }
+    }
+
+    fn poll_man_formatting(&mut self) {
+        if let Some(ref rx) = self.man_formatting_receiver {
+            match rx.try_recv() {
+                Ok(formatting) => {
+                    self.pager.set_man_formatting(formatting);
+                    self.man_formatting_receiver = None;
+                }
+                Err(mpsc::TryRecvError::Disconnected) => {
+                    self.man_formatting_receiver = None;
+                }
+                Err(mpsc::TryRecvError::Empty) => {}
+            }
+        }
+    }
+
+    fn toggle_diff(&mut self) {
+        if self.diff.take().is_some() {
+            return;
+        }
+
+        let Some((ref alt, ref alt_source)) = self.alt_content else {
+            let message = if self.alt_receiver.is_some() {
+                "Still fetching the alternate help source"
+            } else {
+                "Diff needs both --help and man for this page"
+            };
+            self.show_info(message);
+            return;
+        };
+
+        let alt_pager = Pager::new(alt.clone());
+        self.diff = Some(if *alt_source == ContentSource::Man {
+            self.pager.diff(&alt_pager)
+        } else {
+            alt_pager.diff(&self.pager)
+        });
+    }
+
+    /// Keys while the diff is shown: scrolling, or closing it
+    fn handle_diff_action(&mut self, action: Action) {
+        let Some(ref mut diff) = self.diff else {
+            return;
+        };
+        match action {
+            Action::ScrollUp => diff.scroll_up(1),
+            Action::ScrollDown => diff.scroll_down(1),
+            Action::HalfPageUp => diff.scroll_up(10),
+            Action::HalfPageDown => diff.scroll_down(10),
+            Action::PageUp => diff.scroll_up(20),
+            Action::PageDown => diff.scroll_down(20),
+            Action::Top => diff.scroll = 0,
+            Action::Bottom => diff.scroll = usize::MAX, // Clamped in draw
+            Action::ToggleDiff | Action::Quit | Action::Back => self.diff = None,
+            _ => {}
+        }
+    }
+
     fn poll_discovery(&mut self) {
         if let Some(ref rx) = self.discovery_receiver {
             match rx.try_recv() {
                 Ok(discovered) => {
-                    merge_discovered_items(&mut self.subcommands, discovered);
+                    let before = self.subcommands.len();
+                    self.merge_discovery(discovered);
                     self.discovery_receiver = None;
+                    let added = self.subcommands.len() - before;
+                    // An open finder reports new items itself
+                    if added > 0 && self.state != AppState::Finding {
+                        self.show_info_for(discovery_notice(added), DISCOVERY_NOTICE_TIME);
+                    }
                 }
                 Err(mpsc::TryRecvError::Disconnected) => {
                     self.discovery_receiver = None;
                 }
-                Err(mpsc::TryRecvError::Empty) => {}
+                Err(mpsc::TryRecvError::Empty) => return,
+            }
+            // Show the new items in an open finder
+            if let Some(ref mut finder) = self.finder {
+                finder.set_items(self.subcommands.clone());
             }
         }
     }
@@ -188,76 +789,267 @@ impl App {
             AppState::Finding => self.handle_finding_key(key),
             AppState::Switching => self.handle_switching_key(key),
             AppState::Help => self.handle_help_key(key),
+            AppState::Stats => {
+                // Any key closes the statistics overlay
+                self.state = AppState::Paging;
+                Ok(())
+            }
+            AppState::Command => self.handle_command_key(key),
+            AppState::Confirm => {
+                self.state = AppState::Paging;
+                if matches!(key.code, KeyCode::Char('y' | 'Y')) {
+                    self.clear_history();
+                }
+                Ok(())
+            }
         }
     }
 
     fn handle_paging_key(&mut self, key: KeyEvent) -> Result<()> {
+        // Only two Escapes in a row count as a double Escape
+        if key.code != KeyCode::Esc {
+            self.last_escape_time = None;
+        }
+        if self.visual_start.is_some() && self.handle_visual_key(key) {
+            return Ok(());
+        }
+
         if let Some(action) = self.key_handler.handle(key) {
-            match action {
-                Action::Quit => {
-                    self.should_quit = true;
-                }
-                Action::ScrollUp => {
-                    self.pager.scroll_up(1);
-                }
-                Action::ScrollDown => {
-                    self.pager.scroll_down(1);
-                }
-                Action::HalfPageUp => {
-                    self.pager.scroll_up(10);
-                }
-                Action::HalfPageDown => {
-                    self.pager.scroll_down(10);
-                }
-                Action::PageUp => {
-                    self.pager.scroll_up(20);
-                }
-                Action::PageDown => {
-                    self.pager.scroll_down(20);
-                }
-                Action::Top => {
-                    self.pager.scroll_to_top();
-                }
-                Action::Bottom => {
-                    self.pager.scroll_to_bottom(20); // Will be clamped in draw
-                }
-                Action::Search => {
-                    self.state = AppState::Searching;
-                    self.search_input.clear();
-                    self.key_handler.reset_pending();
-                }
-                Action::NextMatch => {
-                    self.pager.next_match();
-                }
-                Action::PrevMatch => {
-                    self.pager.prev_match();
+            if self.diff.is_some() {
+                self.handle_diff_action(action);
+            } else if action == Action::Quit
+                && key.code == KeyCode::Esc
+                && !self.config.escape_quits
+            {
+                self.handle_unheeded_escape();
+            } else {
+                self.perform_action(action)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Carry out `action` for a key, or for a `:` command such as
+    /// `:clear-history` that no key is bound to by default
+    fn perform_action(&mut self, action: Action) -> Result<()> {
+        match action {
+            Action::Quit => {
+                self.should_quit = true;
+            }
+            Action::ScrollUp => {
+                self.pager.scroll_up(1);
+            }
+            Action::ScrollDown => {
+                self.pager.scroll_down(1);
+            }
+            Action::HalfPageUp => {
+                self.pager.scroll_up(10);
+            }
+            Action::HalfPageDown => {
+                self.pager.scroll_down(10);
+            }
+            Action::PageUp => {
+                self.pager.scroll_up(20);
+            }
+            Action::PageDown => {
+                self.pager.scroll_down(20);
+            }
+            Action::Top => {
+                self.pager.scroll_to_top();
+            }
+            Action::Bottom => {
+                self.pager.scroll_to_bottom(20); // Will be clamped in draw
+            }
+            Action::Search => {
+                self.state = AppState::Searching;
+                self.search_input.clear();
+                self.key_handler.reset_pending();
+            }
+            Action::HighlightWord => match self.pager.word_at_line(self.pager.scroll) {
+                // Like vim's `*`: search for the word, keeping the position
+                Some(word) => {
+                    self.pager.set_search(&word);
+                    self.search_input = word;
                 }
-                Action::OpenFinder => {
-                    if !self.subcommands.is_empty() {
-                        self.finder = Some(Finder::new(self.subcommands.clone()));
-                        self.state = AppState::Finding;
-                        self.key_handler.reset_pending();
-                    } else {
-                        self.error_message = Some("No subcommands found".to_string());
+                None => self.show_error("No word on this line"),
+            },
+            Action::NextMatch => {
+                self.pager.next_match();
+            }
+            Action::PrevMatch => {
+                self.pager.prev_match();
+            }
+            Action::OpenFinder => {
+                if !self.subcommands.is_empty() || self.discovery_receiver.is_some() {
+                    let mut finder = Finder::new(self.subcommands.clone());
+                    if self.discovery_receiver.is_some() {
+                        finder.set_discovering();
                     }
-                }
-                Action::OpenCommand => {
-                    self.switcher = Some(CommandSwitcher::new(self.command_history.clone()));
-                    self.state = AppState::Switching;
+                    self.finder = Some(finder);
+                    self.state = AppState::Finding;
                     self.key_handler.reset_pending();
+                } else {
+                    self.show_info("No subcommands found — try / to search within the text");
                 }
-                Action::Back => {
-                    self.go_back()?;
-                }
-                Action::ShowHelp => {
-                    self.state = AppState::Help;
-                    self.key_handler.reset_pending();
+            }
+            Action::OpenCommand => {
+                self.switcher = Some(CommandSwitcher::new(self.command_history.clone()));
+                self.state = AppState::Switching;
+                self.key_handler.reset_pending();
+            }
+            Action::Back => {
+                self.go_back()?;
+            }
+            Action::OpenParent => {
+                self.go_to_root()?;
+            }
+            Action::FetchStderr => self.switch_help_stream(),
+            Action::ToggleSource => self.toggle_source(),
+            Action::ShowHelp => {
+                self.state = AppState::Help;
+                self.key_handler.reset_pending();
+            }
+            Action::ShowStats => {
+                self.state = AppState::Stats;
+                self.key_handler.reset_pending();
+            }
+            Action::ToggleRecord => {
+                self.toggle_recording();
+            }
+            Action::PlayMacro => {
+                self.play_macro();
+            }
+            Action::VisualLine => {
+                self.visual_start = Some(self.pager.scroll);
+            }
+            Action::ToggleFold => {
+                self.pager.toggle_fold();
+            }
+            Action::FoldAll => {
+                self.pager.fold_all();
+            }
+            Action::UnfoldAll => {
+                self.pager.unfold_all();
+            }
+            Action::OpenUrl => {
+                self.open_selected_url();
+            }
+            Action::ToggleDiff => {
+                self.toggle_diff();
+            }
+            Action::Reload => {
+                self.reload_page();
+            }
+            Action::CopyCommand => {
+                self.copy_current_command();
+            }
+            Action::ShowExamples => {
+                self.show_examples();
+            }
+            Action::ExportSession => match self.export_session() {
+                Ok(path) => self.show_info(format!("Session saved to {}", tilde_path(&path))),
+                Err(e) => self.show_error(format!("Could not save session: {}", e)),
+            },
+            Action::CommandMode => {
+                self.state = AppState::Command;
+                self.command_input.clear();
+                self.key_handler.reset_pending();
+            }
+            Action::SaveSnapshot => {
+                // Ask for the name on the command line
+                self.state = AppState::Command;
+                self.command_input = "snapshot ".to_string();
+                self.key_handler.reset_pending();
+            }
+            // Asks first, so a stray key can't clear by accident
+            Action::ClearHistory => self.state = AppState::Confirm,
+            Action::EditConfig => self.pending_edit = Some(self.config.paths.config_file()),
+            Action::Redraw => self.force_clear = true,
+            Action::Suspend => {
+                if cfg!(unix) {
+                    self.pending_suspend = true;
+                } else {
+                    self.show_error("Suspending is not supported on this platform");
                 }
             }
         }
         Ok(())
     }
 
+    /// With `escape_quits` off, quit only on the second of two quick Escapes
+    fn handle_unheeded_escape(&mut self) {
+        let now = Instant::now();
+        match self.last_escape_time.take() {
+            Some(last) if now.duration_since(last) <= DOUBLE_ESCAPE_TIME => {
+                self.should_quit = true;
+            }
+            _ => self.last_escape_time = Some(now),
+        }
+    }
+
+    fn handle_command_key(&mut self, key: KeyEvent) -> Result<()> {
+        match key.code {
+            KeyCode::Esc => self.state = AppState::Paging,
+            KeyCode::Enter => {
+                self.state = AppState::Paging;
+                let input = std::mem::take(&mut self.command_input);
+                self.run_command(&input);
+            }
+            // Backspace on an empty line leaves command mode, as in vim
+            KeyCode::Backspace if self.command_input.is_empty() => self.state = AppState::Paging,
+            KeyCode::Backspace => {
+                self.command_input.pop();
+            }
+            KeyCode::Char(c) => self.command_input.push(c),
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Run a `:` command line: `:help ACTION`, `:snapshot NAME`,
+    /// `:clear-history`, `:e config` or `:e toolpack NAME`
+    fn run_command(&mut self, input: &str) {
+        let mut words = input.split_whitespace();
+        match (words.next(), words.next(), words.next()) {
+            (None, _, _) => {}
+            (Some("help"), Some(name), _) => match self.config.keys.describe_action(name) {
+                Some(description) => self.show_info(description),
+                None => self.show_error(format!("Unknown action: {}", name)),
+            },
+            (Some("help"), None, _) => self.show_error("Usage: :help ACTION (e.g. :help search)"),
+            (Some("snapshot"), Some(name), _) => match self.save_snapshot(name) {
+                Ok(_) => self.show_info(format!("Saved; reopen with helpv --restore {}", name)),
+                Err(e) => self.show_error(format!("Could not save snapshot: {}", e)),
+            },
+            (Some("snapshot"), None, _) => self.show_error("Usage: :snapshot NAME"),
+            (Some("clear-history"), _, _) => self.command_action(Action::ClearHistory),
+            (Some("q" | "quit"), None, _) => self.should_quit = true,
+            (Some("e"), Some("config"), None) => self.command_action(Action::EditConfig),
+            (Some("e"), Some("toolpack"), Some(tool)) => match check_file_name("tool pack", tool) {
+                Ok(()) => self.pending_edit = Some(self.config.paths.toolpack_file(tool)),
+                Err(e) => self.show_error(e.to_string()),
+            },
+            (Some("e"), _, _) => self.show_error("Usage: :e config or :e toolpack NAME"),
+            (Some(command), _, _) => self.show_error(format!("Unknown command: {}", command)),
+        }
+    }
+
+    /// Run `action` for a `:` command, showing any failure
+    fn command_action(&mut self, action: Action) {
+        if let Err(e) = self.perform_action(action) {
+            self.show_error(e.to_string());
+        }
+    }
+
+    /// Forget the back history and the switcher's recent commands, keeping
+    /// only the page on screen
+    fn clear_history(&mut self) {
+        self.history = History::new();
+        self.command_history = vec![self.current_command.join(" ")];
+        self.show_info("History cleared");
+        self.persist_command_history();
+    }
+
     fn handle_searching_key(&mut self, key: KeyEvent) -> Result<()> {
         match key.code {
             KeyCode::Esc => {
@@ -273,6 +1065,10 @@ impl App {
                 // Live search update
                 self.pager.set_search(&self.search_input);
             }
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.pager.case_sensitive = !self.pager.case_sensitive;
+                self.pager.set_search(&self.search_input);
+            }
             KeyCode::Char(c) => {
                 self.search_input.push(c);
                 // Live search update
@@ -293,31 +1089,97 @@ impl App {
                 FinderAction::Select => {
                     if let Some(item) = finder.selected_item() {
                         let item_clone = item.clone();
+                        self.finder_last_selected = Some(item_clone.clone());
                         self.drill_into_item(&item_clone)?;
                     }
                 }
+                FinderAction::Export => {
+                    let items = finder.filtered_items();
+                    self.export_finder_items(&items);
+                }
                 FinderAction::None => {}
             }
         }
         Ok(())
     }
 
-    fn handle_switching_key(&mut self, key: KeyEvent) -> Result<()> {
-        if let Some(ref mut switcher) = self.switcher {
-            match switcher.handle_key(key) {
-                SwitcherAction::Close => {
-                    self.switcher = None;
-                    self.state = AppState::Paging;
+    /// Write finder items as `--subcommand-list` TSV, to `finder_export_file`
+    /// if set and otherwise to the terminal once `run` can step out of the TUI
+    fn export_finder_items(&mut self, items: &[Subcommand]) {
+        let text = format_subcommand_list(items);
+        match self.config.finder_export_file {
+            Some(ref path) => match std::fs::write(path, text) {
+                Ok(()) => {
+                    let message = format!("Exported {} items to {}", items.len(), tilde_path(path));
+                    self.show_info(message);
                 }
-                SwitcherAction::Select(cmd) => {
-                    self.switch_to_command(&cmd)?;
+                Err(e) => self.show_error(format!("Could not export: {}", e)),
+            },
+            None => self.pending_export = Some(text),
+        }
+    }
+
+    fn handle_switching_key(&mut self, key: KeyEvent) -> Result<()> {
+        let Some(ref mut switcher) = self.switcher else {
+            return Ok(());
+        };
+        let action = match switcher.handle_key(key) {
+            SwitcherAction::SelectIndex(index) => match switcher.command_at(index) {
+                Some(cmd) => SwitcherAction::Select(cmd),
+                None => SwitcherAction::None,
+            },
+            action => action,
+        };
+        match action {
+            SwitcherAction::Close => {
+                self.switcher = None;
+                self.state = AppState::Paging;
+            }
+            SwitcherAction::Select(cmd) => {
+                let parts = parse_command_line(&cmd);
+                if parts.is_empty() {
+                    return Ok(());
                 }
-                SwitcherAction::None => {}
+                self.switch_to_command(&parts)?;
             }
+            SwitcherAction::Delete(cmd) => {
+                self.delete_history_entry(&cmd);
+            }
+            SwitcherAction::SelectIndex(_) | SwitcherAction::None => {}
         }
         Ok(())
     }
 
+    fn open_selected_url(&mut self) {
+        let Some(url) = self
+            .finder_last_selected
+            .as_ref()
+            .and_then(|item| item.url.clone())
+        else {
+            self.show_error("No URL available");
+            return;
+        };
+
+        match open_url(&url) {
+            Ok(()) => self.show_info(format!("Opened {}", url)),
+            Err(e) => self.show_error(format!("Could not open URL: {}", e)),
+        }
+    }
+
+    fn delete_history_entry(&mut self, cmd: &str) {
+        if self.current_command.starts_with(&parse_command_line(cmd)) {
+            self.show_error(format!("'{}' is the current command", cmd));
+            return;
+        }
+
+        self.command_history.retain(|c| c != cmd);
+        if let Some(ref mut switcher) = self.switcher {
+            switcher.remove(cmd);
+        }
+        self.show_info(format!("Removed '{}' from history", cmd));
+        self.persist_command_history();
+    }
+
     fn handle_help_key(&mut self, key: KeyEvent) -> Result<()> {
         // Any key closes the help overlay
         match key.code {
@@ -329,73 +1191,320 @@ impl App {
         Ok(())
     }
 
-    fn drill_into_item(&mut self, item: &Subcommand) -> Result<()> {
-        // Save current state to history (including content source)
-        self.history.push(
+    /// Selected line range in visual mode, from the start line to the current scroll line
+    fn visual_selection(&self) -> Option<(usize, usize)> {
+        self.visual_start.map(|start| {
+            let current = self.pager.scroll;
+            (start.min(current), start.max(current))
+        })
+    }
+
+    /// Keys specific to visual mode; returns false for keys that fall through
+    /// to normal paging (so movement extends the selection)
+    fn handle_visual_key(&mut self, key: KeyEvent) -> bool {
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => {
+                self.visual_start = None;
+                true
+            }
+            KeyCode::Char('y') => {
+                if let Some((start, end)) = self.visual_selection() {
+                    let text = self.pager.text_range(start, end);
+                    if let Err(e) = copy_to_clipboard(&text) {
+                        self.show_error(format!("Could not copy: {}", e));
+                    }
+                }
+                self.visual_start = None;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Show an error that dismisses itself after `Config::error_display_ms`
+    fn show_error(&mut self, message: impl Into<String>) {
+        self.set_error(message.into(), ErrorLevel::Transient);
+    }
+
+    /// Show an error that stays until the next keypress
+    fn show_critical_error(&mut self, message: impl Into<String>) {
+        self.set_error(message.into(), ErrorLevel::Critical);
+    }
+
+    /// Report the first key binding conflict, if any. Returns whether there was one.
+    fn show_key_warnings(&mut self) -> bool {
+        let warnings = self.key_handler.warnings().to_vec();
+        self.show_warnings(&warnings)
+    }
+
+    /// Report the first of `warnings`, if any. Returns whether there was one.
+    fn show_warnings(&mut self, warnings: &[String]) -> bool {
+        let message = match warnings {
+            [] => return false,
+            [warning] => warning.clone(),
+            [warning, rest @ ..] => format!("{} (and {} more)", warning, rest.len()),
+        };
+        self.show_error(message);
+        true
+    }
+
+    /// Show a non-error status notice that dismisses itself after two seconds
+    fn show_info(&mut self, message: impl Into<String>) {
+        self.show_info_for(message, INFO_DISPLAY_TIME);
+    }
+
+    fn show_info_for(&mut self, message: impl Into<String>, duration: Duration) {
+        self.info_message = Some((message.into(), Instant::now() + duration));
+    }
+
+    fn set_error(&mut self, message: String, level: ErrorLevel) {
+        self.error_message = Some(ErrorNotice {
+            message,
+            level,
+            shown_at: Instant::now(),
+        });
+    }
+
+    /// Re-read config.toml after it changed on disk, keeping the old config on error
+    fn reload_config(&mut self) {
+        match self.config.reload() {
+            Ok(()) => {
+                self.key_handler = KeyHandler::new(self.config.keys.clone());
+                self.key_handler.set_dead_key_timeout(Duration::from_millis(
+                    self.config.pending_key_timeout_ms,
+                ));
+                self.pager.search_context = self.config.search_context_lines;
+                self.pager.case_sensitive = self.config.search_case_sensitive;
+                let warnings = std::mem::take(&mut self.config.warnings);
+                if !self.show_warnings(&warnings) && !self.show_key_warnings() {
+                    self.show_info("Config reloaded");
+                }
+            }
+            Err(e) => self.show_error(format!("Could not reload config: {}", e)),
+        }
+    }
+
+    fn toggle_recording(&mut self) {
+        // Recorded macros can't start or stop recording themselves
+        if self.replaying {
+            return;
+        }
+
+        if self.recording {
+            // Drop the keys that stopped the recording: two for a sequence like `qq`
+            let stop_keys = match self.macro_buffer.as_slice() {
+                [.., first, second]
+                    if self.config.keys.record_macro.iter().any(|binding| {
+                        *binding == key_to_string(first) + &key_to_string(second)
+                    }) =>
+                {
+                    2
+                }
+                _ => 1,
+            };
+            let kept = self.macro_buffer.len().saturating_sub(stop_keys);
+            self.macro_buffer.truncate(kept);
+            self.recording = false;
+        } else {
+            self.macro_buffer.clear();
+            self.recording = true;
+        }
+    }
+
+    /// First key bound to play the macro back, for the recording indicator
+    fn play_macro_key(&self) -> &str {
+        self.config
+            .keys
+            .play_macro
+            .first()
+            .map_or("", String::as_str)
+    }
+
+    fn play_macro(&mut self) {
+        if self.replaying {
+            return;
+        }
+
+        if self.recording {
+            self.show_error("Cannot play a macro while recording");
+        } else if self.macro_buffer.is_empty() {
+            self.show_error("No macro recorded");
+        } else {
+            self.key_handler.reset_pending();
+            self.playback_buffer = self.macro_buffer.iter().copied().collect();
+        }
+    }
+
+    /// Save the back history plus the current page as snapshot `name`
+    fn save_snapshot(&self, name: &str) -> Result<PathBuf> {
+        let mut snapshot = self.history.clone();
+        snapshot.push(
             self.current_command.clone(),
             self.pager.scroll,
-            self.content_source,
+            self.content_source.clone(),
+            self.pager.as_plain_text(),
+            self.invoked_item.clone(),
+        );
+        snapshot.save_snapshot(&self.config.paths, name)
+    }
+
+    /// Append the tool's examples (its pack's `examples` command) to the page
+    /// and scroll to them; a second press only scrolls
+    fn show_examples(&mut self) {
+        if let Some(line) = self.pager.appended_section(EXAMPLES_TITLE) {
+            self.pager.scroll = line;
+            return;
+        }
+
+        let base_cmd = self.current_command[0].clone();
+        let Some(template) = self
+            .config
+            .toolpacks
+            .get_or_default(&base_cmd)
+            .examples
+            .clone()
+        else {
+            self.show_info(format!("No examples for {}", base_cmd));
+            return;
+        };
+        let env = self.config.get_env_overrides(&base_cmd);
+        match fetch_help_with_invoke(&base_cmd, "examples", &template, &env) {
+            Ok(text) => {
+                self.pager.scroll = self.pager.append_section(EXAMPLES_TITLE, &text);
+                self.content_stats = None;
+            }
+            Err(e) => self.show_error(format!("No examples: {}", e)),
+        }
+    }
+
+    /// Create a pager for new content with config-driven display options applied.
+    /// Drops the cached statistics, which belong to the old page.
+    fn new_pager(&mut self, content: String) -> Pager {
+        self.content_stats = None;
+        self.invoked_item = None;
+        // Keep a case sensitivity toggled with Ctrl-C while searching
+        Pager::new(content)
+            .with_search_context(self.config.search_context_lines)
+            .with_case_sensitive(self.pager.case_sensitive)
+    }
+
+    /// Statistics for the current page, computed on first use
+    pub fn content_statistics(&mut self) -> ContentStats {
+        *self
+            .content_stats
+            .get_or_insert_with(|| self.pager.statistics())
+    }
+
+    /// Mark the page just shown as opened through `invocation`. Its `--help`
+    /// and `man` pair belongs to the parent command, so only the man
+    /// formatting is kept.
+    fn show_invoked(&mut self, invocation: Invocation, man_raw: Option<String>) {
+        self.invoked_item = Some(invocation);
+        self.clear_alt_content();
+        self.start_man_formatting(man_raw);
+    }
+
+    /// Parse a page's subcommands. A man page opened through an item also
+    /// offers the pages from its SEE ALSO section.
+    fn parse_page(
+        &self,
+        content: &str,
+        source: &ContentSource,
+        invoked: bool,
+    ) -> (Vec<Subcommand>, ParseStats) {
+        let base_cmd = &self.current_command[0];
+        let (mut subcommands, stats) = parse_tool_subcommands(base_cmd, content, &self.config);
+        if invoked && *source == ContentSource::Man {
+            let see_also = parse_see_also(content, base_cmd, self.config.see_also_broad);
+            merge_discovered_items(&mut subcommands, see_also);
+        }
+        (subcommands, stats)
+    }
+
+    /// Record how the new page's subcommands were parsed, in the status bar
+    /// and the debug log
+    fn note_parse(&mut self, tool: &str, stats: ParseStats) {
+        self.parse_method = stats.match_method;
+        self.debug_log.log_parse(tool, &stats);
+    }
+
+    fn drill_into_item(&mut self, item: &Subcommand) -> Result<()> {
+        if let Some(ref template) = self.config.exec_on_select {
+            self.exec_command = Some(expand_exec_template(
+                template,
+                &self.current_command,
+                &item.name,
+            ));
+            self.should_quit = true;
+            return Ok(());
+        }
+
+        // Save current state to history (including content source)
+        self.history.push(
+            self.current_command.clone(),
+            self.pager.scroll,
+            self.content_source.clone(),
+            self.pager.as_plain_text(),
+            self.invoked_item.clone(),
         );
 
         let base_cmd = self.current_command[0].clone();
-        let is_man_invoke = item
-            .invoke_command
-            .as_ref()
-            .is_some_and(|cmd| cmd.starts_with("man "));
 
         // Check if this item has a custom invoke command
-        let result = if let Some(ref invoke_cmd) = item.invoke_command {
+        let fetch_started = Instant::now();
+        let invocation = item
+            .invoke_command
+            .as_ref()
+            .map(|invoke_command| Invocation {
+                name: item.name.clone(),
+                invoke_command: invoke_command.clone(),
+            });
+        let result = if let Some(ref invocation) = invocation {
             // Use custom invoke command (e.g., for git guides or man pages)
-            fetch_help_with_invoke(&base_cmd, &item.name, invoke_cmd).map(|text| {
-                (
-                    text,
-                    if is_man_invoke {
-                        ContentSource::Man
-                    } else {
-                        ContentSource::Help
-                    },
-                )
-            })
+            fetch_invoked(&base_cmd, invocation, &self.config)
         } else {
             // Standard subcommand navigation with thin-content upgrade
             let mut new_cmd = self.current_command.clone();
             new_cmd.push(item.name.clone());
-            fetch_best_content(&new_cmd, &self.config)
+            fetch_with_metadata(&new_cmd, &self.config)
         };
+        let fetch_time = fetch_started.elapsed();
 
         match result {
-            Ok((content, source)) => {
-                let mut subcommands = parse_subcommands(&content, &self.config);
+            Ok((content, source, metadata)) => {
+                self.report_fetch(&source, &metadata);
+                self.fetch_time = fetch_time;
+                let invoked = invocation.is_some();
+                let (subcommands, stats) = self.parse_page(&content, &source, invoked);
+                self.note_parse(&base_cmd, stats);
 
                 // If using custom invoke, we stay at the same command level
                 // Otherwise, we're drilling into a subcommand
-                if item.invoke_command.is_some() {
-                    // For custom invokes (like guides or man pages), don't change current_command
-                    // Discover man pages from SEE ALSO if this is man page content
-                    if source == ContentSource::Man {
-                        let see_also = parse_see_also(&content, &base_cmd);
-                        merge_discovered_items(&mut subcommands, see_also);
-                    }
-                } else {
+                if !invoked {
                     let mut new_cmd = self.current_command.clone();
                     new_cmd.push(item.name.clone());
                     self.current_command = new_cmd;
 
                     // Spawn background discovery for the base command
-                    self.discovery_receiver =
-                        Some(spawn_discovery(&base_cmd, &self.config.toolpacks));
+                    self.discovery_receiver = Some(spawn_discovery(&base_cmd, &self.config));
                 }
 
                 self.content_source = source;
                 self.subcommands = subcommands;
-                self.pager = Pager::new(content);
+                self.pager = self.new_pager(content);
+                self.visual_start = None;
                 self.finder = None;
                 self.state = AppState::Paging;
+
+                match invocation {
+                    Some(invocation) => self.show_invoked(invocation, metadata.man_raw),
+                    None => self.start_alt_fetch(metadata.man_raw),
+                }
             }
             Err(e) => {
                 // Restore from history on failure
                 self.history.pop();
-                self.error_message = Some(format!("Could not fetch help: {}", e));
+                self.show_critical_error(format!("Could not fetch help: {}", e));
                 self.finder = None;
                 self.state = AppState::Paging;
             }
@@ -404,58 +1513,346 @@ impl App {
         Ok(())
     }
 
+    /// The current command with each word quoted for the shell as needed
+    pub fn current_command_shell_string(&self) -> String {
+        shell_join(&self.current_command)
+    }
+
+    /// Copy the current command to the clipboard and, if `HELPV_COMMAND_FILE`
+    /// is set, write it there for a wrapping shell script to pick up
+    fn copy_current_command(&mut self) {
+        let command = self.current_command_shell_string();
+
+        if let Some(path) = std::env::var_os("HELPV_COMMAND_FILE")
+            && let Err(e) = std::fs::write(&path, format!("{}\n", command))
+        {
+            self.show_error(format!("Could not write HELPV_COMMAND_FILE: {}", e));
+            return;
+        }
+
+        match copy_to_clipboard(&command) {
+            Ok(()) => self.show_info(format!("Copied: {}", command)),
+            Err(e) => self.show_error(format!("Could not copy: {}", e)),
+        }
+    }
+
+    /// What the app was doing, for a crash report
+    pub fn crash_context(&self) -> serde_json::Value {
+        serde_json::json!({
+            "version": env!("CARGO_PKG_VERSION"),
+            "command": self.current_command,
+            "app_state": format!("{:?}", self.state),
+            "last_key": self.last_key.as_ref().map(key_to_string),
+        })
+    }
+
+    /// Scroll to a 1-based `line` and search for `query`, for `--line` and
+    /// `--search`
+    pub fn open_at(&mut self, line: Option<usize>, query: Option<&str>) {
+        if let Some(query) = query {
+            self.search_input = query.to_string();
+            self.pager.set_search(query);
+        }
+        if let Some(line) = line {
+            self.pager.scroll = line.saturating_sub(1);
+        }
+    }
+
+    /// Write an executable script that reopens helpv at the current command,
+    /// line, and search, returning its path
+    pub fn export_session(&self) -> Result<std::path::PathBuf> {
+        let path = self.config.paths.session_file(SystemTime::now());
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        let script = session_script(
+            &self.current_command,
+            self.pager.scroll + 1,
+            self.pager.search_query.as_deref(),
+        );
+        std::fs::write(&path, script)?;
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755))?;
+        }
+        Ok(path)
+    }
+
+    /// Re-fetch the current page, preferring the source it was shown from
+    fn reload_page(&mut self) {
+        let fetch_started = Instant::now();
+        // A page opened through an item is fetched the same way again
+        let invoked_item = self.invoked_item.clone();
+        let result = match invoked_item {
+            Some(ref invocation) => {
+                fetch_invoked(&self.current_command[0], invocation, &self.config)
+            }
+            None => fetch_with_source_hint(
+                &self.current_command,
+                &self.config,
+                Some(self.content_source.clone()),
+            )
+            .map(|(content, source)| (content, source, FetchMetadata::default())),
+        };
+        match result {
+            Ok((content, source, metadata)) => {
+                self.fetch_time = fetch_started.elapsed();
+                let (subcommands, stats) =
+                    self.parse_page(&content, &source, invoked_item.is_some());
+                let scroll = self.pager.scroll;
+
+                let base_cmd = self.current_command[0].clone();
+                self.subcommands = subcommands;
+                self.note_parse(&base_cmd, stats);
+                self.pager = self.new_pager(content);
+                self.pager.scroll = scroll;
+                self.visual_start = None;
+                self.content_source = source;
+                match invoked_item {
+                    Some(invocation) => self.show_invoked(invocation, metadata.man_raw),
+                    None => {
+                        self.start_alt_fetch(None);
+                        self.discovery_receiver = Some(spawn_discovery(&base_cmd, &self.config));
+                    }
+                }
+            }
+            Err(e) => self.show_error(format!("Could not reload: {}", e)),
+        }
+    }
+
+    /// Tell the user when the page's help stream was a guess, or when the
+    /// page was cut short
+    fn report_fetch(&mut self, source: &ContentSource, metadata: &FetchMetadata) {
+        if metadata.ambiguous_streams {
+            let notice = match source {
+                ContentSource::Stderr => AMBIGUOUS_STDERR_NOTICE,
+                _ => AMBIGUOUS_STDOUT_NOTICE,
+            };
+            self.show_info_for(notice, AMBIGUOUS_NOTICE_TIME);
+        } else if metadata.truncated {
+            self.show_info_for(TRUNCATED_NOTICE, AMBIGUOUS_NOTICE_TIME);
+        }
+        if metadata.truncated {
+            self.debug_log.log_warning(&format!(
+                "{} output truncated at {} bytes (max_content_bytes)",
+                source, self.config.max_content_bytes
+            ));
+        }
+    }
+
+    /// Show the other output stream of a `--help` page (F5)
+    fn switch_help_stream(&mut self) {
+        let stream = match self.content_source {
+            ContentSource::Help => HelpStream::Stderr,
+            ContentSource::Stderr => HelpStream::Stdout,
+            _ => {
+                self.show_info("Only --help pages have stdout and stderr to switch between");
+                return;
+            }
+        };
+
+        let fetch_started = Instant::now();
+        match fetch_help_stream(&self.current_command, &self.config, stream) {
+            Ok((content, source)) => self.show_refetched(content, source, fetch_started),
+            Err(e) => self.show_info(e.to_string()),
+        }
+    }
+
+    /// Show the current command's man page in place of its `--help` output,
+    /// or the reverse (Alt-M). The history is left alone since the command
+    /// hasn't changed.
+    fn toggle_source(&mut self) {
+        if self.invoked_item.is_some() {
+            // Its --help and man page would be the parent command's
+            self.show_info("Pages opened from an item have no --help/man pair");
+            return;
+        }
+        let label = match self.content_source {
+            ContentSource::Help | ContentSource::Stderr => "man",
+            ContentSource::Man => "--help",
+            _ => {
+                self.show_info("Only --help and man pages can be toggled");
+                return;
+            }
+        };
+
+        // Use the page start_alt_fetch already fetched, if it has arrived
+        let fetch_started = Instant::now();
+        let alternate = self.alt_content.take().or_else(|| {
+            fetch_alternate_content(&self.current_command, &self.content_source, &self.config)
+        });
+        match alternate {
+            Some((content, source)) => {
+                self.show_refetched(content, source, fetch_started);
+                self.show_info(format!("Switched to {}", label));
+            }
+            None => self.show_error(format!(
+                "Could not switch to {}: none found for '{}'",
+                label,
+                self.current_command.join(" ")
+            )),
+        }
+    }
+
+    /// Replace the page with another fetch of the same command
+    fn show_refetched(&mut self, content: String, source: ContentSource, fetch_started: Instant) {
+        self.fetch_time = fetch_started.elapsed();
+        let base_cmd = self.current_command[0].clone();
+        let (subcommands, stats) = parse_tool_subcommands(&base_cmd, &content, &self.config);
+
+        self.subcommands = subcommands;
+        self.note_parse(&base_cmd, stats);
+        self.pager = self.new_pager(content);
+        self.visual_start = None;
+        self.content_source = source;
+        self.start_alt_fetch(None);
+    }
+
     fn go_back(&mut self) -> Result<()> {
         if let Some(entry) = self.history.pop() {
-            match fetch_best_content(&entry.command, &self.config) {
-                Ok((content, _source)) => {
-                    let subcommands = parse_subcommands(&content, &self.config);
-                    let base_cmd = entry.command[0].clone();
-
-                    self.subcommands = subcommands;
-                    self.pager = Pager::new(content);
-                    self.pager.scroll = entry.scroll_position;
-                    self.current_command = entry.command;
-                    self.content_source = entry.source;
-
-                    // Spawn background discovery
-                    self.discovery_receiver =
-                        Some(spawn_discovery(&base_cmd, &self.config.toolpacks));
-                }
-                Err(e) => {
-                    self.error_message = Some(format!("Could not go back: {}", e));
+            // Fall back to the page as it was when we left it, so a command that
+            // has since disappeared can't strand us
+            let fetch_started = Instant::now();
+            let (content, source, metadata) = match fetch_entry(&entry, &self.config) {
+                Ok(fetched) => fetched,
+                Err(_) => {
+                    let age = entry.pushed_at.elapsed().unwrap_or_default();
+                    let cached = entry.cached_content.clone();
+                    (
+                        cached,
+                        ContentSource::Cache { age },
+                        FetchMetadata::default(),
+                    )
                 }
+            };
+            self.fetch_time = fetch_started.elapsed();
+            let from_cache = matches!(source, ContentSource::Cache { .. });
+
+            let base_cmd = entry.command[0].clone();
+            self.current_command = entry.command;
+            let invoked = entry.invoked.is_some();
+            let (subcommands, stats) = self.parse_page(&content, &source, invoked);
+
+            self.subcommands = subcommands;
+            self.note_parse(&base_cmd, stats);
+            self.pager = self.new_pager(content);
+            self.visual_start = None;
+            self.pager.scroll = entry.scroll_position;
+            self.content_source = source;
+            if let Some(notice) = cache_notice(&self.content_source) {
+                self.show_info_for(notice, CACHE_NOTICE_TIME);
             }
+            if let Some(invocation) = entry.invoked {
+                self.show_invoked(invocation, metadata.man_raw);
+            } else if from_cache {
+                self.clear_alt_content();
+            } else {
+                self.start_alt_fetch(None);
+            }
+
+            // Spawn background discovery
+            self.discovery_receiver = Some(spawn_discovery(&base_cmd, &self.config));
         }
         Ok(())
     }
 
-    fn switch_to_command(&mut self, cmd: &str) -> Result<()> {
-        let new_command = vec![cmd.to_string()];
+    /// Jump from a subcommand such as `git remote add` to its base command.
+    /// The levels in between are pushed onto the history first, so Back
+    /// returns through `git remote add`, then `git remote`.
+    fn go_to_root(&mut self) -> Result<()> {
+        if self.current_command.len() < 2 {
+            self.show_info(format!("Already at {}", self.current_command.join(" ")));
+            return Ok(());
+        }
 
-        match fetch_best_content(&new_command, &self.config) {
-            Ok((content, source)) => {
-                // Add to command history if not already present
-                if !self.command_history.contains(&cmd.to_string()) {
-                    self.command_history.push(cmd.to_string());
-                }
+        let root = vec![self.current_command[0].clone()];
+        let fetch_started = Instant::now();
+        let (content, source, metadata) = match fetch_with_metadata(&root, &self.config) {
+            Ok(fetched) => fetched,
+            Err(e) => {
+                self.show_error(format!("Could not fetch help for '{}': {}", root[0], e));
+                return Ok(());
+            }
+        };
+        self.fetch_time = fetch_started.elapsed();
+        self.push_command_levels();
+        self.report_fetch(&source, &metadata);
+
+        let (subcommands, stats) = parse_tool_subcommands(&root[0], &content, &self.config);
+        self.subcommands = subcommands;
+        self.note_parse(&root[0], stats);
+        self.pager = self.new_pager(content);
+        self.visual_start = None;
+        self.current_command = root;
+        self.content_source = source;
+        self.start_alt_fetch(metadata.man_raw);
+
+        self.discovery_receiver = Some(spawn_discovery(&self.current_command[0], &self.config));
+        Ok(())
+    }
+
+    /// Push each level of the current command below the base (`git remote`),
+    /// unless history already ends with it, then the current page itself
+    fn push_command_levels(&mut self) {
+        for depth in 2..self.current_command.len() {
+            let level = &self.current_command[..depth];
+            if self.history.current().is_some_and(|e| e.command == level) {
+                continue;
+            }
+            // Never displayed, so there is no page text to fall back on
+            self.history
+                .push(level.to_vec(), 0, ContentSource::Help, String::new(), None);
+        }
+        self.history.push(
+            self.current_command.clone(),
+            self.pager.scroll,
+            self.content_source.clone(),
+            self.pager.as_plain_text(),
+            self.invoked_item.clone(),
+        );
+    }
+
+    /// Open a command such as `["git", "commit"]` as a fresh page
+    fn switch_to_command(&mut self, cmd_parts: &[String]) -> Result<()> {
+        let new_command = cmd_parts.to_vec();
+        let cmd = cmd_parts.join(" ");
+        let base_cmd = &cmd_parts[0];
+
+        let fetch_started = Instant::now();
+        match fetch_with_metadata(&new_command, &self.config) {
+            Ok((content, source, metadata)) => {
+                self.fetch_time = fetch_started.elapsed();
+                self.report_fetch(&source, &metadata);
+                // Move to the front of the MRU command history
+                push_recent(
+                    &mut self.command_history,
+                    &cmd,
+                    self.config.command_history_max,
+                );
+                self.persist_command_history();
 
                 // Clear navigation history since we're switching to a new command
                 self.history = History::new();
 
-                let subcommands = parse_subcommands(&content, &self.config);
+                let (subcommands, stats) = parse_tool_subcommands(base_cmd, &content, &self.config);
 
                 self.subcommands = subcommands;
-                self.pager = Pager::new(content);
+                self.note_parse(base_cmd, stats);
+                self.pager = self.new_pager(content);
+                self.visual_start = None;
                 self.current_command = new_command;
                 self.content_source = source;
                 self.switcher = None;
                 self.state = AppState::Paging;
+                self.start_alt_fetch(metadata.man_raw);
 
                 // Spawn background discovery for the new command
-                self.discovery_receiver = Some(spawn_discovery(cmd, &self.config.toolpacks));
+                self.discovery_receiver = Some(spawn_discovery(base_cmd, &self.config));
             }
             Err(e) => {
-                self.error_message = Some(format!("Could not fetch help for '{}': {}", cmd, e));
+                self.show_critical_error(format!("Could not fetch help for '{}': {}", cmd, e));
                 self.switcher = None;
                 self.state = AppState::Paging;
             }
@@ -465,15 +1862,234 @@ impl App {
     }
 }
 
+/// Set `flag` when the process receives SIGHUP or SIGTERM, instead of dying
+/// with the terminal still in raw mode
+#[cfg(unix)]
+pub fn register_quit_signals(flag: &Arc<AtomicBool>) -> std::io::Result<()> {
+    use signal_hook::consts::{SIGHUP, SIGTERM};
+
+    for signal in [SIGHUP, SIGTERM] {
+        signal_hook::flag::register(signal, Arc::clone(flag))?;
+    }
+    Ok(())
+}
+
+/// Give the terminal back and stop with SIGTSTP, as Ctrl-Z does outside raw
+/// mode. Execution continues here once the shell resumes us with SIGCONT.
+#[cfg(unix)]
+fn suspend_outside_tui() -> Result<()> {
+    outside_tui(|| signal_hook::low_level::raise(signal_hook::consts::SIGTSTP))??;
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn suspend_outside_tui() -> Result<()> {
+    Ok(())
+}
+
+/// Warning for a page shown from cache, so a stale page isn't mistaken for
+/// current documentation
+/// Fetch the page an item's `invoke_command` shows, such as a man page
+/// or guide
+fn fetch_invoked(
+    base_cmd: &str,
+    invocation: &Invocation,
+    config: &Config,
+) -> Result<(String, ContentSource, FetchMetadata)> {
+    let Invocation {
+        name,
+        invoke_command,
+    } = invocation;
+    let mut env = config.get_env_overrides(base_cmd);
+    if !invoke_command.starts_with("man ") {
+        let text = fetch_help_with_invoke(base_cmd, name, invoke_command, &env)?;
+        return Ok((text, ContentSource::Help, FetchMetadata::default()));
+    }
+
+    if let Some(manpath) = config.man_search_path() {
+        env.insert("MANPATH".to_string(), manpath);
+    }
+    // Keep the bold and underline for the pager to draw
+    env.insert("MAN_KEEP_FORMATTING".to_string(), "1".to_string());
+    let raw = fetch_help_with_invoke(base_cmd, name, invoke_command, &env)?;
+    let text = strip_man_formatting(&raw);
+    let metadata = FetchMetadata {
+        man_raw: Some(raw),
+        ..Default::default()
+    };
+    Ok((text, ContentSource::Man, metadata))
+}
+
+/// Fetch a history entry's page again the way it was first opened
+fn fetch_entry(
+    entry: &HistoryEntry,
+    config: &Config,
+) -> Result<(String, ContentSource, FetchMetadata)> {
+    match entry.invoked {
+        Some(ref invocation) => fetch_invoked(&entry.command[0], invocation, config),
+        None => fetch_with_source_hint(&entry.command, config, Some(entry.source.clone()))
+            .map(|(content, source)| (content, source, FetchMetadata::default())),
+    }
+}
+
+fn cache_notice(source: &ContentSource) -> Option<String> {
+    match source {
+        ContentSource::Cache { age } => Some(format!(
+            "Showing cached content from {} ago (press r to refresh)",
+            format_age(*age)
+        )),
+        _ => None,
+    }
+}
+
+/// Fill in an `exec_on_select` template: `{cmd}` is the current command,
+/// `{base}` its first word, and `{sub}` the selected item. Each value is
+/// shell-quoted, since item names come from parsed help text and run via `sh -c`.
+fn expand_exec_template(template: &str, current_command: &[String], sub: &str) -> String {
+    use regex::Regex;
+
+    let placeholder = Regex::new(r"\{(cmd|base|sub)\}").expect("valid regex");
+    placeholder
+        .replace_all(template, |caps: &regex::Captures| match &caps[1] {
+            "cmd" => shell_join(current_command),
+            "base" => current_command.first().map_or(String::new(), |base| {
+                shell_escape::escape(base.into()).into_owned()
+            }),
+            _ => shell_escape::escape(sub.into()).into_owned(),
+        })
+        .into_owned()
+}
+
+/// Join command words into a string that is safe to paste into a shell
+fn shell_join(parts: &[String]) -> String {
+    parts
+        .iter()
+        .map(|part| shell_escape::escape(part.into()))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Shell script that reopens helpv at `command`, `line`, and `search`
+fn session_script(command: &[String], line: usize, search: Option<&str>) -> String {
+    let mut args = vec!["helpv".to_string()];
+    args.extend(command.iter().cloned());
+    args.push("--line".to_string());
+    args.push(line.to_string());
+    if let Some(query) = search {
+        args.push("--search".to_string());
+        args.push(query.to_string());
+    }
+    format!("#!/bin/sh\n{}\n", shell_join(&args))
+}
+
+/// Split a command typed into the switcher, e.g. `"git commit"`, into its words
+fn parse_command_line(cmd: &str) -> Vec<String> {
+    cmd.split_whitespace().map(str::to_string).collect()
+}
+
+/// Move `cmd` to the front of a most-recently-used list, keeping at most `max` entries
+fn push_recent(history: &mut Vec<String>, cmd: &str, max: usize) {
+    history.retain(|c| c != cmd);
+    history.insert(0, cmd.to_string());
+    history.truncate(max.max(1));
+}
+
+/// Leave raw mode and the alternate screen, run `f` on the normal terminal,
+/// and return to the TUI whether or not `f` succeeded
+fn outside_tui<T>(f: impl FnOnce() -> T) -> Result<T> {
+    use crossterm::{
+        execute,
+        terminal::{EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode},
+    };
+
+    let mut stdout = std::io::stdout();
+    disable_raw_mode()?;
+    execute!(stdout, LeaveAlternateScreen)?;
+    let result = f();
+    execute!(stdout, EnterAlternateScreen)?;
+    enable_raw_mode()?;
+    Ok(result)
+}
+
+/// Leave the alternate screen, print `text` to the normal screen (where it
+/// stays in the scrollback), and return to the TUI
+fn print_outside_tui(text: &str) -> Result<()> {
+    use std::io::Write;
+
+    outside_tui(|| {
+        let mut stdout = std::io::stdout();
+        stdout.write_all(text.as_bytes())?;
+        stdout.flush()
+    })??;
+    Ok(())
+}
+
+/// Suspend the TUI to edit `path` in `$VISUAL`, `$EDITOR` or `vi`, creating
+/// its directory first so the editor can save a new file
+fn edit_outside_tui(path: &Path) -> Result<()> {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    let editor = editor_command(
+        std::env::var("VISUAL").ok().as_deref(),
+        std::env::var("EDITOR").ok().as_deref(),
+    );
+
+    let status = outside_tui(|| {
+        std::process::Command::new(&editor[0])
+            .args(&editor[1..])
+            .arg(path)
+            .status()
+    })?;
+    let status = status.map_err(|e| anyhow::anyhow!("could not run {}: {}", editor[0], e))?;
+    if !status.success() {
+        return Err(anyhow::anyhow!("{} exited with {}", editor[0], status));
+    }
+    Ok(())
+}
+
+/// The editor to run, split into words so `EDITOR="code -w"` works.
+/// Unset or blank variables are skipped.
+fn editor_command(visual: Option<&str>, editor: Option<&str>) -> Vec<String> {
+    [visual, editor]
+        .into_iter()
+        .flatten()
+        .map(|cmd| {
+            cmd.split_whitespace()
+                .map(str::to_string)
+                .collect::<Vec<_>>()
+        })
+        .find(|words| !words.is_empty())
+        .unwrap_or_else(|| vec!["vi".to_string()])
+}
+
+/// Format subcommands as tab-separated lines: `name\tdescription\tlabel\tinvoke_command`.
+/// Absent fields are left empty.
+pub fn format_subcommand_list(subcommands: &[Subcommand]) -> String {
+    subcommands
+        .iter()
+        .map(|s| {
+            format!(
+                "{}\t{}\t{}\t{}\n",
+                s.name,
+                s.description.as_deref().unwrap_or(""),
+                s.label.as_deref().unwrap_or(""),
+                s.invoke_command.as_deref().unwrap_or("")
+            )
+        })
+        .collect()
+}
+
 /// Spawn a background thread that runs both discovery sources (toolpacks + man -k)
 /// and sends the combined results back via a channel.
-fn spawn_discovery(base_cmd: &str, toolpacks: &ToolPacks) -> mpsc::Receiver<Vec<Subcommand>> {
+fn spawn_discovery(base_cmd: &str, config: &Config) -> mpsc::Receiver<Vec<Subcommand>> {
     let (tx, rx) = mpsc::channel();
     let base_cmd = base_cmd.to_string();
-    let toolpacks = toolpacks.clone();
+    let toolpacks = config.toolpacks.clone();
+    let manpath = config.man_search_path();
 
     std::thread::spawn(move || {
-        let results = run_discovery(&base_cmd, &toolpacks);
+        let results = run_discovery(&base_cmd, &toolpacks, manpath.as_deref());
         // Send silently fails if receiver was dropped (e.g. user navigated away) — that's fine
         let _ = tx.send(results);
     });
@@ -482,12 +2098,12 @@ fn spawn_discovery(base_cmd: &str, toolpacks: &ToolPacks) -> mpsc::Receiver<Vec<
 }
 
 /// Run both discovery sources in parallel using scoped threads.
-fn run_discovery(base_cmd: &str, toolpacks: &ToolPacks) -> Vec<Subcommand> {
+fn run_discovery(base_cmd: &str, toolpacks: &ToolPacks, manpath: Option<&str>) -> Vec<Subcommand> {
     let mut all = Vec::new();
 
     std::thread::scope(|s| {
         let toolpack_handle = s.spawn(|| discover_items(base_cmd, toolpacks));
-        let man_handle = s.spawn(|| discover_man_pages(base_cmd));
+        let man_handle = s.spawn(|| discover_man_pages(base_cmd, manpath));
 
         if let Ok(items) = toolpack_handle.join() {
             all.extend(items);
@@ -508,56 +2124,105 @@ fn discover_items(base_cmd: &str, toolpacks: &ToolPacks) -> Vec<Subcommand> {
 
     pack.discover_items(base_cmd)
         .into_iter()
-        .map(|item| Subcommand {
-            name: item.name,
-            description: item.description,
-            label: Some(item.label),
-            invoke_command: Some(item.invoke_template),
-        })
+        .map(discovered_to_subcommand)
         .collect()
 }
 
-/// Discover man pages matching `<base>-*` via `man -k`
-fn discover_man_pages(base_cmd: &str) -> Vec<Subcommand> {
-    use regex::Regex;
-    use std::process::Command;
-
-    let pattern = format!("^{}-", regex::escape(base_cmd));
-    let Ok(output) = Command::new("man").args(["-k", &pattern]).output() else {
-        return Vec::new();
+/// A web-link invoke becomes the item's URL rather than a command to run
+fn discovered_to_subcommand(item: DiscoveredItem) -> Subcommand {
+    let is_url =
+        item.invoke_template.starts_with("https://") || item.invoke_template.starts_with("http://");
+    let (invoke_command, url) = if is_url {
+        (
+            None,
+            Some(item.invoke_template.replace("{name}", &item.name)),
+        )
+    } else {
+        (Some(item.invoke_template), None)
     };
 
-    if !output.status.success() {
-        return Vec::new();
+    Subcommand {
+        name: item.name,
+        description: item.description,
+        label: Some(item.label),
+        invoke_command,
+        url,
+        aliases: Vec::new(),
+        dedupe_strategy: item.dedupe_strategy,
+        confidence: 1.0,
     }
+}
 
-    let text = String::from_utf8_lossy(&output.stdout);
-    // man -k output format: "name (section) - description" or "name(section) - description"
-    let entry_re = Regex::new(r"^([\w][\w.-]*)\s*\(\d+\)\s*-\s*(.*)$").unwrap();
+/// Discover man pages matching `<base>-*` via `man -k`, searching `manpath`
+/// (see `Config::man_search_path`) when given. Systems without `man -k` or an
+/// initialized man database fall back to `apropos`, then `whatis -w`.
+fn discover_man_pages(base_cmd: &str, manpath: Option<&str>) -> Vec<Subcommand> {
+    let pattern = format!("^{}-", regex::escape(base_cmd));
+    let glob = format!("{}-*", base_cmd);
+    let lookups: [(&str, &[&str]); 3] = [
+        ("man", &["-k", &pattern]),
+        ("apropos", &[&pattern]),
+        ("whatis", &["-w", &glob]),
+    ];
+
+    lookups
+        .iter()
+        .find_map(|(program, args)| run_man_index(program, args, manpath))
+        .map(|text| parse_man_index(&text, base_cmd))
+        .unwrap_or_default()
+}
 
-    text.lines()
-        .filter_map(|line| {
-            let caps = entry_re.captures(line.trim())?;
-            let name = caps.get(1)?.as_str().to_string();
-            let description = caps.get(2).map(|m| m.as_str().trim().to_string());
+/// Stdout of a man database lookup, or `None` if it couldn't run or failed
+fn run_man_index(program: &str, args: &[&str], manpath: Option<&str>) -> Option<String> {
+    let mut command = child_command(program);
+    command.args(args);
+    if let Some(manpath) = manpath {
+        command.env("MANPATH", manpath);
+    }
+    let output = command.output().ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).into_owned())
+}
 
+/// Parse `man -k`/`apropos`/`whatis` output into the `<base>-*` pages it lists
+fn parse_man_index(text: &str, base_cmd: &str) -> Vec<Subcommand> {
+    use regex::Regex;
+    // "name (section) - description" or "name(section) - description"; BSD
+    // apropos lists every name of a page: "name1, name2(1) - description"
+    let entry_re = Regex::new(r"^([\w][\w.,\s-]*?)\s*\(\d\w*\)\s*-\s*(.*)$").unwrap();
+    let prefix = format!("{}-", base_cmd);
+
+    let mut pages: Vec<Subcommand> = Vec::new();
+    for line in text.lines() {
+        let Some(caps) = entry_re.captures(line.trim()) else {
+            continue;
+        };
+        let description = caps.get(2).map(|m| m.as_str().trim().to_string());
+        for name in caps[1].split(',').map(str::trim) {
             // Only include pages that start with base_cmd-
-            if !name.starts_with(&format!("{}-", base_cmd)) {
-                return None;
+            if !name.starts_with(&prefix) || pages.iter().any(|page| page.name == name) {
+                continue;
             }
-
-            Some(Subcommand {
-                name: name.clone(),
-                description,
+            pages.push(Subcommand {
+                name: name.to_string(),
+                description: description.clone(),
                 label: Some("Man Pages".to_string()),
                 invoke_command: Some(format!("man {}", name)),
-            })
-        })
-        .collect()
+                url: None,
+                aliases: Vec::new(),
+                dedupe_strategy: DedupeStrategy::Name,
+                confidence: 1.0,
+            });
+        }
+    }
+    pages
 }
 
-/// Parse SEE ALSO section from man page content to discover related pages
-fn parse_see_also(content: &str, base_cmd: &str) -> Vec<Subcommand> {
+/// Parse SEE ALSO section from man page content to discover related pages.
+/// With `broad`, pages for other tools are included too, labeled "See Also".
+fn parse_see_also(content: &str, base_cmd: &str, broad: bool) -> Vec<Subcommand> {
     use regex::Regex;
 
     let mut in_see_also = false;
@@ -594,34 +2259,57 @@ fn parse_see_also(content: &str, base_cmd: &str) -> Vec<Subcommand> {
         .captures_iter(&see_also_text)
         .filter_map(|caps| {
             let name = caps.get(1)?.as_str().to_string();
-            // Only include pages related to the base command
-            if !name.starts_with(&prefix) && name != base_cmd {
-                return None;
-            }
             // Skip the base command itself
             if name == base_cmd {
                 return None;
             }
+            // Only include pages related to the base command unless broad
+            let label = if name.starts_with(&prefix) {
+                "Man Pages"
+            } else if broad {
+                "See Also"
+            } else {
+                return None;
+            };
             Some(Subcommand {
                 name: name.clone(),
                 description: None,
-                label: Some("Man Pages".to_string()),
+                label: Some(label.to_string()),
                 invoke_command: Some(format!("man {}", name)),
+                url: None,
+                aliases: Vec::new(),
+                dedupe_strategy: DedupeStrategy::Name,
+                confidence: 1.0,
             })
         })
         .collect()
 }
 
+/// Notice shown when background discovery adds `added` subcommands
+fn discovery_notice(added: usize) -> String {
+    match added {
+        1 => "Discovered 1 new subcommand — press f to browse".to_string(),
+        n => format!("Discovered {} new subcommands — press f to browse", n),
+    }
+}
+
 /// Merge discovered items into the subcommands list, avoiding duplicates
+/// according to each item's dedupe strategy
 fn merge_discovered_items(subcommands: &mut Vec<Subcommand>, discovered: Vec<Subcommand>) {
     for item in discovered {
-        // Skip if there's already a subcommand with this name
-        if !subcommands.iter().any(|s| s.name == item.name) {
+        if !subcommands.iter().any(|s| is_duplicate(&item, s)) {
             subcommands.push(item);
         }
     }
 }
 
+fn is_duplicate(item: &Subcommand, existing: &Subcommand) -> bool {
+    item.dedupe_strategy.is_duplicate(
+        (&item.name, item.invoke_command.as_deref()),
+        (&existing.name, existing.invoke_command.as_deref()),
+    )
+}
+
 struct Dim;
 
 impl Widget for Dim {
@@ -634,7 +2322,8 @@ impl Widget for Dim {
     }
 }
 
-struct ErrorMessage<'a>(&'a str);
+/// Error line with an optional auto-dismiss countdown shown on the right
+struct ErrorMessage<'a>(&'a str, Option<Duration>);
 
 impl Widget for ErrorMessage<'_> {
     fn render(self, area: Rect, buf: &mut Buffer) {
@@ -649,19 +2338,1253 @@ impl Widget for ErrorMessage<'_> {
             buf[(x, area.y)].set_char(' ');
         }
 
-        let msg = format!(" Error: {} ", self.0);
-        let span = Span::styled(msg, style);
+        let span = Span::styled(format!(" Error: {} ", self.0), style);
         buf.set_span(area.x, area.y, &span, area.width);
+
+        if let Some(remaining) = self.1 {
+            // Round up so the countdown never shows [0s] while still visible
+            let countdown = format!("[{}s] ", remaining.as_millis().div_ceil(1000));
+            let x = area.right().saturating_sub(countdown.len() as u16);
+            buf.set_span(x, area.y, &Span::styled(countdown, style), area.width);
+        }
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    // ========================================
-    // parse_see_also tests
-    // ========================================
+/// One line of text in the middle of the area, cut to fit
+struct CenteredNotice<'a>(&'a str);
+
+impl Widget for CenteredNotice<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        use ratatui::style::Style;
+        use ratatui::text::Span;
+
+        if area.is_empty() {
+            return;
+        }
+        let width = (self.0.chars().count() as u16).min(area.width);
+        let x = area.x + (area.width - width) / 2;
+        let y = area.y + area.height / 2;
+        let style = Style::default().fg(Color::Yellow);
+        buf.set_span(x, y, &Span::styled(self.0, style), width);
+    }
+}
+
+/// Status notice line, styled apart from errors
+struct InfoMessage<'a>(&'a str);
+
+impl Widget for InfoMessage<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        use ratatui::style::{Color, Style};
+        use ratatui::text::Span;
+
+        let style = Style::default().fg(Color::White).bg(Color::Blue);
+
+        for x in area.left()..area.right() {
+            buf[(x, area.y)].set_style(style);
+            buf[(x, area.y)].set_char(' ');
+        }
+
+        let span = Span::styled(format!(" {} ", self.0), style);
+        buf.set_span(area.x, area.y, &span, area.width);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // ========================================
+    // format_subcommand_list tests
+    // ========================================
+
+    #[test]
+    fn subcommand_list_tab_separated() {
+        let subs = vec![
+            Subcommand {
+                name: "build".to_string(),
+                description: Some("Compile the project".to_string()),
+                label: None,
+                invoke_command: None,
+                url: None,
+                aliases: Vec::new(),
+                dedupe_strategy: DedupeStrategy::Name,
+                confidence: 1.0,
+            },
+            Subcommand {
+                name: "git-log".to_string(),
+                description: None,
+                label: Some("Man Pages".to_string()),
+                invoke_command: Some("man git-log".to_string()),
+                url: None,
+                aliases: Vec::new(),
+                dedupe_strategy: DedupeStrategy::Name,
+                confidence: 1.0,
+            },
+        ];
+        let output = format_subcommand_list(&subs);
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(lines[0], "build\tCompile the project\t\t");
+        assert_eq!(lines[1], "git-log\t\tMan Pages\tman git-log");
+    }
+
+    // ========================================
+    // discovered_to_subcommand tests
+    // ========================================
+
+    fn discovered_item(invoke: &str) -> DiscoveredItem {
+        DiscoveredItem {
+            name: "serde".to_string(),
+            description: None,
+            label: "Crates".to_string(),
+            invoke_template: invoke.to_string(),
+            dedupe_strategy: DedupeStrategy::Name,
+        }
+    }
+
+    #[test]
+    fn url_invoke_becomes_url() {
+        let sub = discovered_to_subcommand(discovered_item("https://docs.rs/{name}"));
+        assert_eq!(sub.url.as_deref(), Some("https://docs.rs/serde"));
+        assert!(sub.invoke_command.is_none());
+    }
+
+    #[test]
+    fn command_invoke_has_no_url() {
+        let sub = discovered_to_subcommand(discovered_item("cargo help {name}"));
+        assert_eq!(sub.invoke_command.as_deref(), Some("cargo help {name}"));
+        assert!(sub.url.is_none());
+    }
+
+    // ========================================
+    // merge_discovered_items tests
+    // ========================================
+
+    fn discovered(name: &str, invoke: &str, strategy: DedupeStrategy) -> Subcommand {
+        Subcommand {
+            name: name.to_string(),
+            description: None,
+            label: Some("Test".to_string()),
+            invoke_command: Some(invoke.to_string()),
+            url: None,
+            aliases: Vec::new(),
+            dedupe_strategy: strategy,
+            confidence: 1.0,
+        }
+    }
+
+    #[test]
+    fn merge_dedupes_by_name_by_default() {
+        let mut subs = vec![discovered("log", "git help {name}", DedupeStrategy::Name)];
+        merge_discovered_items(
+            &mut subs,
+            vec![
+                discovered("log", "man git-log", DedupeStrategy::Name),
+                discovered("diff", "man git-diff", DedupeStrategy::Name),
+            ],
+        );
+        let names: Vec<&str> = subs.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["log", "diff"]);
+    }
+
+    #[test]
+    fn merge_dedupes_by_invoke() {
+        let mut subs = vec![discovered("ci", "tool commit", DedupeStrategy::Name)];
+        merge_discovered_items(
+            &mut subs,
+            vec![
+                discovered("commit", "tool commit", DedupeStrategy::Invoke),
+                discovered("ci", "tool checkin", DedupeStrategy::Invoke),
+            ],
+        );
+        let names: Vec<&str> = subs.iter().map(|s| s.name.as_str()).collect();
+        // Same invoke is a duplicate; same name with a different invoke is kept
+        assert_eq!(names, vec!["ci", "ci"]);
+    }
+
+    #[test]
+    fn merge_invoke_compares_resolved_templates() {
+        let mut subs = vec![discovered("log", "git help {name}", DedupeStrategy::Name)];
+        merge_discovered_items(
+            &mut subs,
+            vec![discovered("log", "git help log", DedupeStrategy::Invoke)],
+        );
+        assert_eq!(subs.len(), 1);
+    }
+
+    #[test]
+    fn merge_dedupes_by_name_and_invoke() {
+        let mut subs = vec![discovered("log", "git help log", DedupeStrategy::Name)];
+        merge_discovered_items(
+            &mut subs,
+            vec![
+                discovered("log", "man git-log", DedupeStrategy::NameAndInvoke),
+                discovered("log", "git help log", DedupeStrategy::NameAndInvoke),
+            ],
+        );
+        assert_eq!(subs.len(), 2);
+        assert_eq!(subs[1].invoke_command.as_deref(), Some("man git-log"));
+    }
+
+    /// An app whose background discovery will deliver `items`
+    fn app_discovering(items: Vec<Subcommand>) -> App {
+        let mut app = App::with_content(
+            vec!["helpv-test-tool".to_string()],
+            Config::default(),
+            "usage: helpv-test-tool".to_string(),
+            ContentSource::Help,
+            Duration::ZERO,
+        );
+        let (tx, rx) = mpsc::channel();
+        tx.send(items).unwrap();
+        app.discovery_receiver = Some(rx);
+        app
+    }
+
+    #[test]
+    fn finished_discovery_announces_new_items() {
+        let mut app = app_discovering(vec![
+            discovered("log", "man git-log", DedupeStrategy::Name),
+            discovered("diff", "man git-diff", DedupeStrategy::Name),
+        ]);
+        app.poll_discovery();
+        assert!(app.discovery_receiver.is_none());
+        assert_eq!(
+            app.info_message.as_ref().map(|(m, _)| m.as_str()),
+            Some("Discovered 2 new subcommands — press f to browse")
+        );
+    }
+
+    #[test]
+    fn discovery_without_new_items_stays_quiet() {
+        let mut app = app_discovering(Vec::new());
+        app.poll_discovery();
+        assert!(app.info_message.is_none());
+    }
+
+    #[test]
+    fn discovery_notice_is_singular_for_one_item() {
+        assert_eq!(
+            discovery_notice(1),
+            "Discovered 1 new subcommand — press f to browse"
+        );
+    }
+
+    // ========================================
+    // CenteredNotice tests
+    // ========================================
+
+    fn render_notice(text: &str, width: u16, height: u16) -> Vec<String> {
+        let area = Rect::new(0, 0, width, height);
+        let mut buf = Buffer::empty(area);
+        CenteredNotice(text).render(area, &mut buf);
+        (0..height)
+            .map(|y| (0..width).map(|x| buf[(x, y)].symbol()).collect())
+            .collect()
+    }
+
+    #[test]
+    fn centered_notice_sits_in_the_middle() {
+        let lines = render_notice("too small", 13, 3);
+        assert_eq!(
+            lines,
+            vec!["             ", "  too small  ", "             "]
+        );
+    }
+
+    #[test]
+    fn centered_notice_cuts_text_to_fit() {
+        assert_eq!(render_notice("Terminal too small", 8, 1), vec!["Terminal"]);
+        assert!(render_notice("Terminal too small", 0, 0).is_empty());
+    }
+
+    // ========================================
+    // cache_notice tests
+    // ========================================
+
+    #[test]
+    fn cache_notice_only_for_cached_pages() {
+        let cached = ContentSource::Cache {
+            age: Duration::from_secs(3 * 3600),
+        };
+        assert_eq!(
+            cache_notice(&cached).as_deref(),
+            Some("Showing cached content from 3h ago (press r to refresh)")
+        );
+        assert_eq!(cache_notice(&ContentSource::Help), None);
+    }
+
+    // ========================================
+    // Signal handling tests
+    // ========================================
+
+    #[cfg(unix)]
+    #[test]
+    fn sigterm_ends_run() {
+        let mut app = app_at(&["helpv-test-tool"]);
+        let mut terminal = ratatui::Terminal::new(TestBackend::new(40, 10)).unwrap();
+        register_quit_signals(&app.signal_quit).unwrap();
+
+        // The handler stays registered, so the test process only sets the flag
+        signal_hook::low_level::raise(signal_hook::consts::SIGTERM).unwrap();
+        assert!(app.signal_quit.load(Ordering::Relaxed));
+
+        app.run(&mut terminal).unwrap();
+    }
+
+    // ========================================
+    // Macro tests
+    // ========================================
+
+    #[test]
+    fn stopping_a_recording_drops_the_whole_stop_sequence() {
+        let key = |c| KeyEvent::from(KeyCode::Char(c));
+        let mut app = app_at(&["helpv-test-tool"]);
+        app.config.keys.record_macro = vec!["Q".to_string(), "qq".to_string()];
+
+        app.recording = true;
+        app.macro_buffer = vec![key('j'), key('Q')];
+        app.toggle_recording();
+        assert_eq!(app.macro_buffer, vec![key('j')]);
+
+        app.recording = true;
+        app.macro_buffer = vec![key('j'), key('q'), key('q')];
+        app.toggle_recording();
+        assert_eq!(app.macro_buffer, vec![key('j')]);
+    }
+
+    #[test]
+    fn recording_indicator_names_the_play_key() {
+        let mut app = render_app(SNAPSHOT_HELP);
+        app.config.keys.play_macro = vec!["@a".to_string()];
+        app.recording = true;
+        assert!(app.render_to_string(100, 10).contains("recording @a"));
+    }
+
+    // ========================================
+    // Command history tests
+    // ========================================
+
+    fn history(cmds: &[&str]) -> Vec<String> {
+        cmds.iter().map(|c| c.to_string()).collect()
+    }
+
+    #[test]
+    fn push_recent_adds_to_front() {
+        let mut h = history(&["git"]);
+        push_recent(&mut h, "cargo", 100);
+        assert_eq!(h, history(&["cargo", "git"]));
+    }
+
+    #[test]
+    fn push_recent_moves_existing_to_front() {
+        let mut h = history(&["cargo", "git", "docker"]);
+        push_recent(&mut h, "docker", 100);
+        assert_eq!(h, history(&["docker", "cargo", "git"]));
+    }
+
+    #[test]
+    fn push_recent_truncates_oldest() {
+        let mut h = history(&["cargo", "git"]);
+        push_recent(&mut h, "docker", 2);
+        assert_eq!(h, history(&["docker", "cargo"]));
+    }
+
+    #[test]
+    fn exec_template_substitutes_placeholders() {
+        let current = history(&["git", "remote"]);
+        assert_eq!(
+            expand_exec_template("{base} {sub} --help | bat", &current, "add"),
+            "git add --help | bat"
+        );
+        assert_eq!(
+            expand_exec_template("{cmd} {sub} -h", &current, "add"),
+            "git remote add -h"
+        );
+    }
+
+    #[test]
+    fn exec_template_quotes_substitutions() {
+        let current = history(&["my tool"]);
+        assert_eq!(
+            expand_exec_template("{base} {sub} --help", &current, "foo; rm -rf ~"),
+            "'my tool' 'foo; rm -rf ~' --help"
+        );
+        assert_eq!(
+            expand_exec_template("{cmd} {sub}", &current, "$(touch pwned)"),
+            "'my tool' '$(touch pwned)'"
+        );
+        // A placeholder inside a substituted value is not expanded again
+        assert_eq!(
+            expand_exec_template("{sub} {cmd}", &current, "{cmd}"),
+            "'{cmd}' 'my tool'"
+        );
+    }
+
+    #[test]
+    fn session_script_reopens_command_at_line() {
+        assert_eq!(
+            session_script(&history(&["git", "commit"]), 42, None),
+            "#!/bin/sh\nhelpv git commit --line 42\n"
+        );
+    }
+
+    #[test]
+    fn session_script_quotes_search() {
+        assert_eq!(
+            session_script(&history(&["git", "log"]), 1, Some("--since date")),
+            "#!/bin/sh\nhelpv git log --line 1 --search '--since date'\n"
+        );
+    }
+
+    #[test]
+    fn shell_join_quotes_only_when_needed() {
+        assert_eq!(shell_join(&history(&["git", "log"])), "git log");
+        assert_eq!(
+            shell_join(&history(&["grep", "two words", "it's"])),
+            r#"grep 'two words' 'it'\''s'"#
+        );
+    }
+
+    #[test]
+    fn parse_command_line_splits_words() {
+        assert_eq!(
+            parse_command_line("  git   commit "),
+            history(&["git", "commit"])
+        );
+        assert_eq!(parse_command_line("cargo"), history(&["cargo"]));
+        assert!(parse_command_line("   ").is_empty());
+    }
+
+    #[test]
+    fn subcommand_list_empty() {
+        assert_eq!(format_subcommand_list(&[]), "");
+    }
+
+    // ========================================
+    // Key conflict warning tests
+    // ========================================
+
+    #[test]
+    fn conflicting_keys_are_reported_at_startup() {
+        let mut config = Config::default();
+        config.keys.apply_defaults();
+        config
+            .keys
+            .search
+            .extend(["q".to_string(), "f".to_string()]);
+        let app = App::with_content(
+            vec!["helpv-test-tool".to_string()],
+            config,
+            "usage: helpv-test-tool".to_string(),
+            ContentSource::Help,
+            Duration::ZERO,
+        );
+
+        let notice = app.error_message.expect("conflict should be reported");
+        assert_eq!(
+            notice.message,
+            "Key 'q' is bound to both quit and search; quit wins (and 1 more)"
+        );
+    }
+
+    // ========================================
+    // Help stream tests
+    // ========================================
+
+    #[test]
+    fn ambiguous_streams_are_reported() {
+        let mut app = app_at(&["helpv-test-tool"]);
+        let ambiguous = FetchMetadata {
+            ambiguous_streams: true,
+            ..Default::default()
+        };
+        app.report_fetch(&ContentSource::Help, &ambiguous);
+        assert_eq!(
+            app.info_message.as_ref().map(|(m, _)| m.as_str()),
+            Some(AMBIGUOUS_STDOUT_NOTICE)
+        );
+
+        app.report_fetch(&ContentSource::Stderr, &ambiguous);
+        assert_eq!(
+            app.info_message.as_ref().map(|(m, _)| m.as_str()),
+            Some(AMBIGUOUS_STDERR_NOTICE)
+        );
+
+        app.info_message = None;
+        app.report_fetch(&ContentSource::Help, &FetchMetadata::default());
+        assert!(app.info_message.is_none());
+    }
+
+    #[test]
+    fn truncated_content_is_reported() {
+        let dir = std::env::temp_dir().join(format!("helpv-truncated-{}", std::process::id()));
+        let log = dir.join("debug.jsonl");
+        let mut app = app_at(&["helpv-test-tool"]);
+        app.debug_log = DebugLogger::new(Some(log.clone()));
+        let truncated = FetchMetadata {
+            truncated: true,
+            ..Default::default()
+        };
+        app.report_fetch(&ContentSource::Man, &truncated);
+        assert_eq!(
+            app.info_message.as_ref().map(|(m, _)| m.as_str()),
+            Some(TRUNCATED_NOTICE)
+        );
+
+        let text = std::fs::read_to_string(&log).unwrap();
+        let entry: serde_json::Value = serde_json::from_str(text.trim()).unwrap();
+        assert!(entry["warning"].as_str().unwrap().contains("truncated"));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn man_formatting_reuses_fetched_output() {
+        // No `helpv-test-tool` man page exists, so the formatting can only
+        // come from the output the fetch kept
+        let raw = "N\x08NA\x08AM\x08ME\x08E\n       helpv-test-tool";
+        let metadata = FetchMetadata {
+            man_raw: Some(raw.to_string()),
+            ..Default::default()
+        };
+        let content = strip_man_formatting(raw);
+        let mut app = App::with_fetched(
+            history(&["helpv-test-tool"]),
+            Config::default(),
+            (content, ContentSource::Man, metadata),
+            Duration::ZERO,
+        );
+
+        let rx = app.man_formatting_receiver.take().unwrap();
+        let formatting = rx.recv_timeout(Duration::from_secs(5)).unwrap();
+        assert_eq!(formatting.len(), 1);
+        assert_eq!(formatting[&0], "N\x08NA\x08AM\x08ME\x08E");
+    }
+
+    #[test]
+    fn switching_streams_needs_a_help_page() {
+        let mut app = App::with_content(
+            history(&["helpv-test-tool"]),
+            Config::default(),
+            "HELPV-TEST-TOOL(1)".to_string(),
+            ContentSource::Man,
+            Duration::ZERO,
+        );
+        app.switch_help_stream();
+        assert_eq!(app.content_source, ContentSource::Man);
+        assert!(app.info_message.is_some());
+    }
+
+    #[test]
+    fn toggling_source_needs_a_help_or_man_page() {
+        let mut app = App::with_content(
+            history(&["helpv-test-tool"]),
+            Config::default(),
+            "helpv-test-tool: does things".to_string(),
+            ContentSource::Tldr,
+            Duration::ZERO,
+        );
+        app.toggle_source();
+        assert_eq!(app.content_source, ContentSource::Tldr);
+        assert!(app.info_message.is_some());
+    }
+
+    #[test]
+    fn toggling_source_keeps_page_when_fetch_fails() {
+        let config = Config {
+            disable_help_fallback: true,
+            ..Default::default()
+        };
+        let mut app = App::with_content(
+            history(&["helpv-test-tool"]),
+            config,
+            "HELPV-TEST-TOOL(1)".to_string(),
+            ContentSource::Man,
+            Duration::ZERO,
+        );
+        app.toggle_source();
+
+        assert_eq!(app.content_source, ContentSource::Man);
+        assert_eq!(app.pager.content[0], "HELPV-TEST-TOOL(1)");
+        assert!(app.history.is_empty());
+        let error = app.error_message.expect("failure should be reported");
+        assert!(error.message.starts_with("Could not switch to --help"));
+    }
+
+    #[test]
+    fn toggling_source_uses_prefetched_page() {
+        // No `helpv-test-tool` man page exists, so the page can only come
+        // from the prefetched alternate
+        let mut app = app_at(&["helpv-test-tool"]);
+        app.alt_content = Some(("HELPV-TEST-TOOL(1)".to_string(), ContentSource::Man));
+        app.toggle_source();
+
+        assert!(app.error_message.is_none());
+        assert_eq!(app.content_source, ContentSource::Man);
+        assert_eq!(app.pager.content[0], "HELPV-TEST-TOOL(1)");
+    }
+
+    #[test]
+    fn toggling_source_is_refused_on_invoked_pages() {
+        let mut app = app_at(&["helpv-test-tool"]);
+        app.drill_into_item(&guide("guide")).unwrap();
+        app.alt_content = Some(("HELPV-TEST-TOOL(1)".to_string(), ContentSource::Man));
+        app.toggle_source();
+
+        assert_eq!(app.pager.content[0], "the guide page for helpv-test-tool");
+        assert!(app.info_message.is_some());
+    }
+
+    // ========================================
+    // Examples tests
+    // ========================================
+
+    #[test]
+    fn tools_without_examples_command_have_none() {
+        let mut app = app_at(&["helpv-test-tool"]);
+        app.show_examples();
+        assert!(app.error_message.is_none());
+        assert_eq!(
+            app.info_message.as_ref().map(|(m, _)| m.as_str()),
+            Some("No examples for helpv-test-tool")
+        );
+        assert!(app.pager.appended_section(EXAMPLES_TITLE).is_none());
+    }
+
+    // ========================================
+    // Reload tests
+    // ========================================
+
+    #[test]
+    fn reload_refetches_invoked_page() {
+        let config = Config {
+            disable_help_fallback: true,
+            ..Default::default()
+        };
+        let mut app = App::with_content(
+            history(&["helpv-test-tool"]),
+            config,
+            "usage: helpv-test-tool".to_string(),
+            ContentSource::Help,
+            Duration::ZERO,
+        );
+        app.drill_into_item(&guide("guide")).unwrap();
+        assert_eq!(app.pager.content[0], "the guide page for helpv-test-tool");
+
+        app.pager = Pager::new("stale".to_string());
+        app.reload_page();
+
+        // The guide again, not the tool's own (missing) --help
+        assert!(app.error_message.is_none());
+        assert_eq!(app.pager.content[0], "the guide page for helpv-test-tool");
+        assert_eq!(app.current_command, history(&["helpv-test-tool"]));
+    }
+
+    fn guide(name: &str) -> Subcommand {
+        Subcommand {
+            name: name.to_string(),
+            description: None,
+            label: Some("Guides".to_string()),
+            invoke_command: Some("echo the {name} page for {base}".to_string()),
+            url: None,
+            aliases: Vec::new(),
+            dedupe_strategy: DedupeStrategy::Name,
+            confidence: 1.0,
+        }
+    }
+
+    #[test]
+    fn back_refetches_invoked_page() {
+        let config = Config {
+            disable_help_fallback: true,
+            ..Default::default()
+        };
+        let mut app = App::with_content(
+            history(&["helpv-test-tool"]),
+            config,
+            "usage: helpv-test-tool".to_string(),
+            ContentSource::Help,
+            Duration::ZERO,
+        );
+        app.drill_into_item(&guide("guide")).unwrap();
+        app.drill_into_item(&guide("tutorial")).unwrap();
+        app.go_back().unwrap();
+
+        // The first guide, fetched again rather than shown from its saved text
+        assert_eq!(app.content_source, ContentSource::Help);
+        assert_eq!(app.pager.content[0], "the guide page for helpv-test-tool");
+        assert_eq!(app.invoked_item.as_ref().unwrap().name, "guide");
+    }
+
+    #[test]
+    fn restore_refetches_invoked_page() {
+        let mut snapshot = History::new();
+        snapshot.push(
+            history(&["helpv-test-tool"]),
+            0,
+            ContentSource::Help,
+            "saved text".to_string(),
+            Some(Invocation {
+                name: "guide".to_string(),
+                invoke_command: "echo the {name} page for {base}".to_string(),
+            }),
+        );
+        let app = App::restore(snapshot, Config::default()).unwrap();
+
+        assert_eq!(app.content_source, ContentSource::Help);
+        assert_eq!(app.pager.content[0], "the guide page for helpv-test-tool");
+        assert_eq!(app.invoked_item.as_ref().unwrap().name, "guide");
+    }
+
+    // ========================================
+    // Open parent tests
+    // ========================================
+
+    fn app_at(command: &[&str]) -> App {
+        App::with_content(
+            history(command),
+            Config::default(),
+            "usage: helpv-test-tool".to_string(),
+            ContentSource::Help,
+            Duration::ZERO,
+        )
+    }
+
+    fn history_commands(app: &App) -> Vec<String> {
+        let mut app_history = app.history.clone();
+        let mut commands = Vec::new();
+        while let Some(entry) = app_history.pop() {
+            commands.insert(0, entry.command.join(" "));
+        }
+        commands
+    }
+
+    #[test]
+    fn command_levels_are_pushed_in_order() {
+        let mut app = app_at(&["helpv-test-tool", "remote", "add"]);
+        app.pager.scroll = 2;
+        app.push_command_levels();
+
+        assert_eq!(
+            history_commands(&app),
+            vec!["helpv-test-tool remote", "helpv-test-tool remote add"]
+        );
+        assert_eq!(app.history.current().unwrap().scroll_position, 2);
+    }
+
+    #[test]
+    fn command_levels_already_in_history_are_not_repeated() {
+        let mut app = app_at(&["helpv-test-tool", "remote", "add"]);
+        app.history.push(
+            history(&["helpv-test-tool"]),
+            0,
+            ContentSource::Help,
+            String::new(),
+            None,
+        );
+        app.history.push(
+            history(&["helpv-test-tool", "remote"]),
+            0,
+            ContentSource::Help,
+            String::new(),
+            None,
+        );
+        app.push_command_levels();
+
+        assert_eq!(
+            history_commands(&app),
+            vec![
+                "helpv-test-tool",
+                "helpv-test-tool remote",
+                "helpv-test-tool remote add"
+            ]
+        );
+    }
+
+    #[test]
+    fn open_parent_at_base_command_does_nothing() {
+        let mut app = app_at(&["helpv-test-tool"]);
+        app.go_to_root().unwrap();
+        assert!(app.history.is_empty());
+        assert_eq!(app.current_command, history(&["helpv-test-tool"]));
+    }
+
+    #[test]
+    fn open_parent_keeps_page_when_base_fetch_fails() {
+        let config = Config {
+            disable_help_fallback: true,
+            disable_man_fallback: true,
+            ..Default::default()
+        };
+        let mut app = App::with_content(
+            history(&["helpv-test-tool", "sub"]),
+            config,
+            "usage: helpv-test-tool sub".to_string(),
+            ContentSource::Help,
+            Duration::ZERO,
+        );
+        app.go_to_root().unwrap();
+
+        assert!(app.history.is_empty());
+        assert_eq!(app.current_command, history(&["helpv-test-tool", "sub"]));
+        assert!(app.error_message.is_some());
+    }
+
+    // ========================================
+    // Clear history tests
+    // ========================================
+
+    fn app_with_history() -> App {
+        let mut app = App::with_content(
+            history(&["helpv-test-tool", "sub"]),
+            Config::default(),
+            "usage: helpv-test-tool sub".to_string(),
+            ContentSource::Help,
+            Duration::ZERO,
+        );
+        app.history.push(
+            history(&["helpv-test-tool"]),
+            3,
+            ContentSource::Help,
+            "usage: helpv-test-tool".to_string(),
+            None,
+        );
+        app.command_history.push("other-tool".to_string());
+        app
+    }
+
+    #[test]
+    fn clear_history_asks_first() {
+        let mut app = app_with_history();
+        app.run_command("clear-history");
+        assert_eq!(app.state, AppState::Confirm);
+        assert_eq!(app.history.len(), 1);
+    }
+
+    #[test]
+    fn confirming_clears_history() {
+        let mut app = app_with_history();
+        app.run_command("clear-history");
+        app.handle_key(KeyEvent::from(KeyCode::Char('y'))).unwrap();
+
+        assert_eq!(app.state, AppState::Paging);
+        assert!(app.history.is_empty());
+        assert_eq!(app.command_history, vec!["helpv-test-tool sub"]);
+    }
+
+    #[test]
+    fn denying_keeps_history() {
+        let mut app = app_with_history();
+        app.run_command("clear-history");
+        app.handle_key(KeyEvent::from(KeyCode::Char('n'))).unwrap();
+
+        assert_eq!(app.state, AppState::Paging);
+        assert_eq!(app.history.len(), 1);
+        assert_eq!(app.command_history.len(), 2);
+    }
+
+    #[test]
+    fn clear_history_can_be_bound_to_a_key() {
+        let mut app = app_with_history();
+        app.config.keys.clear_history = vec!["Ctrl-x".to_string()];
+        app.key_handler = KeyHandler::new(app.config.keys.clone());
+
+        app.handle_key(KeyEvent::new(KeyCode::Char('x'), KeyModifiers::CONTROL))
+            .unwrap();
+        assert_eq!(app.state, AppState::Confirm);
+    }
+
+    // ========================================
+    // Command history persistence tests
+    // ========================================
+
+    fn config_with_data_dir(name: &str, max: usize) -> (Config, std::path::PathBuf) {
+        let dir = std::env::temp_dir().join(format!("helpv-{}-{}", name, std::process::id()));
+        let config = Config {
+            paths: crate::paths::Paths {
+                data_dir: dir.clone(),
+                ..Default::default()
+            },
+            command_history_max: max,
+            ..Default::default()
+        };
+        (config, dir)
+    }
+
+    #[test]
+    fn command_history_round_trips_through_disk() {
+        let (config, dir) = config_with_data_dir("cmd-history", 100);
+        App::save_command_history(&config, &history(&["git log", "cargo build"])).unwrap();
+        assert_eq!(
+            App::load_command_history(&config),
+            history(&["git log", "cargo build"])
+        );
+
+        let app = App::with_content(
+            history(&["helpv-test-tool"]),
+            config,
+            "usage: helpv-test-tool".to_string(),
+            ContentSource::Help,
+            Duration::ZERO,
+        );
+        assert_eq!(
+            app.command_history,
+            history(&["helpv-test-tool", "git log", "cargo build"])
+        );
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn command_history_respects_max_on_disk() {
+        let (config, dir) = config_with_data_dir("cmd-history-max", 2);
+        App::save_command_history(&config, &history(&["a", "b", "c"])).unwrap();
+        let path = config.paths.command_history_file().unwrap();
+        let saved: Vec<String> =
+            serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap();
+        assert_eq!(saved, history(&["a", "b"]));
+
+        let (bigger, _) = config_with_data_dir("cmd-history-max", 100);
+        App::save_command_history(&bigger, &history(&["a", "b", "c"])).unwrap();
+        assert_eq!(App::load_command_history(&config), history(&["a", "b"]));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn unreadable_command_history_is_empty() {
+        let (config, dir) = config_with_data_dir("cmd-history-bad", 100);
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("command_history.json"), "not json").unwrap();
+        assert!(App::load_command_history(&config).is_empty());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn clearing_history_persists() {
+        let (config, dir) = config_with_data_dir("cmd-history-clear", 100);
+        App::save_command_history(&config, &history(&["git log"])).unwrap();
+        let mut app = App::with_content(
+            history(&["helpv-test-tool"]),
+            config.clone(),
+            "usage: helpv-test-tool".to_string(),
+            ContentSource::Help,
+            Duration::ZERO,
+        );
+        app.clear_history();
+        assert_eq!(
+            App::load_command_history(&config),
+            history(&["helpv-test-tool"])
+        );
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    // ========================================
+    // Edit config tests
+    // ========================================
+
+    #[test]
+    fn editor_prefers_visual_then_editor() {
+        assert_eq!(editor_command(Some("nvim"), Some("nano")), vec!["nvim"]);
+        assert_eq!(editor_command(None, Some("code -w")), vec!["code", "-w"]);
+        assert_eq!(editor_command(Some("  "), Some("nano")), vec!["nano"]);
+        assert_eq!(editor_command(None, None), vec!["vi"]);
+    }
+
+    #[test]
+    fn edit_commands_pick_the_file() {
+        let mut app = app_with_history();
+        app.run_command("e config");
+        assert_eq!(app.pending_edit, Some(app.config.paths.config_file()));
+
+        app.pending_edit = None;
+        app.run_command("e toolpack git");
+        assert_eq!(
+            app.pending_edit,
+            Some(app.config.paths.tools_dir().join("git.toml"))
+        );
+
+        app.pending_edit = None;
+        app.run_command("e toolpack ../config");
+        assert_eq!(app.pending_edit, None);
+        assert_eq!(
+            app.error_message.map(|notice| notice.message).as_deref(),
+            Some("Invalid tool pack name '../config'")
+        );
+    }
+
+    #[test]
+    fn reload_shows_config_warnings() {
+        let dir = std::env::temp_dir().join(format!("helpv-reload-warn-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let config_path = dir.join("config.toml");
+        std::fs::write(&config_path, "tab_width = 8\n").unwrap();
+
+        let mut app = app_with_history();
+        app.config.paths = crate::paths::Paths::default().with_config_file(&config_path);
+        app.config.profile = Some("missing".to_string());
+        app.reload_config();
+
+        assert_eq!(app.config.tab_width, 8);
+        let error = app.error_message.expect("warning should be shown");
+        assert!(error.message.starts_with("profile 'missing' not found"));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    // ========================================
+    // render_to_string snapshot tests
+    // ========================================
+
+    const SNAPSHOT_HELP: &str = "\
+Usage: demo <COMMAND>
+
+Commands:
+  build    Compile the project
+  bundle   Package the build output
+  clean    Remove build artifacts
+";
+
+    /// An app for `demo`, with no background results left to arrive
+    fn render_app(content: &str) -> App {
+        let mut config = Config::default();
+        config.keys.apply_defaults();
+        let mut app = App::with_content(
+            history(&["demo"]),
+            config,
+            content.to_string(),
+            ContentSource::Help,
+            Duration::ZERO,
+        );
+        app.discovery_receiver = None;
+        app.alt_receiver = None;
+        app
+    }
+
+    #[test]
+    fn render_empty_pager() {
+        let mut app = render_app("");
+        insta::assert_snapshot!(app.render_to_string(50, 10));
+    }
+
+    #[test]
+    fn render_search_with_match() {
+        let mut app = render_app(SNAPSHOT_HELP);
+        app.pager.set_search("build");
+        insta::assert_snapshot!(app.render_to_string(100, 10));
+    }
+
+    #[test]
+    fn render_finder_with_query() {
+        let mut app = render_app(SNAPSHOT_HELP);
+        app.handle_key(KeyEvent::from(KeyCode::Char('f'))).unwrap();
+        app.finder.as_mut().unwrap().set_query("bun".to_string());
+        insta::assert_snapshot!(app.render_to_string(60, 16));
+    }
+
+    #[test]
+    fn render_error_message() {
+        let mut app = render_app(SNAPSHOT_HELP);
+        app.show_error("Could not fetch help for 'demo nope'");
+        insta::assert_snapshot!(app.render_to_string(50, 10));
+    }
+
+    #[test]
+    fn debug_log_records_state_transitions() {
+        let (mut config, dir) = config_with_data_dir("debug-log", 100);
+        config.debug_log = true;
+        let mut app = App::with_content(
+            history(&["helpv-test-tool"]),
+            config,
+            "usage: helpv-test-tool".to_string(),
+            ContentSource::Help,
+            Duration::ZERO,
+        );
+        app.render_to_string(80, 24);
+        app.last_key = Some(KeyEvent::from(KeyCode::Char('/')));
+        app.state = AppState::Searching;
+        app.render_to_string(80, 24);
+
+        let text = std::fs::read_to_string(dir.join("debug.jsonl")).unwrap();
+        let entries: Vec<serde_json::Value> = text
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(entries.len(), 2);
+        // The first page's parse, then the transition
+        assert_eq!(entries[0]["parsed"], "helpv-test-tool");
+        assert_eq!(entries[0]["frame"], 0);
+        let entry = &entries[1];
+        assert_eq!(entry["from"], "Paging");
+        assert_eq!(entry["to"], "Searching");
+        assert_eq!(entry["frame"], 2);
+        assert_eq!(entry["key"], "/");
+        assert_eq!(entry["command"], serde_json::json!(["helpv-test-tool"]));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    // ========================================
+    // Escape tests
+    // ========================================
+
+    fn app_escape_quits(escape_quits: bool) -> App {
+        let mut config = Config {
+            escape_quits,
+            ..Default::default()
+        };
+        config.keys.apply_defaults();
+        App::with_content(
+            history(&["helpv-test-tool"]),
+            config,
+            "usage: helpv-test-tool".to_string(),
+            ContentSource::Help,
+            Duration::ZERO,
+        )
+    }
+
+    #[test]
+    fn escape_quits_by_default() {
+        let mut app = app_escape_quits(true);
+        app.handle_key(KeyEvent::from(KeyCode::Esc)).unwrap();
+        assert!(app.should_quit);
+    }
+
+    #[test]
+    fn double_escape_quits_when_escape_quits_is_off() {
+        let mut app = app_escape_quits(false);
+        app.handle_key(KeyEvent::from(KeyCode::Esc)).unwrap();
+        assert!(!app.should_quit);
+        app.handle_key(KeyEvent::from(KeyCode::Esc)).unwrap();
+        assert!(app.should_quit);
+    }
+
+    #[test]
+    fn slow_second_escape_does_not_quit() {
+        let mut app = app_escape_quits(false);
+        app.handle_key(KeyEvent::from(KeyCode::Esc)).unwrap();
+        app.last_escape_time = Instant::now().checked_sub(Duration::from_secs(1));
+        app.handle_key(KeyEvent::from(KeyCode::Esc)).unwrap();
+        assert!(!app.should_quit);
+    }
+
+    #[test]
+    fn other_key_between_escapes_does_not_quit() {
+        let mut app = app_escape_quits(false);
+        app.handle_key(KeyEvent::from(KeyCode::Esc)).unwrap();
+        app.handle_key(KeyEvent::from(KeyCode::Char('j'))).unwrap();
+        app.handle_key(KeyEvent::from(KeyCode::Esc)).unwrap();
+        assert!(!app.should_quit);
+    }
+
+    #[test]
+    fn q_still_quits_when_escape_quits_is_off() {
+        let mut app = app_escape_quits(false);
+        app.handle_key(KeyEvent::from(KeyCode::Char('q'))).unwrap();
+        assert!(app.should_quit);
+
+        let mut app = app_escape_quits(false);
+        app.run_command("q");
+        assert!(app.should_quit);
+    }
+
+    #[test]
+    fn escaping_the_finder_keeps_the_last_selection() {
+        let mut app = render_app(SNAPSHOT_HELP);
+        app.handle_key(KeyEvent::from(KeyCode::Char('f'))).unwrap();
+        assert!(app.finder.is_some());
+
+        app.handle_key(KeyEvent::from(KeyCode::Esc)).unwrap();
+        assert!(app.finder.is_none());
+        assert!(!app.should_quit);
+        assert!(app.finder_last_selected.is_none());
+    }
+
+    // ========================================
+    // Search case tests
+    // ========================================
+
+    #[test]
+    fn ctrl_c_toggles_case_while_searching() {
+        let mut app = App::with_content(
+            history(&["helpv-test-tool"]),
+            Config::default(),
+            "Usage: helpv-test-tool\nusage notes".to_string(),
+            ContentSource::Help,
+            Duration::ZERO,
+        );
+        app.state = AppState::Searching;
+        app.handle_key(KeyEvent::from(KeyCode::Char('U'))).unwrap();
+        assert_eq!(app.pager.search_matches, vec![0, 1]);
+
+        let ctrl_c = KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL);
+        app.handle_key(ctrl_c).unwrap();
+        assert!(app.pager.case_sensitive);
+        assert_eq!(app.search_input, "U");
+        assert_eq!(app.pager.search_matches, vec![0]);
+
+        app.handle_key(ctrl_c).unwrap();
+        assert!(!app.pager.case_sensitive);
+        assert_eq!(app.pager.search_matches, vec![0, 1]);
+    }
+
+    #[test]
+    fn search_case_sensitive_config_sets_pager_default() {
+        let config = Config {
+            search_case_sensitive: true,
+            ..Default::default()
+        };
+        let app = App::with_content(
+            history(&["helpv-test-tool"]),
+            config,
+            "usage: helpv-test-tool".to_string(),
+            ContentSource::Help,
+            Duration::ZERO,
+        );
+        assert!(app.pager.case_sensitive);
+    }
+
+    // ========================================
+    // parse_man_index tests
+    // ========================================
+
+    #[test]
+    fn man_index_parses_man_k_output() {
+        let text = "\
+git-log (1)          - Show commit logs
+git-diff(1) - Show changes between commits
+gitk (1)             - The Git repository browser
+";
+        let results = parse_man_index(text, "git");
+        let names: Vec<&str> = results.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["git-log", "git-diff"]);
+        assert_eq!(results[0].description.as_deref(), Some("Show commit logs"));
+        assert_eq!(results[0].invoke_command.as_deref(), Some("man git-log"));
+    }
+
+    #[test]
+    fn man_index_splits_bsd_apropos_names() {
+        let text = "\
+git-log, git-whatchanged(1) - Show commit logs
+perl-git(3pm) - Perl interface to Git
+git-log(1) - duplicate entry
+";
+        let results = parse_man_index(text, "git");
+        let names: Vec<&str> = results.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["git-log", "git-whatchanged"]);
+        assert_eq!(results[1].description.as_deref(), Some("Show commit logs"));
+    }
+
+    #[test]
+    fn man_index_ignores_nothing_appropriate() {
+        assert!(parse_man_index("git-: nothing appropriate.\n", "git").is_empty());
+    }
+
+    // ========================================
+    // parse_see_also tests
+    // ========================================
 
     #[test]
     fn see_also_standard_format() {
@@ -678,7 +3601,7 @@ SEE ALSO
 AUTHOR
        Written by Linus Torvalds
 ";
-        let results = parse_see_also(content, "git");
+        let results = parse_see_also(content, "git", false);
         let names: Vec<&str> = results.iter().map(|s| s.name.as_str()).collect();
         assert!(names.contains(&"git-diff"));
         assert!(names.contains(&"git-show"));
@@ -698,7 +3621,7 @@ AUTHOR
 SEE ALSO
        curl-config(1), libcurl(3), curl-easy-init(3)
 ";
-        let results = parse_see_also(content, "curl");
+        let results = parse_see_also(content, "curl", false);
         let names: Vec<&str> = results.iter().map(|s| s.name.as_str()).collect();
         assert!(names.contains(&"curl-config"));
         assert!(names.contains(&"curl-easy-init"));
@@ -706,6 +3629,23 @@ SEE ALSO
         assert!(!names.contains(&"libcurl"));
     }
 
+    #[test]
+    fn see_also_broad_includes_other_tools() {
+        let content = "\
+SEE ALSO
+       curl(1), curl-config(1), libcurl(3)
+";
+        let results = parse_see_also(content, "curl", true);
+        let labeled: Vec<(&str, &str)> = results
+            .iter()
+            .map(|s| (s.name.as_str(), s.label.as_deref().unwrap_or("")))
+            .collect();
+        assert_eq!(
+            labeled,
+            vec![("curl-config", "Man Pages"), ("libcurl", "See Also")]
+        );
+    }
+
     #[test]
     fn see_also_no_section() {
         let content = "\
@@ -715,7 +3655,7 @@ NAME
 DESCRIPTION
        It does things.
 ";
-        let results = parse_see_also(content, "foo");
+        let results = parse_see_also(content, "foo", false);
         assert!(results.is_empty());
     }
 
@@ -725,7 +3665,7 @@ DESCRIPTION
 SEE ALSO
        git(1), git-log(1)
 ";
-        let results = parse_see_also(content, "git");
+        let results = parse_see_also(content, "git", false);
         let names: Vec<&str> = results.iter().map(|s| s.name.as_str()).collect();
         assert!(!names.contains(&"git"));
         assert!(names.contains(&"git-log"));
@@ -0,0 +1,42 @@
+use anyhow::{Result, anyhow};
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+/// Clipboard tools to try, in order: macOS, Wayland, then X11
+const CLIPBOARD_COMMANDS: &[(&str, &[&str])] = &[
+    ("pbcopy", &[]),
+    ("wl-copy", &[]),
+    ("xclip", &["-selection", "clipboard"]),
+    ("xsel", &["--clipboard", "--input"]),
+];
+
+/// Copy text to the system clipboard using the first clipboard tool that
+/// works; one that can't be started or fails moves on to the next
+pub fn copy_to_clipboard(text: &str) -> Result<()> {
+    for (program, args) in CLIPBOARD_COMMANDS {
+        let Ok(mut child) = Command::new(program)
+            .args(*args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+        else {
+            continue;
+        };
+
+        // Dropping stdin closes it so the tool sees the end of the text
+        let written = child
+            .stdin
+            .take()
+            .is_some_and(|mut stdin| stdin.write_all(text.as_bytes()).is_ok());
+        // Wait even after a failed write, so the child is reaped
+        let succeeded = child.wait().is_ok_and(|status| status.success());
+        if written && succeeded {
+            return Ok(());
+        }
+    }
+
+    Err(anyhow!(
+        "No working clipboard tool found (tried pbcopy, wl-copy, xclip, xsel)"
+    ))
+}
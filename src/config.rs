@@ -1,11 +1,14 @@
 use anyhow::Result;
+use regex::Regex;
 use serde::Deserialize;
 use std::collections::HashMap;
 use std::path::PathBuf;
 
-use crate::toolpacks::ToolPacks;
+use crate::keys::{Action, is_sequence, normalize_key};
+use crate::paths::Paths;
+use crate::toolpacks::{DiscoverySource, ToolPacks};
 
-#[derive(Debug, Clone, Deserialize, Default)]
+#[derive(Debug, Clone, Deserialize)]
 #[serde(default)]
 pub struct Config {
     pub tools: HashMap<String, ToolConfig>,
@@ -13,13 +16,200 @@ pub struct Config {
     pub subcommand_patterns: Vec<SubcommandPattern>,
     #[serde(default)]
     pub keys: KeyConfig,
+    /// How long a multi-key prefix (e.g. the first `g` of `gg`) stays pending
+    pub pending_key_timeout_ms: u64,
+    /// Show a scrollbar with search match markers on the right edge of the pager
+    pub show_scrollbar: bool,
+    /// Lines of context shown around search matches; 0 shows the full page
+    pub search_context_lines: usize,
+    /// Match search queries' case exactly (toggle with Ctrl-C while searching)
+    pub search_case_sensitive: bool,
+    /// Never fall back to `man` pages (e.g. in containers without man installed)
+    pub disable_man_fallback: bool,
+    /// Never run help flags; only the other sources are tried
+    pub disable_help_fallback: bool,
+    /// Order in which help sources are tried: "help", "man", "tldr", "info"
+    pub help_flag_order: Vec<String>,
+    /// How long (ms) non-critical error messages stay on screen
+    pub error_display_ms: u64,
+    /// Run help flags and `man` concurrently and take the first good result
+    pub parallel_fetch: bool,
+    /// How per-tool patterns combine with global ones
+    pub per_tool_patterns_mode: PatternsMode,
+    /// Maximum number of commands kept in the switcher history
+    pub command_history_max: usize,
+    /// Columns between tab stops when rendering hard tabs in help text
+    pub tab_width: usize,
+    /// Reload config.toml when it changes (requires the `watch-config` feature)
+    pub watch_config: bool,
+    /// A single Escape quits while paging; when false it takes two in quick
+    /// succession
+    pub escape_quits: bool,
+    /// Environment variables set for every help command (e.g. `NO_COLOR = "1"`)
+    pub global_env: HashMap<String, String>,
+    /// Indent wrapped continuations of indented help lines
+    pub smart_wrap: bool,
+    /// Color lines starting with `NOTE:`, `WARNING:`, `TIP:` and similar
+    pub callout_highlighting: bool,
+    /// Shade the first visible line as a position marker
+    pub cursor_line: bool,
+    /// Show an estimate of the words on screen in the status bar
+    pub show_word_count: bool,
+    /// Regexes for noise lines removed from fetched help (e.g. terminal warnings)
+    pub strip_patterns: Vec<String>,
+    /// Lines always dropped from the top of fetched help
+    pub strip_prefix_lines: usize,
+    /// Offer every SEE ALSO man page in the finder, not just `<tool>-*` ones
+    pub see_also_broad: bool,
+    /// Shell command run in place of drilling into a finder selection; helpv
+    /// exits first. `{cmd}`, `{base}`, and `{sub}` are substituted.
+    pub exec_on_select: Option<String>,
+    /// Wrapper that runs help commands, e.g. `"corp-wrapper run --tool {cmd} {args}"`.
+    /// `{cmd}` is the program and `{args}` its arguments; without either, the
+    /// whole command is appended. `HELPV_PROXY` overrides this.
+    pub help_proxy: Option<String>,
+    /// Fetched help longer than this many bytes is cut short; 0 for no limit
+    pub max_content_bytes: usize,
+    /// Extra man page directories, searched before `$MANPATH` and the
+    /// system default
+    pub manpath: Option<Vec<PathBuf>>,
+    /// File that Ctrl-X in the finder writes the filtered items to, instead
+    /// of printing them to the terminal
+    pub finder_export_file: Option<PathBuf>,
+    /// Log app state transitions to `debug.jsonl` in the data directory.
+    /// `HELPV_DEBUG=1` and `--debug` turn this on.
+    pub debug_log: bool,
+    /// Command shown when helpv is run without one, e.g. `["git"]`
+    pub default_command: Option<Vec<String>>,
+    /// Below this many columns only a "terminal too small" notice is drawn
+    pub min_width: u16,
+    /// Below this many rows only a "terminal too small" notice is drawn
+    pub min_height: u16,
+    /// How the built-in subcommand patterns combine with `subcommand_patterns`
+    pub defaults: DefaultsConfig,
     #[serde(skip)]
     pub toolpacks: ToolPacks,
+    #[serde(skip)]
+    pub paths: Paths,
+    /// Name of the `[profiles.*]` section applied on load, kept for `reload`
+    #[serde(skip)]
+    pub profile: Option<String>,
+    /// Command-line and environment settings, applied again on `reload`
+    #[serde(skip)]
+    pub overrides: Overrides,
+    /// Problems found while loading that didn't stop it, for the caller to
+    /// report (on stderr at startup, in the UI on reload)
+    #[serde(skip)]
+    pub warnings: Vec<String>,
+}
+
+/// Settings from command-line flags and environment variables, which take
+/// precedence over the config file
+#[derive(Debug, Clone, Default)]
+pub struct Overrides {
+    /// `--exec-on-select`, or `Some(None)` for `--no-exec-on-select`
+    pub exec_on_select: Option<Option<String>>,
+    /// `--finder-export-file`
+    pub finder_export_file: Option<PathBuf>,
+    /// `HELPV_PROXY`
+    pub help_proxy: Option<String>,
+    /// `--debug` or `HELPV_DEBUG=1`
+    pub debug_log: bool,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            tools: HashMap::new(),
+            subcommand_patterns: Vec::new(),
+            keys: KeyConfig::default(),
+            pending_key_timeout_ms: 1000,
+            show_scrollbar: true,
+            search_context_lines: 0,
+            search_case_sensitive: false,
+            disable_man_fallback: false,
+            disable_help_fallback: false,
+            help_flag_order: vec!["help".to_string(), "man".to_string()],
+            error_display_ms: 3000,
+            parallel_fetch: false,
+            per_tool_patterns_mode: PatternsMode::Replace,
+            command_history_max: 100,
+            tab_width: 4,
+            watch_config: false,
+            escape_quits: true,
+            global_env: HashMap::new(),
+            smart_wrap: true,
+            callout_highlighting: true,
+            cursor_line: false,
+            show_word_count: false,
+            strip_patterns: Vec::new(),
+            strip_prefix_lines: 0,
+            see_also_broad: false,
+            exec_on_select: None,
+            help_proxy: None,
+            max_content_bytes: 1_048_576,
+            manpath: None,
+            finder_export_file: None,
+            debug_log: false,
+            default_command: None,
+            min_width: 40,
+            min_height: 10,
+            defaults: DefaultsConfig::default(),
+            toolpacks: ToolPacks::default(),
+            paths: Paths::default(),
+            profile: None,
+            overrides: Overrides::default(),
+            warnings: Vec::new(),
+        }
+    }
+}
+
+/// How a tool's own `subcommand_patterns` combine with the global ones
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PatternsMode {
+    /// Use only the tool's patterns
+    #[default]
+    Replace,
+    /// Try the tool's patterns before the global ones
+    Prepend,
+    /// Try the tool's patterns after the global ones
+    Append,
+}
+
+/// The `[defaults]` section
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct DefaultsConfig {
+    /// Don't fill in the built-in patterns when `subcommand_patterns` is empty
+    pub override_default_patterns: bool,
+    /// Always add the built-in patterns after the user's own
+    pub append_default_patterns: bool,
 }
 
 #[derive(Debug, Clone, Deserialize)]
 pub struct ToolConfig {
+    #[serde(default)]
     pub help_flags: Vec<String>,
+    /// Parser patterns for this tool, combined with the global patterns
+    /// according to `Config::per_tool_patterns_mode`
+    #[serde(default)]
+    pub subcommand_patterns: Option<Vec<SubcommandPattern>>,
+    /// Environment variables set when running this tool's help commands
+    #[serde(default)]
+    pub env: HashMap<String, String>,
+    /// Noise-line regexes for this tool, applied along with the global ones
+    #[serde(default)]
+    pub strip_patterns: Vec<String>,
+    /// Overrides the global `strip_prefix_lines` for this tool
+    #[serde(default)]
+    pub strip_prefix_lines: Option<usize>,
+    /// Discovery sources added to this tool's pack (`[[tools.NAME.discover]]`)
+    #[serde(default)]
+    pub discover: Vec<DiscoverySource>,
+    /// Use `discover` instead of the pack's own sources rather than after them
+    #[serde(default)]
+    pub replace_discover: bool,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -28,6 +218,36 @@ pub struct SubcommandPattern {
     pub entry: String,
 }
 
+/// Two actions competing for one key, from [`KeyConfig::find_conflicts`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyConflict {
+    /// The key as the losing action spells it
+    pub key: String,
+    /// The action the key triggers
+    pub winner: &'static str,
+    pub loser: &'static str,
+    /// The winner's two-key sequence starting with `key`, when that sequence
+    /// is what keeps `key` from ever firing
+    pub sequence: Option<String>,
+}
+
+impl std::fmt::Display for KeyConflict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.sequence {
+            Some(ref sequence) => write!(
+                f,
+                "Key '{}' for {} never fires: it starts '{}' ({})",
+                self.key, self.loser, sequence, self.winner
+            ),
+            None => write!(
+                f,
+                "Key '{}' is bound to both {} and {}; {} wins",
+                self.key, self.winner, self.loser, self.winner
+            ),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Deserialize, Default)]
 #[serde(default)]
 pub struct KeyConfig {
@@ -47,42 +267,145 @@ pub struct KeyConfig {
     pub open_command: Vec<String>,
     pub back: Vec<String>,
     pub help: Vec<String>,
+    pub record_macro: Vec<String>,
+    pub play_macro: Vec<String>,
+    pub visual_line: Vec<String>,
+    pub toggle_fold: Vec<String>,
+    pub fold_all: Vec<String>,
+    pub unfold_all: Vec<String>,
+    pub open_url: Vec<String>,
+    pub toggle_diff: Vec<String>,
+    pub reload: Vec<String>,
+    pub copy_command: Vec<String>,
+    pub show_stats: Vec<String>,
+    pub export_session: Vec<String>,
+    pub command_mode: Vec<String>,
+    pub show_examples: Vec<String>,
+    pub highlight_word: Vec<String>,
+    pub save_snapshot: Vec<String>,
+    /// Unbound by default so no stray key clears; `:clear-history` runs it
+    pub clear_history: Vec<String>,
+    /// Unbound by default; `:e config` runs it
+    pub edit_config: Vec<String>,
+    pub suspend: Vec<String>,
+    pub redraw: Vec<String>,
+    pub open_parent: Vec<String>,
+    pub fetch_stderr: Vec<String>,
+    pub toggle_source: Vec<String>,
 }
 
 impl Config {
-    pub fn load() -> Result<Self> {
-        let config_path = Self::config_path();
-
-        let mut config = if config_path.exists() {
-            let content = std::fs::read_to_string(&config_path)?;
-            let mut config: Config = toml::from_str(&content)?;
-            config.apply_defaults();
-            config
+    pub fn load(paths: &Paths) -> Result<Self> {
+        Self::load_with_profile(paths, None)
+    }
+
+    /// Load config, then apply the `[profiles.<name>]` section on top of it.
+    /// An unknown profile is noted in `warnings` and the base config is used.
+    pub fn load_with_profile(paths: &Paths, profile: Option<&str>) -> Result<Self> {
+        let config_path = paths.config_file();
+
+        let mut warnings = Vec::new();
+        let content = if config_path.exists() {
+            std::fs::read_to_string(&config_path)?
         } else {
-            Self::default_config()
+            if paths.config_override.is_some() {
+                warnings.push(format!(
+                    "config file {} not found; using defaults",
+                    config_path.display()
+                ));
+            }
+            String::new()
         };
+        let (mut config, found) = Self::from_toml_with_profile(&content, profile)?;
+        warnings.extend(config.strip_pattern_warnings());
+        config.warnings = warnings;
+        if let Some(name) = profile
+            && !found
+        {
+            config.warnings.push(format!(
+                "profile '{}' not found in config; using base config",
+                name
+            ));
+        }
 
         // Load tool packs
-        config.toolpacks = ToolPacks::load()?;
+        let (toolpacks, pack_warnings) = ToolPacks::load(paths)?;
+        config.toolpacks = toolpacks;
+        config.warnings.extend(pack_warnings);
+        config.merge_tool_discovery();
+        config.paths = paths.clone();
+        config.profile = profile.map(str::to_string);
 
         Ok(config)
     }
 
-    fn config_path() -> PathBuf {
-        dirs::config_dir()
-            .unwrap_or_else(|| PathBuf::from("."))
-            .join("helpv")
-            .join("config.toml")
-    }
+    /// Parse config text with an optional profile applied. Also returns whether
+    /// the requested profile existed.
+    fn from_toml_with_profile(content: &str, profile: Option<&str>) -> Result<(Self, bool)> {
+        let mut table: toml::Table = toml::from_str(content)?;
+        let profiles = table.remove("profiles");
 
-    fn default_config() -> Self {
-        let mut config = Config::default();
+        let overlay = profile.and_then(|name| match &profiles {
+            Some(toml::Value::Table(profiles)) => profiles.get(name).cloned(),
+            _ => None,
+        });
+        let found = overlay.is_some();
+        if let Some(toml::Value::Table(overlay)) = overlay {
+            merge_tables(&mut table, overlay);
+        }
+
+        let mut config: Config = toml::Value::Table(table).try_into()?;
         config.apply_defaults();
-        config
+        Ok((config, found))
+    }
+
+    /// Add each `[tools.NAME]` table's `discover` sources to NAME's tool pack,
+    /// or swap them in when it sets `replace_discover`. A tool without a pack
+    /// gets one that fetches help the same way as before.
+    fn merge_tool_discovery(&mut self) {
+        for (name, tool) in &self.tools {
+            if tool.discover.is_empty() && !tool.replace_discover {
+                continue;
+            }
+            let pack = self.toolpacks.tools.entry(name.clone()).or_default();
+            if tool.replace_discover {
+                pack.discover.clear();
+            }
+            pack.discover.extend(tool.discover.iter().cloned());
+        }
+    }
+
+    /// Re-read the config file and tool packs from the same paths and profile,
+    /// keeping the command-line and environment overrides
+    pub fn reload(&mut self) -> Result<()> {
+        let mut config = Config::load_with_profile(&self.paths, self.profile.as_deref())?;
+        config.apply_overrides(self.overrides.clone());
+        *self = config;
+        Ok(())
+    }
+
+    /// Apply command-line and environment settings over the loaded ones
+    pub fn apply_overrides(&mut self, overrides: Overrides) {
+        if let Some(ref exec) = overrides.exec_on_select {
+            self.exec_on_select = exec.clone();
+        }
+        if let Some(ref file) = overrides.finder_export_file {
+            self.finder_export_file = Some(file.clone());
+        }
+        if let Some(ref proxy) = overrides.help_proxy {
+            self.help_proxy = Some(proxy.clone());
+        }
+        if overrides.debug_log {
+            self.debug_log = true;
+        }
+        self.overrides = overrides;
     }
 
     fn apply_defaults(&mut self) {
-        if self.subcommand_patterns.is_empty() {
+        if self.defaults.append_default_patterns {
+            self.subcommand_patterns
+                .extend(Self::default_subcommand_patterns());
+        } else if self.subcommand_patterns.is_empty() && !self.defaults.override_default_patterns {
             self.subcommand_patterns = Self::default_subcommand_patterns();
         }
 
@@ -93,11 +416,12 @@ impl Config {
         vec![
             SubcommandPattern {
                 section: r"(?im)^(commands?|subcommands?|available\s+commands?):?\s*$".to_string(),
-                entry: r"^\s{2,4}([\w][\w-]*)\s+(.*)$".to_string(),
+                // Aliases may follow the name: "build, b" or "build|b"
+                entry: r"^\s{2,4}([\w][\w-]*(?:(?:,\s*|\|)[\w][\w-]*)*)\s+(.*)$".to_string(),
             },
             SubcommandPattern {
                 section: r"(?im)^(usage|options):?\s*$".to_string(),
-                entry: r"^\s{2,4}([\w][\w-]*)\s{2,}(.*)$".to_string(),
+                entry: r"^\s{2,4}([\w][\w-]*(?:(?:,\s*|\|)[\w][\w-]*)*)\s{2,}(.*)$".to_string(),
             },
             // gh-style: "GENERAL COMMANDS" section header with "  cmd:  description" entries
             SubcommandPattern {
@@ -110,37 +434,138 @@ impl Config {
     /// Get help flags for a tool (base command only)
     pub fn get_help_flags(&self, tool: &str) -> Vec<String> {
         // User config in config.toml takes precedence
-        if let Some(tool_config) = self.tools.get(tool) {
+        if let Some(tool_config) = self.tools.get(tool)
+            && !tool_config.help_flags.is_empty()
+        {
             return tool_config.help_flags.clone();
         }
 
-        // Check toolpacks
-        if let Some(pack) = self.toolpacks.get(tool) {
-            return pack.get_help_commands();
+        self.toolpacks.get_or_default(tool).get_help_commands()
+    }
+
+    /// Environment for running a tool's help commands; per-tool values win over `global_env`
+    pub fn get_env_overrides(&self, tool: &str) -> HashMap<String, String> {
+        let mut env = self.global_env.clone();
+        if let Some(tool_config) = self.tools.get(tool) {
+            env.extend(tool_config.env.clone());
+        }
+        env
+    }
+
+    /// `MANPATH` for man commands: `manpath` followed by the process's own
+    /// `MANPATH`. `None` when `manpath` is unset, so `man` keeps its default.
+    pub fn man_search_path(&self) -> Option<String> {
+        let dirs = self.manpath.as_ref()?;
+        Some(merge_manpath(
+            dirs,
+            std::env::var("MANPATH").ok().as_deref(),
+        ))
+    }
+
+    /// Noise-line patterns for a tool: the global ones followed by the tool's own
+    pub fn strip_patterns_for(&self, tool: &str) -> Vec<String> {
+        let mut patterns = self.strip_patterns.clone();
+        if let Some(tool_config) = self.tools.get(tool) {
+            patterns.extend(tool_config.strip_patterns.iter().cloned());
+        }
+        patterns
+    }
+
+    /// One warning per `strip_patterns` entry that isn't a valid regex, global
+    /// ones first, then tools by name. `strip_noise` skips such entries.
+    fn strip_pattern_warnings(&self) -> Vec<String> {
+        let mut tools: Vec<_> = self.tools.iter().collect();
+        tools.sort_by_key(|(name, _)| name.as_str());
+        let lists = std::iter::once(("strip_patterns".to_string(), &self.strip_patterns)).chain(
+            tools.into_iter().map(|(name, tool)| {
+                (
+                    format!("tools.{}.strip_patterns", name),
+                    &tool.strip_patterns,
+                )
+            }),
+        );
+
+        let mut warnings = Vec::new();
+        for (key, patterns) in lists {
+            for pattern in patterns.iter().filter(|p| Regex::new(p).is_err()) {
+                warnings.push(format!(
+                    "{} entry '{}' is not a valid regex; ignoring it",
+                    key, pattern
+                ));
+            }
+        }
+        warnings
+    }
+
+    /// Header lines to drop for a tool; a per-tool value replaces the global one
+    pub fn strip_prefix_lines_for(&self, tool: &str) -> usize {
+        self.tools
+            .get(tool)
+            .and_then(|t| t.strip_prefix_lines)
+            .unwrap_or(self.strip_prefix_lines)
+    }
+
+    /// Subcommand patterns to use when parsing help for a tool
+    pub fn patterns_for(&self, tool: &str) -> Vec<SubcommandPattern> {
+        let Some(tool_patterns) = self
+            .tools
+            .get(tool)
+            .and_then(|t| t.subcommand_patterns.as_ref())
+        else {
+            return self.subcommand_patterns.clone();
+        };
+
+        match self.per_tool_patterns_mode {
+            PatternsMode::Prepend => tool_patterns
+                .iter()
+                .chain(&self.subcommand_patterns)
+                .cloned()
+                .collect(),
+            PatternsMode::Append => self
+                .subcommand_patterns
+                .iter()
+                .chain(tool_patterns)
+                .cloned()
+                .collect(),
+            PatternsMode::Replace => tool_patterns.clone(),
         }
+    }
 
-        // Generic fallback
-        vec!["{cmd} --help".to_string(), "{cmd} -h".to_string()]
+    /// Help sources to try for `tool`: its tool pack's `help_order` when set,
+    /// otherwise `help_flag_order`
+    pub fn get_help_order(&self, tool: &str) -> &[String] {
+        match self.toolpacks.get(tool) {
+            Some(pack) if !pack.help_order.is_empty() => &pack.help_order,
+            _ => &self.help_flag_order,
+        }
     }
 
     /// Get help flags for a subcommand
     pub fn get_subcommand_help_flags(&self, tool: &str) -> Vec<String> {
-        // Check toolpacks
-        if let Some(pack) = self.toolpacks.get(tool) {
-            return pack.get_subcommand_commands();
-        }
+        self.toolpacks
+            .get_or_default(tool)
+            .get_subcommand_commands()
+    }
+}
 
-        // Generic fallback
-        vec![
-            "{cmd} --help".to_string(),
-            "{base} help {sub}".to_string(),
-            "{cmd} -h".to_string(),
-        ]
+/// Join `dirs` with the inherited `MANPATH`. Without one, a trailing `:`
+/// tells `man` to search its default path after `dirs`.
+fn merge_manpath(dirs: &[PathBuf], inherited: Option<&str>) -> String {
+    let mut joined = dirs
+        .iter()
+        .map(|dir| dir.to_string_lossy())
+        .collect::<Vec<_>>()
+        .join(":");
+    joined.push(':');
+    if let Some(inherited) = inherited {
+        joined.push_str(inherited);
     }
+    joined
 }
 
 impl KeyConfig {
-    fn apply_defaults(&mut self) {
+    /// Fill in the default keys for every action left unbound
+    pub(crate) fn apply_defaults(&mut self) {
         if self.quit.is_empty() {
             self.quit = vec!["q".to_string(), "Escape".to_string()];
         }
@@ -184,18 +609,260 @@ impl KeyConfig {
             self.open_command = vec!["o".to_string()];
         }
         if self.back.is_empty() {
-            self.back = vec!["Backspace".to_string()];
+            self.back = vec!["Backspace".to_string(), "Alt-Left".to_string()];
         }
         if self.help.is_empty() {
             self.help = vec!["?".to_string()];
         }
+        if self.record_macro.is_empty() {
+            self.record_macro = vec!["Q".to_string()];
+        }
+        if self.play_macro.is_empty() {
+            self.play_macro = vec!["@q".to_string()];
+        }
+        if self.visual_line.is_empty() {
+            self.visual_line = vec!["V".to_string()];
+        }
+        if self.toggle_fold.is_empty() {
+            self.toggle_fold = vec!["za".to_string()];
+        }
+        if self.fold_all.is_empty() {
+            self.fold_all = vec!["zM".to_string()];
+        }
+        if self.unfold_all.is_empty() {
+            self.unfold_all = vec!["zR".to_string()];
+        }
+        // `u` is already half-page-up
+        if self.open_url.is_empty() {
+            self.open_url = vec!["U".to_string()];
+        }
+        if self.toggle_diff.is_empty() {
+            self.toggle_diff = vec!["Alt-D".to_string()];
+        }
+        if self.reload.is_empty() {
+            self.reload = vec!["r".to_string()];
+        }
+        if self.copy_command.is_empty() {
+            self.copy_command = vec!["yc".to_string()];
+        }
+        if self.show_stats.is_empty() {
+            // Terminals send Ctrl-I as Tab
+            self.show_stats = vec!["Ctrl-i".to_string(), "Tab".to_string()];
+        }
+        if self.export_session.is_empty() {
+            self.export_session = vec!["Ctrl-e".to_string()];
+        }
+        if self.command_mode.is_empty() {
+            self.command_mode = vec![":".to_string()];
+        }
+        if self.show_examples.is_empty() {
+            self.show_examples = vec!["E".to_string()];
+        }
+        if self.highlight_word.is_empty() {
+            self.highlight_word = vec!["*".to_string()];
+        }
+        if self.save_snapshot.is_empty() {
+            self.save_snapshot = vec!["ms".to_string()];
+        }
+        if self.suspend.is_empty() {
+            self.suspend = vec!["Ctrl-z".to_string()];
+        }
+        if self.redraw.is_empty() {
+            self.redraw = vec!["Ctrl-l".to_string()];
+        }
+        if self.open_parent.is_empty() {
+            self.open_parent = vec!["P".to_string()];
+        }
+        if self.fetch_stderr.is_empty() {
+            self.fetch_stderr = vec!["F5".to_string()];
+        }
+        if self.toggle_source.is_empty() {
+            self.toggle_source = vec!["Alt-M".to_string()];
+        }
+    }
+
+    /// All bindings in priority order
+    pub(crate) fn bindings(&self) -> [(&[String], Action); 39] {
+        [
+            (&self.quit, Action::Quit),
+            (&self.scroll_up, Action::ScrollUp),
+            (&self.scroll_down, Action::ScrollDown),
+            (&self.half_page_up, Action::HalfPageUp),
+            (&self.half_page_down, Action::HalfPageDown),
+            (&self.page_up, Action::PageUp),
+            (&self.page_down, Action::PageDown),
+            (&self.top, Action::Top),
+            (&self.bottom, Action::Bottom),
+            (&self.search, Action::Search),
+            (&self.next_match, Action::NextMatch),
+            (&self.prev_match, Action::PrevMatch),
+            (&self.find_subcommand, Action::OpenFinder),
+            (&self.open_command, Action::OpenCommand),
+            (&self.back, Action::Back),
+            (&self.help, Action::ShowHelp),
+            (&self.record_macro, Action::ToggleRecord),
+            (&self.play_macro, Action::PlayMacro),
+            (&self.visual_line, Action::VisualLine),
+            (&self.toggle_fold, Action::ToggleFold),
+            (&self.fold_all, Action::FoldAll),
+            (&self.unfold_all, Action::UnfoldAll),
+            (&self.open_url, Action::OpenUrl),
+            (&self.toggle_diff, Action::ToggleDiff),
+            (&self.reload, Action::Reload),
+            (&self.copy_command, Action::CopyCommand),
+            (&self.show_stats, Action::ShowStats),
+            (&self.export_session, Action::ExportSession),
+            (&self.command_mode, Action::CommandMode),
+            (&self.show_examples, Action::ShowExamples),
+            (&self.highlight_word, Action::HighlightWord),
+            (&self.save_snapshot, Action::SaveSnapshot),
+            (&self.clear_history, Action::ClearHistory),
+            (&self.edit_config, Action::EditConfig),
+            (&self.suspend, Action::Suspend),
+            (&self.redraw, Action::Redraw),
+            (&self.open_parent, Action::OpenParent),
+            (&self.fetch_stderr, Action::FetchStderr),
+            (&self.toggle_source, Action::ToggleSource),
+        ]
+    }
+
+    /// Keys bound to the action named `action_name` (see `Action::name`), as
+    /// `"search: /"`
+    pub fn describe_action(&self, action_name: &str) -> Option<String> {
+        self.bindings()
+            .into_iter()
+            .find(|(_, action)| action.name() == action_name)
+            .map(|(keys, action)| match keys {
+                [] => format!("{}: no key bound", action.name()),
+                keys => format!("{}: {}", action.name(), keys.join(", ")),
+            })
+    }
+
+    /// Every action name `describe_action` accepts
+    pub fn action_names(&self) -> Vec<&'static str> {
+        self.bindings()
+            .iter()
+            .map(|(_, action)| action.name())
+            .collect()
+    }
+
+    /// Keys bound to more than one action, compared the way `KeyHandler`
+    /// matches them, so `Esc` and `Escape` are the same key. The winner is the
+    /// action earlier in `bindings`, which is the one the key triggers. A
+    /// single key that starts another action's sequence (`m` with `ms`) is
+    /// reported too, since the sequence always waits for a second key.
+    pub fn find_conflicts(&self) -> Vec<KeyConflict> {
+        let bindings = self.bindings();
+        let mut conflicts = Vec::new();
+        for (i, (keys, action)) in bindings.iter().enumerate() {
+            for key in keys.iter() {
+                let normalized = normalize_key(key);
+                let earlier = bindings[..i].iter().find(|(other_keys, other)| {
+                    other != action && other_keys.iter().any(|k| normalize_key(k) == normalized)
+                });
+                if let Some((_, winner)) = earlier {
+                    conflicts.push(KeyConflict {
+                        key: key.clone(),
+                        winner: winner.name(),
+                        loser: action.name(),
+                        sequence: None,
+                    });
+                    continue;
+                }
+
+                let mut chars = key.chars();
+                let (Some(c), None) = (chars.next(), chars.next()) else {
+                    continue;
+                };
+                let shadowing = bindings.iter().find_map(|(other_keys, other)| {
+                    let sequence = other_keys
+                        .iter()
+                        .find(|k| is_sequence(k) && k.starts_with(c))?;
+                    (other != action).then_some((sequence, other))
+                });
+                if let Some((sequence, winner)) = shadowing {
+                    conflicts.push(KeyConflict {
+                        key: key.clone(),
+                        winner: winner.name(),
+                        loser: action.name(),
+                        sequence: Some(sequence.clone()),
+                    });
+                }
+            }
+        }
+        conflicts
+    }
+}
+
+/// Recursively overlay `overlay` onto `base`; nested tables merge, other values replace
+fn merge_tables(base: &mut toml::Table, overlay: toml::Table) {
+    for (key, value) in overlay {
+        match (base.get_mut(&key), value) {
+            (Some(toml::Value::Table(base_table)), toml::Value::Table(overlay_table)) => {
+                merge_tables(base_table, overlay_table);
+            }
+            (_, value) => {
+                base.insert(key, value);
+            }
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use regex::Regex;
+    use crate::toolpacks::ToolPack;
+
+    // ========================================
+    // Strip rule tests
+    // ========================================
+
+    const STRIP_CONFIG: &str = r#"
+strip_patterns = ["^WARNING:"]
+strip_prefix_lines = 1
+
+[tools.bash]
+strip_patterns = ["^bash: help:"]
+strip_prefix_lines = 0
+"#;
+
+    #[test]
+    fn strip_patterns_combine_global_and_tool() {
+        let (config, _) = Config::from_toml_with_profile(STRIP_CONFIG, None).unwrap();
+        assert_eq!(
+            config.strip_patterns_for("bash"),
+            vec!["^WARNING:", "^bash: help:"]
+        );
+        assert_eq!(config.strip_patterns_for("git"), vec!["^WARNING:"]);
+    }
+
+    #[test]
+    fn invalid_strip_patterns_are_load_warnings() {
+        let (config, _) = Config::from_toml_with_profile(
+            r#"
+strip_patterns = ["^WARNING:", "("]
+
+[tools.bash]
+strip_patterns = ["[unclosed"]
+"#,
+            None,
+        )
+        .unwrap();
+        assert_eq!(
+            config.strip_pattern_warnings(),
+            vec![
+                "strip_patterns entry '(' is not a valid regex; ignoring it",
+                "tools.bash.strip_patterns entry '[unclosed' is not a valid regex; ignoring it",
+            ]
+        );
+    }
+
+    #[test]
+    fn strip_prefix_lines_tool_overrides_global() {
+        let (config, _) = Config::from_toml_with_profile(STRIP_CONFIG, None).unwrap();
+        assert_eq!(config.strip_prefix_lines_for("bash"), 0);
+        assert_eq!(config.strip_prefix_lines_for("git"), 1);
+    }
 
     // ========================================
     // Default pattern tests
@@ -284,6 +951,12 @@ mod tests {
             "mytool".to_string(),
             ToolConfig {
                 help_flags: vec!["mytool help".to_string()],
+                subcommand_patterns: None,
+                env: HashMap::new(),
+                strip_patterns: Vec::new(),
+                strip_prefix_lines: None,
+                discover: Vec::new(),
+                replace_discover: false,
             },
         );
 
@@ -292,6 +965,28 @@ mod tests {
         assert_eq!(flags[0], "mytool help");
     }
 
+    #[test]
+    fn help_order_comes_from_tool_pack() {
+        let config = Config {
+            toolpacks: ToolPacks::load_toml(
+                r#"
+[ansible-doc]
+help_order = ["man", "help"]
+
+[git]
+help = ["git --help"]
+"#,
+            )
+            .unwrap(),
+            ..Default::default()
+        };
+
+        assert_eq!(config.get_help_order("ansible-doc"), ["man", "help"]);
+        // Packs without help_order, and tools without packs, use the global order
+        assert_eq!(config.get_help_order("git"), ["help", "man"]);
+        assert_eq!(config.get_help_order("unknown-tool"), ["help", "man"]);
+    }
+
     #[test]
     fn subcommand_generic_fallback() {
         let config = Config::default();
@@ -303,6 +998,403 @@ mod tests {
         assert!(flags.contains(&"{cmd} -h".to_string()));
     }
 
+    #[test]
+    fn tool_config_without_help_flags_uses_fallback() {
+        let mut config = Config::default();
+        config.tools.insert(
+            "mytool".to_string(),
+            ToolConfig {
+                help_flags: Vec::new(),
+                subcommand_patterns: None,
+                env: HashMap::new(),
+                strip_patterns: Vec::new(),
+                strip_prefix_lines: None,
+                discover: Vec::new(),
+                replace_discover: false,
+            },
+        );
+
+        let flags = config.get_help_flags("mytool");
+        assert_eq!(flags.len(), 2);
+    }
+
+    // ========================================
+    // Profile tests
+    // ========================================
+
+    const PROFILE_CONFIG: &str = r#"
+show_scrollbar = true
+tab_width = 4
+
+[keys]
+quit = ["q"]
+
+[profiles.minimal]
+show_scrollbar = false
+
+[profiles.minimal.keys]
+help = ["h"]
+"#;
+
+    #[test]
+    fn profile_overrides_base_keys() {
+        let (config, found) =
+            Config::from_toml_with_profile(PROFILE_CONFIG, Some("minimal")).unwrap();
+        assert!(found);
+        assert!(!config.show_scrollbar);
+        // Keys not mentioned by the profile keep their base values
+        assert_eq!(config.tab_width, 4);
+    }
+
+    #[test]
+    fn profile_merges_nested_tables() {
+        let (config, _) = Config::from_toml_with_profile(PROFILE_CONFIG, Some("minimal")).unwrap();
+        assert_eq!(config.keys.help, vec!["h"]);
+        assert_eq!(config.keys.quit, vec!["q"]);
+    }
+
+    #[test]
+    fn unknown_profile_falls_back_to_base() {
+        let (config, found) =
+            Config::from_toml_with_profile(PROFILE_CONFIG, Some("missing")).unwrap();
+        assert!(!found);
+        assert!(config.show_scrollbar);
+    }
+
+    #[test]
+    fn unknown_profile_is_a_load_warning() {
+        let dir = std::env::temp_dir().join(format!("helpv-profile-gone-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let config_path = dir.join("config.toml");
+        std::fs::write(&config_path, PROFILE_CONFIG).unwrap();
+
+        let paths = paths_with_helpv_config(&dir, &config_path);
+        let config = Config::load_with_profile(&paths, Some("missing")).unwrap();
+        assert_eq!(
+            config.warnings,
+            vec!["profile 'missing' not found in config; using base config"]
+        );
+        assert!(
+            Config::load_with_profile(&paths, Some("minimal"))
+                .unwrap()
+                .warnings
+                .is_empty()
+        );
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn no_profile_ignores_profiles_section() {
+        let (config, found) = Config::from_toml_with_profile(PROFILE_CONFIG, None).unwrap();
+        assert!(!found);
+        assert!(config.show_scrollbar);
+        assert_eq!(config.keys.help, vec!["?"]);
+    }
+
+    #[test]
+    fn profile_on_empty_config() {
+        let (config, found) = Config::from_toml_with_profile("", Some("fast")).unwrap();
+        assert!(!found);
+        assert!(!config.subcommand_patterns.is_empty());
+    }
+
+    #[test]
+    fn default_command_takes_multiple_words() {
+        let toml = r#"
+default_command = ["cargo", "build"]
+
+[profiles.git]
+default_command = ["git"]
+"#;
+        let (config, _) = Config::from_toml_with_profile(toml, None).unwrap();
+        assert_eq!(
+            config.default_command,
+            Some(vec!["cargo".to_string(), "build".to_string()])
+        );
+        let (config, _) = Config::from_toml_with_profile(toml, Some("git")).unwrap();
+        assert_eq!(config.default_command, Some(vec!["git".to_string()]));
+        let (config, _) = Config::from_toml_with_profile("", None).unwrap();
+        assert_eq!(config.default_command, None);
+    }
+
+    // ========================================
+    // Default pattern tests
+    // ========================================
+
+    const USER_PATTERN: &str = r#"
+[[subcommand_patterns]]
+section = "^VERBS$"
+entry = "^\\s+(\\w+)$"
+"#;
+
+    #[test]
+    fn user_patterns_replace_defaults() {
+        let (config, _) = Config::from_toml_with_profile(USER_PATTERN, None).unwrap();
+        assert_eq!(sections(&config.subcommand_patterns), vec!["^VERBS$"]);
+    }
+
+    #[test]
+    fn override_default_patterns_leaves_patterns_empty() {
+        let toml = "[defaults]\noverride_default_patterns = true\n";
+        let (config, _) = Config::from_toml_with_profile(toml, None).unwrap();
+        assert!(config.subcommand_patterns.is_empty());
+    }
+
+    #[test]
+    fn append_default_patterns_after_user_patterns() {
+        let toml = format!(
+            "{}\n[defaults]\nappend_default_patterns = true\n",
+            USER_PATTERN
+        );
+        let (config, _) = Config::from_toml_with_profile(&toml, None).unwrap();
+        let builtin = Config::default_subcommand_patterns();
+        assert_eq!(config.subcommand_patterns.len(), builtin.len() + 1);
+        assert_eq!(sections(&config.subcommand_patterns)[0], "^VERBS$");
+        assert_eq!(sections(&config.subcommand_patterns)[1], builtin[0].section);
+    }
+
+    #[test]
+    fn append_default_patterns_without_user_patterns() {
+        let toml = "[defaults]\nappend_default_patterns = true\n";
+        let (config, _) = Config::from_toml_with_profile(toml, None).unwrap();
+        assert_eq!(
+            config.subcommand_patterns.len(),
+            Config::default_subcommand_patterns().len()
+        );
+    }
+
+    // ========================================
+    // Environment override tests
+    // ========================================
+
+    fn env(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn env_overrides_default_empty() {
+        assert!(Config::default().get_env_overrides("git").is_empty());
+    }
+
+    #[test]
+    fn env_overrides_merge_global_and_tool() {
+        let mut config = Config {
+            global_env: env(&[("NO_COLOR", "1"), ("COLUMNS", "120")]),
+            ..Default::default()
+        };
+        config.tools.insert(
+            "wide".to_string(),
+            ToolConfig {
+                help_flags: Vec::new(),
+                subcommand_patterns: None,
+                env: env(&[("COLUMNS", "200"), ("TERM", "dumb")]),
+                strip_patterns: Vec::new(),
+                strip_prefix_lines: None,
+                discover: Vec::new(),
+                replace_discover: false,
+            },
+        );
+
+        assert_eq!(
+            config.get_env_overrides("wide"),
+            env(&[("NO_COLOR", "1"), ("COLUMNS", "200"), ("TERM", "dumb")])
+        );
+        assert_eq!(config.get_env_overrides("other"), config.global_env);
+    }
+
+    #[test]
+    fn env_parses_from_toml() {
+        let config: Config = toml::from_str(
+            r#"
+global_env = { NO_COLOR = "1" }
+
+[tools.wide]
+env = { COLUMNS = "200" }
+"#,
+        )
+        .unwrap();
+        assert_eq!(
+            config.get_env_overrides("wide"),
+            env(&[("NO_COLOR", "1"), ("COLUMNS", "200")])
+        );
+    }
+
+    // ========================================
+    // MANPATH tests
+    // ========================================
+
+    #[test]
+    fn manpath_puts_config_dirs_first() {
+        let dirs = vec![
+            PathBuf::from("/opt/homebrew/share/man"),
+            PathBuf::from("/nix/profile/share/man"),
+        ];
+        assert_eq!(
+            merge_manpath(&dirs, Some("/usr/local/man")),
+            "/opt/homebrew/share/man:/nix/profile/share/man:/usr/local/man"
+        );
+        assert_eq!(
+            merge_manpath(&dirs, None),
+            "/opt/homebrew/share/man:/nix/profile/share/man:"
+        );
+    }
+
+    #[test]
+    fn manpath_unset_leaves_man_default() {
+        assert_eq!(Config::default().man_search_path(), None);
+
+        let config: Config = toml::from_str(r#"manpath = ["/opt/man"]"#).unwrap();
+        assert!(config.man_search_path().unwrap().starts_with("/opt/man:"));
+    }
+
+    // ========================================
+    // Per-tool pattern tests
+    // ========================================
+
+    fn pattern(section: &str) -> SubcommandPattern {
+        SubcommandPattern {
+            section: section.to_string(),
+            entry: r"^\s+(\w+)".to_string(),
+        }
+    }
+
+    fn config_with_tool_patterns(mode: PatternsMode) -> Config {
+        let mut config = Config {
+            subcommand_patterns: vec![pattern("global")],
+            per_tool_patterns_mode: mode,
+            ..Default::default()
+        };
+        config.tools.insert(
+            "odd".to_string(),
+            ToolConfig {
+                help_flags: Vec::new(),
+                subcommand_patterns: Some(vec![pattern("tool")]),
+                env: HashMap::new(),
+                strip_patterns: Vec::new(),
+                strip_prefix_lines: None,
+                discover: Vec::new(),
+                replace_discover: false,
+            },
+        );
+        config
+    }
+
+    fn sections(patterns: &[SubcommandPattern]) -> Vec<&str> {
+        patterns.iter().map(|p| p.section.as_str()).collect()
+    }
+
+    #[test]
+    fn patterns_for_tool_without_overrides_are_global() {
+        let config = config_with_tool_patterns(PatternsMode::Replace);
+        assert_eq!(sections(&config.patterns_for("other")), vec!["global"]);
+    }
+
+    #[test]
+    fn patterns_for_replace_mode() {
+        let config = config_with_tool_patterns(PatternsMode::Replace);
+        assert_eq!(sections(&config.patterns_for("odd")), vec!["tool"]);
+    }
+
+    #[test]
+    fn patterns_for_prepend_mode() {
+        let config = config_with_tool_patterns(PatternsMode::Prepend);
+        assert_eq!(
+            sections(&config.patterns_for("odd")),
+            vec!["tool", "global"]
+        );
+    }
+
+    #[test]
+    fn patterns_for_append_mode() {
+        let config = config_with_tool_patterns(PatternsMode::Append);
+        assert_eq!(
+            sections(&config.patterns_for("odd")),
+            vec!["global", "tool"]
+        );
+    }
+
+    #[test]
+    fn patterns_mode_is_read_in_snake_case() {
+        let config: Config = toml::from_str(r#"per_tool_patterns_mode = "prepend""#).unwrap();
+        assert_eq!(config.per_tool_patterns_mode, PatternsMode::Prepend);
+        assert!(toml::from_str::<Config>(r#"per_tool_patterns_mode = "merge""#).is_err());
+    }
+
+    // ========================================
+    // Inline discover tests
+    // ========================================
+
+    fn config_with_inline_discover(replace: bool) -> Config {
+        let content = format!(
+            r#"
+[tools.git]
+replace_discover = {replace}
+
+[[tools.git.discover]]
+label = "Aliases"
+run = "git config --get-regexp ^alias"
+pattern = "^alias\\.(\\S+)\\s+(.*)$"
+invoke = "git {{name}}"
+
+[[tools.mytool.discover]]
+label = "Plugins"
+run = "mytool plugins"
+pattern = "^(\\S+)"
+invoke = "mytool {{name}} --help"
+"#
+        );
+        let (mut config, _) = Config::from_toml_with_profile(&content, None).unwrap();
+        config.toolpacks = ToolPacks::load_toml(
+            r#"
+[git]
+help = ["git --help"]
+
+[[git.discover]]
+label = "Guides"
+run = "git help -g"
+pattern = "^   (\\S+)"
+invoke = "git help {name}"
+"#,
+        )
+        .unwrap();
+        config.merge_tool_discovery();
+        config
+    }
+
+    fn labels(pack: &ToolPack) -> Vec<&str> {
+        pack.discover.iter().map(|s| s.label.as_str()).collect()
+    }
+
+    #[test]
+    fn inline_discover_appends_to_pack() {
+        let config = config_with_inline_discover(false);
+        let git = config.toolpacks.get("git").unwrap();
+        assert_eq!(labels(git), vec!["Guides", "Aliases"]);
+        assert_eq!(git.help, vec!["git --help"]);
+    }
+
+    #[test]
+    fn inline_discover_replaces_pack_sources() {
+        let config = config_with_inline_discover(true);
+        let git = config.toolpacks.get("git").unwrap();
+        assert_eq!(labels(git), vec!["Aliases"]);
+    }
+
+    #[test]
+    fn inline_discover_without_pack_keeps_help_fallback() {
+        let config = config_with_inline_discover(false);
+        let mytool = config.toolpacks.get("mytool").unwrap();
+        assert_eq!(labels(mytool), vec!["Plugins"]);
+        assert_eq!(
+            config.get_subcommand_help_flags("mytool"),
+            Config::default().get_subcommand_help_flags("mytool")
+        );
+    }
+
     // ========================================
     // KeyConfig defaults tests
     // ========================================
@@ -328,12 +1420,171 @@ mod tests {
         assert!(!config.open_command.is_empty());
         assert!(!config.back.is_empty());
         assert!(!config.help.is_empty());
+        assert!(!config.record_macro.is_empty());
+        assert!(!config.play_macro.is_empty());
+        assert!(!config.visual_line.is_empty());
+        assert!(!config.toggle_fold.is_empty());
+        assert!(!config.fold_all.is_empty());
+        assert!(!config.unfold_all.is_empty());
+        assert!(!config.open_url.is_empty());
+        assert!(!config.toggle_diff.is_empty());
+        assert!(!config.reload.is_empty());
+        assert!(!config.copy_command.is_empty());
+        assert!(!config.show_stats.is_empty());
+        assert!(!config.export_session.is_empty());
+        assert!(!config.command_mode.is_empty());
+        assert!(!config.show_examples.is_empty());
+        assert!(!config.highlight_word.is_empty());
+        assert!(!config.save_snapshot.is_empty());
+        assert!(!config.suspend.is_empty());
+        assert!(!config.redraw.is_empty());
+        assert!(!config.open_parent.is_empty());
+        assert!(!config.fetch_stderr.is_empty());
+        assert!(!config.toggle_source.is_empty());
     }
 
     #[test]
-    fn key_config_apply_defaults_preserves_user_values() {
+    fn describe_action_lists_current_keys() {
+        let mut config = KeyConfig {
+            scroll_up: vec!["Ctrl-p".to_string()],
+            ..Default::default()
+        };
+        config.apply_defaults();
+
+        assert_eq!(
+            config.describe_action("search").as_deref(),
+            Some("search: /")
+        );
+        assert_eq!(
+            config.describe_action("open-finder").as_deref(),
+            Some("open-finder: f")
+        );
+        assert_eq!(
+            config.describe_action("scroll-up").as_deref(),
+            Some("scroll-up: Ctrl-p")
+        );
+        assert_eq!(config.describe_action("scroll_up"), None);
+        assert_eq!(config.describe_action("bogus"), None);
+        assert_eq!(
+            config.describe_action("clear-history").as_deref(),
+            Some("clear-history: no key bound")
+        );
+    }
+
+    #[test]
+    fn default_bindings_have_no_conflicts() {
         let mut config = KeyConfig::default();
-        config.quit = vec!["x".to_string()]; // User override
+        config.apply_defaults();
+        assert_eq!(config.find_conflicts(), Vec::new());
+    }
+
+    #[test]
+    fn find_conflicts_reports_shared_keys() {
+        let mut config = KeyConfig {
+            search: vec!["/".to_string(), "q".to_string()],
+            top: vec!["gg".to_string()],
+            bottom: vec!["G".to_string(), "gg".to_string()],
+            ..Default::default()
+        };
+        config.apply_defaults();
+
+        let conflicts: Vec<_> = config
+            .find_conflicts()
+            .into_iter()
+            .map(|c| (c.key, c.winner, c.loser))
+            .collect();
+        assert_eq!(
+            conflicts,
+            vec![
+                ("gg".to_string(), "top", "bottom"),
+                ("q".to_string(), "quit", "search"),
+            ]
+        );
+    }
+
+    fn conflict_keys(config: KeyConfig) -> Vec<String> {
+        let mut config = config;
+        config.apply_defaults();
+        config.find_conflicts().into_iter().map(|c| c.key).collect()
+    }
+
+    #[test]
+    fn find_conflicts_treats_esc_and_escape_alike() {
+        let config = KeyConfig {
+            quit: vec!["Esc".to_string()],
+            back: vec!["Escape".to_string()],
+            ..Default::default()
+        };
+        assert_eq!(conflict_keys(config), ["Escape"]);
+    }
+
+    #[test]
+    fn find_conflicts_ignores_ctrl_letter_case() {
+        let config = KeyConfig {
+            half_page_up: vec!["Ctrl-u".to_string()],
+            search: vec!["Ctrl-U".to_string()],
+            ..Default::default()
+        };
+        assert_eq!(conflict_keys(config), ["Ctrl-U"]);
+    }
+
+    #[test]
+    fn find_conflicts_keeps_plain_letter_case() {
+        let config = KeyConfig {
+            next_match: vec!["n".to_string()],
+            prev_match: vec!["N".to_string()],
+            ..Default::default()
+        };
+        assert!(conflict_keys(config).is_empty());
+    }
+
+    #[test]
+    fn find_conflicts_reports_keys_starting_a_sequence() {
+        let mut config = KeyConfig {
+            reload: vec!["m".to_string()],
+            search: vec!["y".to_string()],
+            ..Default::default()
+        };
+        config.apply_defaults();
+        let conflicts = config.find_conflicts();
+
+        assert_eq!(conflicts.len(), 2);
+        assert_eq!(
+            conflicts[0].to_string(),
+            "Key 'y' for search never fires: it starts 'yc' (copy-command)"
+        );
+        assert_eq!(conflicts[1].key, "m");
+        assert_eq!(conflicts[1].loser, "reload");
+        assert_eq!(conflicts[1].winner, "save-snapshot");
+        assert_eq!(conflicts[1].sequence.as_deref(), Some("ms"));
+    }
+
+    #[test]
+    fn find_conflicts_ignores_repeats_within_one_action() {
+        let mut config = KeyConfig {
+            quit: vec!["q".to_string(), "q".to_string()],
+            ..Default::default()
+        };
+        config.apply_defaults();
+        assert!(config.find_conflicts().is_empty());
+    }
+
+    #[test]
+    fn action_names_are_unique() {
+        let config = KeyConfig::default();
+        let mut names = config.action_names();
+        let count = names.len();
+        names.sort_unstable();
+        names.dedup();
+        assert_eq!(names.len(), count);
+    }
+
+    #[test]
+    fn key_config_apply_defaults_preserves_user_values() {
+        let mut config = KeyConfig {
+            quit: vec!["x".to_string()], // User override
+            ..Default::default()
+        };
         config.apply_defaults();
 
         // User's quit binding should be preserved
@@ -353,6 +1604,70 @@ mod tests {
         assert!(config.half_page_up.contains(&"Ctrl-u".to_string()));
         assert!(config.half_page_down.contains(&"Ctrl-d".to_string()));
         assert!(config.bottom.contains(&"G".to_string()));
+        assert!(config.back.contains(&"Alt-Left".to_string()));
         assert!(config.search.contains(&"/".to_string()));
     }
+
+    // ========================================
+    // Config file location tests
+    // ========================================
+
+    /// Paths rooted in `dir`, with `HELPV_CONFIG` set to `config`
+    fn paths_with_helpv_config(dir: &std::path::Path, config: &std::path::Path) -> Paths {
+        let config = config.to_string_lossy().into_owned();
+        Paths::resolve(dir, |var| (var == "HELPV_CONFIG").then(|| config.clone()))
+    }
+
+    #[test]
+    fn helpv_config_env_selects_config_file() {
+        let dir = std::env::temp_dir().join(format!("helpv-config-env-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let config_path = dir.join("alt.toml");
+        std::fs::write(&config_path, "show_scrollbar = false\n").unwrap();
+
+        let paths = paths_with_helpv_config(&dir, &config_path);
+        let config = Config::load(&paths).unwrap();
+        assert!(!config.show_scrollbar);
+        assert_eq!(config.paths.config_file(), config_path);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn missing_explicit_config_file_uses_defaults() {
+        let dir = std::env::temp_dir().join(format!("helpv-config-gone-{}", std::process::id()));
+        let paths = paths_with_helpv_config(&dir, &dir.join("missing.toml"));
+
+        let config = Config::load(&paths).unwrap();
+        assert_eq!(config.show_scrollbar, Config::default().show_scrollbar);
+        assert_eq!(config.warnings.len(), 1);
+        assert!(config.warnings[0].starts_with("config file"));
+        assert!(!dir.exists());
+    }
+
+    #[test]
+    fn reload_keeps_command_line_overrides() {
+        let dir = std::env::temp_dir().join(format!("helpv-config-reload-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let config_path = dir.join("config.toml");
+        std::fs::write(&config_path, "exec_on_select = \"{base} {sub} --help\"\n").unwrap();
+
+        let paths = paths_with_helpv_config(&dir, &config_path);
+        let mut config = Config::load(&paths).unwrap();
+        config.apply_overrides(Overrides {
+            exec_on_select: Some(None),
+            help_proxy: Some("wrap {cmd} {args}".to_string()),
+            debug_log: true,
+            ..Default::default()
+        });
+        assert!(config.exec_on_select.is_none());
+
+        std::fs::write(&config_path, "exec_on_select = \"{cmd} {sub}\"\n").unwrap();
+        config.reload().unwrap();
+        assert!(config.exec_on_select.is_none());
+        assert_eq!(config.help_proxy.as_deref(), Some("wrap {cmd} {args}"));
+        assert!(config.debug_log);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
 }
@@ -0,0 +1,197 @@
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+use std::time::SystemTime;
+
+use crate::parser::ParseStats;
+
+/// Appends one JSON object per app state transition, per parsed page and per
+/// warning to a JSONL file, for `HELPV_DEBUG=1` or `--debug`. Read it back with e.g.
+/// `jq -c . debug.jsonl`.
+#[derive(Debug, Default)]
+pub struct DebugLogger {
+    /// Where to log; `None` when debugging is off or the file couldn't be opened
+    path: Option<PathBuf>,
+    /// Opened on the first transition, so a session without one creates nothing
+    file: Option<File>,
+    /// Frames drawn so far
+    pub frame: u64,
+}
+
+impl DebugLogger {
+    /// A logger writing to `path`, or one that does nothing for `None`
+    pub fn new(path: Option<PathBuf>) -> Self {
+        Self {
+            path,
+            file: None,
+            frame: 0,
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.path.is_some()
+    }
+
+    /// Count a drawn frame
+    pub fn next_frame(&mut self) {
+        self.frame += 1;
+    }
+
+    /// Record a move from state `from` to `to`, with the key that caused it
+    pub fn log_transition(&mut self, from: &str, to: &str, key: Option<&str>, command: &[String]) {
+        if !self.is_enabled() {
+            return;
+        }
+        let entry = serde_json::json!({
+            "time_ns": now_ns(),
+            "from": from,
+            "to": to,
+            "frame": self.frame,
+            "key": key,
+            "command": command,
+        });
+        self.write_line(&entry.to_string());
+    }
+
+    /// Record how `tool`'s page was parsed, for tuning custom patterns
+    pub fn log_parse(&mut self, tool: &str, stats: &ParseStats) {
+        if !self.is_enabled() {
+            return;
+        }
+        let entry = serde_json::json!({
+            "time_ns": now_ns(),
+            "parsed": tool,
+            "method": stats.match_method.label(),
+            "patterns_tried": stats.patterns_tried,
+            "items_before_dedup": stats.items_before_dedup,
+            "items_after_dedup": stats.items_after_dedup,
+            "frame": self.frame,
+        });
+        self.write_line(&entry.to_string());
+    }
+
+    /// Record something the user was only briefly told about, like a page
+    /// cut short by `max_content_bytes`
+    pub fn log_warning(&mut self, warning: &str) {
+        if !self.is_enabled() {
+            return;
+        }
+        let entry = serde_json::json!({
+            "time_ns": now_ns(),
+            "warning": warning,
+            "frame": self.frame,
+        });
+        self.write_line(&entry.to_string());
+    }
+
+    fn write_line(&mut self, line: &str) {
+        if self.file.is_none() {
+            self.file = self.open();
+        }
+        let written = match self.file {
+            Some(ref mut file) => writeln!(file, "{}", line).is_ok(),
+            None => false,
+        };
+        // A log that can't be written is dropped rather than retried every frame
+        if !written {
+            self.path = None;
+            self.file = None;
+        }
+    }
+
+    fn open(&self) -> Option<File> {
+        let path = self.path.as_ref()?;
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir).ok()?;
+        }
+        OpenOptions::new().create(true).append(true).open(path).ok()
+    }
+}
+
+fn now_ns() -> u64 {
+    SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map_or(0, |d| d.as_nanos() as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn log_path(name: &str) -> PathBuf {
+        std::env::temp_dir()
+            .join(format!("helpv-debug-{}-{}", name, std::process::id()))
+            .join("debug.jsonl")
+    }
+
+    #[test]
+    fn transitions_are_appended_as_json_lines() {
+        let path = log_path("lines");
+        let mut logger = DebugLogger::new(Some(path.clone()));
+        logger.next_frame();
+        logger.log_transition("Paging", "Finding", Some("f"), &["git".to_string()]);
+        logger.next_frame();
+        logger.log_transition("Finding", "Paging", None, &["git".to_string()]);
+
+        let text = std::fs::read_to_string(&path).unwrap();
+        let entries: Vec<serde_json::Value> = text
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0]["from"], "Paging");
+        assert_eq!(entries[0]["to"], "Finding");
+        assert_eq!(entries[0]["frame"], 1);
+        assert_eq!(entries[0]["key"], "f");
+        assert_eq!(entries[0]["command"], serde_json::json!(["git"]));
+        assert!(entries[0]["time_ns"].as_u64().unwrap() > 0);
+        assert_eq!(entries[1]["frame"], 2);
+        assert!(entries[1]["key"].is_null());
+
+        std::fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn parse_stats_are_logged() {
+        let path = log_path("parse");
+        let mut logger = DebugLogger::new(Some(path.clone()));
+        let stats = ParseStats {
+            patterns_tried: 3,
+            match_method: crate::parser::ParseMethod::GitStyle,
+            items_before_dedup: 12,
+            items_after_dedup: 10,
+        };
+        logger.log_parse("git", &stats);
+
+        let text = std::fs::read_to_string(&path).unwrap();
+        let entry: serde_json::Value = serde_json::from_str(text.trim()).unwrap();
+        assert_eq!(entry["parsed"], "git");
+        assert_eq!(entry["method"], "git-style");
+        assert_eq!(entry["patterns_tried"], 3);
+        assert_eq!(entry["items_before_dedup"], 12);
+        assert_eq!(entry["items_after_dedup"], 10);
+
+        std::fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn disabled_logger_writes_nothing() {
+        let mut logger = DebugLogger::new(None);
+        assert!(!logger.is_enabled());
+        logger.log_transition("Paging", "Help", Some("?"), &[]);
+        assert!(logger.file.is_none());
+    }
+
+    #[test]
+    fn file_is_opened_on_first_transition() {
+        let path = log_path("lazy");
+        let mut logger = DebugLogger::new(Some(path.clone()));
+        logger.next_frame();
+        assert!(!path.exists());
+
+        logger.log_transition("Paging", "Help", Some("?"), &[]);
+        assert!(path.exists());
+
+        std::fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+}
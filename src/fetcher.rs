@@ -1,19 +1,187 @@
 use anyhow::{Result, anyhow};
-use std::process::Command;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::ffi::OsStr;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+use std::sync::mpsc;
+use std::time::Duration;
 
 use crate::config::Config;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum ContentSource {
+    #[default]
     Help,
     Man,
+    Tldr,
+    Info,
+    /// Help flag output read from stderr even though stdout had output too
+    Stderr,
+    /// Saved page text shown because re-running the command failed
+    Cache {
+        age: Duration,
+    },
+    /// Help text read from a file given with `--file`
+    File(PathBuf),
+}
+
+impl ContentSource {
+    /// Parse a `help_flag_order` or tool pack `help_order` entry
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "help" => Some(Self::Help),
+            "man" => Some(Self::Man),
+            "tldr" => Some(Self::Tldr),
+            "info" => Some(Self::Info),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for ContentSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Help => write!(f, "help"),
+            Self::Man => write!(f, "man"),
+            Self::Tldr => write!(f, "tldr"),
+            Self::Info => write!(f, "info"),
+            Self::Stderr => write!(f, "stderr"),
+            Self::Cache { age } => write!(f, "cached {} ago", format_age(*age)),
+            Self::File(path) => write!(f, "file {}", path.display()),
+        }
+    }
+}
+
+/// Set in the environment of every command helpv runs
+pub const RUNNING_ENV: &str = "HELPV_RUNNING";
+
+/// A command for a help or discovery program. It is marked as run by helpv
+/// and pages with `less`, so a tool that opens `$PAGER` while helpv is the
+/// user's pager can't start helpv inside itself.
+pub(crate) fn child_command(program: impl AsRef<OsStr>) -> Command {
+    let mut command = Command::new(program);
+    command
+        .env(RUNNING_ENV, "1")
+        .env("PAGER", "less")
+        .env("MANPAGER", "less");
+    command
+}
+
+/// Coarse age like `45s`, `45m`, `2h`, or `3d`
+pub(crate) fn format_age(age: Duration) -> String {
+    let secs = age.as_secs();
+    match secs {
+        0..60 => format!("{}s", secs),
+        60..3600 => format!("{}m", secs / 60),
+        3600..86400 => format!("{}h", secs / 3600),
+        _ => format!("{}d", secs / 86400),
+    }
+}
+
+/// Sources to try for `tool`, in its help order, minus any disabled or
+/// unrecognized entries
+fn source_order(config: &Config, tool: &str) -> Vec<ContentSource> {
+    let mut order = Vec::new();
+    for source in config
+        .get_help_order(tool)
+        .iter()
+        .filter_map(|name| ContentSource::from_name(name))
+    {
+        let disabled = match source {
+            ContentSource::Help => config.disable_help_fallback,
+            ContentSource::Man => config.disable_man_fallback,
+            ContentSource::Tldr
+            | ContentSource::Info
+            | ContentSource::Stderr
+            | ContentSource::Cache { .. }
+            | ContentSource::File(_) => false,
+        };
+        if !disabled && !order.contains(&source) {
+            order.push(source);
+        }
+    }
+    order
+}
+
+/// Whether `source` is in `cmd`'s source order, so the thin-help upgrade and
+/// the parallel race run only what `help_order` or `help_flag_order` asks for
+fn in_order(cmd: &[String], config: &Config, source: &ContentSource) -> bool {
+    cmd.first()
+        .is_some_and(|tool| source_order(config, tool).contains(source))
+}
+
+/// Details about how fetched content was chosen
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct FetchMetadata {
+    /// A help command wrote more than half of `max_content_bytes` to both
+    /// stdout and stderr, so which one is shown was a guess
+    pub ambiguous_streams: bool,
+    /// The content was longer than `max_content_bytes` and was cut short
+    pub truncated: bool,
+    /// `man` output the content was stripped from, with its bold and
+    /// underline sequences still in place
+    pub man_raw: Option<String>,
+}
+
+/// Content with the source it came from and how it was chosen
+type Fetched = (String, ContentSource, FetchMetadata);
+
+/// Output stream of a help command
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HelpStream {
+    Stdout,
+    Stderr,
 }
 
 pub fn fetch_help(cmd: &[String], config: &Config) -> Result<String> {
+    fetch_from_sources(cmd, config, &[]).map(|(text, _, _)| text)
+}
+
+/// Try each configured source in order, returning the first meaningful output.
+/// Sources in `tried` already came up empty and are skipped.
+fn fetch_from_sources(cmd: &[String], config: &Config, tried: &[ContentSource]) -> Result<Fetched> {
     if cmd.is_empty() {
         return Err(anyhow!("No command specified"));
     }
 
+    for source in source_order(config, &cmd[0]) {
+        if tried.contains(&source) {
+            continue;
+        }
+        if let Some(fetched) = try_source(cmd, &source, config) {
+            return Ok(fetched);
+        }
+    }
+
+    Err(anyhow!("Could not fetch help for '{}'", cmd.join(" ")))
+}
+
+/// Meaningful output from a single source, if it has any. Help flags may
+/// answer with `ContentSource::Stderr` instead of `source`.
+fn try_source(cmd: &[String], source: &ContentSource, config: &Config) -> Option<Fetched> {
+    let plain = |output: Option<String>| {
+        output.map(|text| (text, source.clone(), FetchMetadata::default()))
+    };
+    let fetched = match source {
+        ContentSource::Help => try_help_flags(cmd, config),
+        ContentSource::Stderr => plain(try_help_stream(cmd, config, HelpStream::Stderr)),
+        ContentSource::Man => try_man_page(cmd, config),
+        ContentSource::Tldr => plain(try_tldr(cmd)),
+        ContentSource::Info => plain(try_info(cmd)),
+        ContentSource::Cache { .. } | ContentSource::File(_) => None,
+    };
+    fetched
+        .filter(|(output, _, _)| is_meaningful(output))
+        .map(|fetched| cap_fetched(fetched, config.max_content_bytes))
+}
+
+/// Help flags for `cmd` and the environment to run them in
+fn help_commands(cmd: &[String], config: &Config) -> (Vec<String>, HashMap<String, String>) {
     let base_cmd = &cmd[0];
     let is_subcommand = cmd.len() > 1;
 
@@ -23,37 +191,276 @@ pub fn fetch_help(cmd: &[String], config: &Config) -> Result<String> {
     } else {
         config.get_help_flags(base_cmd)
     };
+    (help_flags, config.get_env_overrides(base_cmd))
+}
 
-    for flag_pattern in &help_flags {
-        if let Some(output) = try_help_pattern(cmd, flag_pattern)
-            && !output.trim().is_empty()
-        {
-            return Ok(output);
-        }
+fn try_help_flags(cmd: &[String], config: &Config) -> Option<Fetched> {
+    let (help_flags, env) = help_commands(cmd, config);
+    help_flags
+        .iter()
+        .filter_map(|flag_pattern| {
+            let output = run_help_pattern(cmd, flag_pattern, &env, config.help_proxy.as_deref())?;
+            choose_help_output(&output, config.max_content_bytes)
+        })
+        .find(|(output, _, _)| is_meaningful(output))
+}
+
+/// Output of the first help flag that writes meaningful text to `stream`,
+/// regardless of what it writes to the other one
+fn try_help_stream(cmd: &[String], config: &Config, stream: HelpStream) -> Option<String> {
+    let (help_flags, env) = help_commands(cmd, config);
+    help_flags
+        .iter()
+        .filter_map(|flag_pattern| {
+            let output = run_help_pattern(cmd, flag_pattern, &env, config.help_proxy.as_deref())?;
+            let bytes = match stream {
+                HelpStream::Stdout => output.stdout,
+                HelpStream::Stderr => output.stderr,
+            };
+            Some(String::from_utf8_lossy(&bytes).into_owned())
+        })
+        .find(|output| is_meaningful(output))
+        .map(|output| cap_content(output, config.max_content_bytes))
+}
+
+/// Help for `cmd` read from one stream of its help flags, for switching
+/// between them when both had output. Stdout is reported as `Help`.
+pub fn fetch_help_stream(
+    cmd: &[String],
+    config: &Config,
+    stream: HelpStream,
+) -> Result<(String, ContentSource)> {
+    if cmd.is_empty() || config.disable_help_fallback {
+        return Err(anyhow!("Help flags are not available"));
     }
+    let content = try_help_stream(cmd, config, stream).ok_or_else(|| {
+        anyhow!(
+            "No help on {}",
+            match stream {
+                HelpStream::Stdout => "stdout",
+                HelpStream::Stderr => "stderr",
+            }
+        )
+    })?;
+    let source = match stream {
+        HelpStream::Stdout => ContentSource::Help,
+        HelpStream::Stderr => ContentSource::Stderr,
+    };
+    Ok((clean_content(cmd, &content, config), source))
+}
+
+/// Fetch the best content for a command: try help first, upgrade to man page if thin.
+pub fn fetch_best_content(cmd: &[String], config: &Config) -> Result<(String, ContentSource)> {
+    fetch_with_metadata(cmd, config).map(|(content, source, _)| (content, source))
+}
+
+/// Like `fetch_best_content`, also saying how the content was chosen
+pub fn fetch_with_metadata(cmd: &[String], config: &Config) -> Result<Fetched> {
+    let (content, source, metadata) = fetch_untrimmed_content(cmd, config)?;
+    Ok((clean_content(cmd, &content, config), source, metadata))
+}
 
-    // Try man page as fallback
-    if let Some(output) = try_man_page(cmd)
-        && !output.trim().is_empty()
+/// Like `fetch_best_content`, but try `preferred_source` first so a refresh
+/// stays on the same kind of page (e.g. tldr) when it is still available
+pub fn fetch_with_source_hint(
+    cmd: &[String],
+    config: &Config,
+    preferred_source: Option<ContentSource>,
+) -> Result<(String, ContentSource)> {
+    if let Some(ContentSource::File(path)) = preferred_source {
+        let content = read_help_file(&path)?;
+        return Ok((content, ContentSource::File(path)));
+    }
+    if let Some(source) = preferred_source
+        && !cmd.is_empty()
+        && is_available(&source, &source_order(config, &cmd[0]))
+        && let Some((content, source, _)) = try_source(cmd, &source, config)
     {
-        return Ok(output);
+        return Ok((clean_content(cmd, &content, config), source));
     }
 
-    Err(anyhow!("Could not fetch help for '{}'", cmd.join(" ")))
+    fetch_best_content(cmd, config)
 }
 
-/// Fetch the best content for a command: try help first, upgrade to man page if thin.
-pub fn fetch_best_content(cmd: &[String], config: &Config) -> Result<(String, ContentSource)> {
-    let help_text = fetch_help(cmd, config)?;
+/// Whether `source` may be fetched given the configured `order`. Stderr
+/// comes from the help flags, so it is available whenever they are.
+fn is_available(source: &ContentSource, order: &[ContentSource]) -> bool {
+    match source {
+        ContentSource::Stderr => order.contains(&ContentSource::Help),
+        _ => order.contains(source),
+    }
+}
+
+/// Read help text saved to a file. Man page source (troff, starting with `.\"`
+/// or `.TH`) is rendered with `man -l`; saved terminal output has its
+/// formatting sequences stripped.
+pub fn read_help_file(path: &Path) -> Result<String> {
+    let text = std::fs::read_to_string(path)
+        .map_err(|e| anyhow!("Could not read {}: {}", path.display(), e))?;
+    if !is_troff(&text) {
+        return Ok(strip_man_formatting(&text));
+    }
+
+    let rendered = child_command("man")
+        .arg("-l")
+        .arg(path)
+        .env("MANPAGER", "cat")
+        .env("PAGER", "cat")
+        .env("MAN_KEEP_FORMATTING", "0")
+        .output()
+        .ok()
+        .filter(|result| result.status.success())
+        .map(|result| String::from_utf8_lossy(&result.stdout).into_owned());
+    // Without `man`, the source is still more useful than nothing
+    Ok(strip_man_formatting(rendered.as_deref().unwrap_or(&text)))
+}
+
+/// Whether `text` looks like man page source rather than rendered help
+fn is_troff(text: &str) -> bool {
+    let start = text.trim_start();
+    start.starts_with(".\\\"") || start.starts_with(".TH") || start.starts_with("'\\\"")
+}
+
+/// Apply the configured noise stripping and trim trailing help pointers
+fn clean_content(cmd: &[String], content: &str, config: &Config) -> String {
+    let content = match cmd.first() {
+        Some(tool) => strip_noise(
+            content,
+            &config.strip_patterns_for(tool),
+            config.strip_prefix_lines_for(tool),
+        ),
+        None => content.to_string(),
+    };
+    strip_help_trailers(&content).to_string()
+}
+
+/// Drop the first `prefix_lines` lines, then any line matching one of `patterns`.
+/// Invalid patterns are ignored; config loading warns about them.
+pub fn strip_noise(content: &str, patterns: &[String], prefix_lines: usize) -> String {
+    if patterns.is_empty() && prefix_lines == 0 {
+        return content.to_string();
+    }
+
+    let patterns: Vec<Regex> = patterns.iter().filter_map(|p| Regex::new(p).ok()).collect();
+    content
+        .lines()
+        .skip(prefix_lines)
+        .filter(|line| !patterns.iter().any(|re| re.is_match(line)))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn fetch_untrimmed_content(cmd: &[String], config: &Config) -> Result<Fetched> {
+    let mut tried = Vec::new();
+    if config.parallel_fetch && !cmd.is_empty() {
+        if let Some(result) = fetch_parallel(cmd, config) {
+            return Ok(result);
+        }
+        // Neither stream of the help flags nor `man` had anything, so only
+        // the remaining sources are worth running
+        tried = vec![
+            ContentSource::Help,
+            ContentSource::Stderr,
+            ContentSource::Man,
+        ];
+    }
+
+    let fetched = fetch_from_sources(cmd, config, &tried)?;
+    let man_in_order = in_order(cmd, config, &ContentSource::Man);
+    Ok(upgrade_thin_help(
+        fetched,
+        || man_in_order.then(|| try_man_page(cmd, config)).flatten(),
+        config.max_content_bytes,
+    ))
+}
 
-    if is_thin(&help_text)
-        && let Some(man_text) = try_man_page(cmd)
-        && !man_text.trim().is_empty()
+/// Replace a thin `--help` page with the man page from `man` when that has
+/// more to say, capped to `max_bytes` like any other fetch
+fn upgrade_thin_help(
+    fetched: Fetched,
+    man: impl FnOnce() -> Option<Fetched>,
+    max_bytes: usize,
+) -> Fetched {
+    if fetched.1 == ContentSource::Help
+        && is_thin(&fetched.0)
+        && let Some(man) = man().filter(|(text, _, _)| is_meaningful(text))
     {
-        return Ok((man_text, ContentSource::Man));
+        return cap_fetched(man, max_bytes);
+    }
+    fetched
+}
+
+/// Fetch the other of `--help` and `man` for a page that came from `source`,
+/// cleaned like the page itself, so the two can be compared or swapped. Tldr
+/// and info pages have no alternate.
+pub fn fetch_alternate_content(
+    cmd: &[String],
+    source: &ContentSource,
+    config: &Config,
+) -> Option<(String, ContentSource)> {
+    if cmd.is_empty() {
+        return None;
+    }
+
+    let alternate = match *source {
+        ContentSource::Help | ContentSource::Stderr => {
+            try_man_page(cmd, config).map(|(text, source, _)| (text, source))
+        }
+        ContentSource::Man if !config.disable_help_fallback => {
+            try_help_flags(cmd, config).map(|(text, source, _)| (text, source))
+        }
+        _ => None,
+    };
+    alternate
+        .filter(|(text, _)| is_meaningful(text))
+        .map(|(text, source)| {
+            let text = cap_content(text, config.max_content_bytes);
+            (clean_content(cmd, &text, config), source)
+        })
+}
+
+/// Race help flags against `man` on separate threads, each only if the
+/// tool's source order includes it. The first meaningful result wins, except
+/// that thin help output still waits for the man page upgrade. Returns `None`
+/// if neither produces anything, so the caller can fall back to the other
+/// sources.
+fn fetch_parallel(cmd: &[String], config: &Config) -> Option<Fetched> {
+    let (tx, rx) = mpsc::channel();
+
+    if in_order(cmd, config, &ContentSource::Help) {
+        let tx = tx.clone();
+        let cmd = cmd.to_vec();
+        let config = config.clone();
+        std::thread::spawn(move || {
+            let _ = tx.send(try_help_flags(&cmd, &config));
+        });
+    }
+
+    if in_order(cmd, config, &ContentSource::Man) {
+        let tx = tx.clone();
+        let cmd = cmd.to_vec();
+        let config = config.clone();
+        std::thread::spawn(move || {
+            let _ = tx.send(try_man_page(&cmd, &config));
+        });
     }
+    // Only the threads hold senders now, so `rx` ends once both are done
+    drop(tx);
 
-    Ok((help_text, ContentSource::Help))
+    let mut thin_help = None;
+    // Dropping `rx` on return abandons the slower thread
+    for fetched in rx {
+        let Some(fetched) = fetched.filter(|(text, _, _)| is_meaningful(text)) else {
+            continue;
+        };
+        if fetched.1 == ContentSource::Help && is_thin(&fetched.0) {
+            thin_help = Some(fetched);
+            continue;
+        }
+        return Some(cap_fetched(fetched, config.max_content_bytes));
+    }
+
+    thin_help.map(|fetched| cap_fetched(fetched, config.max_content_bytes))
 }
 
 /// Fetch help using a specific invoke command template
@@ -61,6 +468,7 @@ pub fn fetch_help_with_invoke(
     base_cmd: &str,
     item_name: &str,
     invoke_template: &str,
+    env: &HashMap<String, String>,
 ) -> Result<String> {
     let cmd_str = invoke_template
         .replace("{base}", base_cmd)
@@ -71,15 +479,18 @@ pub fn fetch_help_with_invoke(
         return Err(anyhow!("Invalid invoke command"));
     }
 
-    let result = Command::new(parts[0]).args(&parts[1..]).output()?;
+    let result = child_command(parts[0])
+        .args(&parts[1..])
+        .envs(env)
+        .output()?;
 
     // Some tools write help to stderr
     let stdout = String::from_utf8_lossy(&result.stdout);
     let stderr = String::from_utf8_lossy(&result.stderr);
 
-    if !stdout.trim().is_empty() {
+    if is_meaningful(&stdout) {
         Ok(stdout.into_owned())
-    } else if !stderr.trim().is_empty() && (result.status.success() || looks_like_help(&stderr)) {
+    } else if is_meaningful(&stderr) && (result.status.success() || looks_like_help(&stderr)) {
         Ok(stderr.into_owned())
     } else {
         Err(anyhow!(
@@ -90,53 +501,153 @@ pub fn fetch_help_with_invoke(
     }
 }
 
-fn try_help_pattern(cmd: &[String], pattern: &str) -> Option<String> {
-    let full_cmd = cmd.join(" ");
-    let base = &cmd[0];
-    let sub = if cmd.len() > 1 {
-        cmd[1..].join(" ")
-    } else {
-        String::new()
-    };
-
-    let expanded = pattern
-        .replace("{cmd}", &full_cmd)
-        .replace("{base}", base)
-        .replace("{sub}", &sub);
-
+/// Run one help command template for `cmd`
+fn run_help_pattern(
+    cmd: &[String],
+    pattern: &str,
+    env: &HashMap<String, String>,
+    proxy: Option<&str>,
+) -> Option<Output> {
+    let expanded = expand_help_template(pattern, cmd);
     let parts: Vec<&str> = expanded.split_whitespace().collect();
     if parts.is_empty() {
         return None;
     }
+    let argv = match proxy {
+        Some(proxy) => proxy_argv(proxy, &parts),
+        None => parts.iter().map(|p| p.to_string()).collect(),
+    };
+    if argv.is_empty() {
+        return None;
+    }
 
-    let result = Command::new(parts[0]).args(&parts[1..]).output().ok()?;
+    child_command(&argv[0])
+        .args(&argv[1..])
+        .envs(env)
+        .output()
+        .ok()
+}
 
+/// Pick the help text from a help command's output. Stdout wins unless both
+/// streams are over half of `max_bytes`, in which case stdout must look like
+/// help to be preferred and the pick is flagged as ambiguous.
+fn choose_help_output(result: &Output, max_bytes: usize) -> Option<Fetched> {
     // Some tools write help to stderr
     let stdout = String::from_utf8_lossy(&result.stdout);
     let stderr = String::from_utf8_lossy(&result.stderr);
 
-    if !stdout.trim().is_empty() {
-        Some(stdout.into_owned())
-    } else if !stderr.trim().is_empty() && result.status.success() {
-        Some(stderr.into_owned())
-    } else if !stderr.trim().is_empty() {
-        // Some tools return non-zero but still output help to stderr
-        let stderr_str = stderr.into_owned();
-        if looks_like_help(&stderr_str) {
-            Some(stderr_str)
+    let half = max_bytes / 2;
+    if max_bytes > 0 && stdout.len() > half && stderr.len() > half {
+        let ambiguous = FetchMetadata {
+            ambiguous_streams: true,
+            ..Default::default()
+        };
+        return Some(if looks_like_help(&stdout) {
+            (stdout.into_owned(), ContentSource::Help, ambiguous)
         } else {
-            None
-        }
+            (stderr.into_owned(), ContentSource::Stderr, ambiguous)
+        });
+    }
+
+    let help = |text: Cow<str>| {
+        (
+            text.into_owned(),
+            ContentSource::Help,
+            FetchMetadata::default(),
+        )
+    };
+    if is_meaningful(&stdout) {
+        Some(help(stdout))
+    } else if is_meaningful(&stderr) && result.status.success() {
+        Some(help(stderr))
+    } else if is_meaningful(&stderr) && looks_like_help(&stderr) {
+        // Some tools return non-zero but still output help to stderr
+        Some(help(stderr))
     } else {
         None
     }
 }
 
-fn try_man_page(cmd: &[String]) -> Option<String> {
+/// Fill in a help command template for `cmd`: `{cmd}` is the whole command,
+/// `{base}` its first word, `{sub}` or `{sub_path}` the rest, and `{sub_N}`
+/// the Nth subcommand (zero-indexed, empty when the command isn't that deep)
+fn expand_help_template(pattern: &str, cmd: &[String]) -> String {
+    let sub_path = cmd.get(1..).unwrap_or_default().join(" ");
+    let expanded = pattern
+        .replace("{cmd}", &cmd.join(" "))
+        .replace("{base}", cmd.first().map_or("", String::as_str))
+        .replace("{sub_path}", &sub_path)
+        .replace("{sub}", &sub_path);
+
+    let indexed = Regex::new(r"\{sub_(\d+)\}").expect("valid regex");
+    indexed
+        .replace_all(&expanded, |caps: &regex::Captures| {
+            caps[1]
+                .parse::<usize>()
+                .ok()
+                .and_then(|n| cmd.get(n + 1))
+                .cloned()
+                .unwrap_or_default()
+        })
+        .into_owned()
+}
+
+/// Wrap a help command in the `help_proxy` template. `{cmd}` becomes the
+/// program and `{args}` its arguments, each as separate words; a template with
+/// neither gets the whole command appended.
+fn proxy_argv(proxy: &str, parts: &[&str]) -> Vec<String> {
+    let mut argv = Vec::new();
+    let mut substituted = false;
+    for word in proxy.split_whitespace() {
+        match word {
+            "{cmd}" => {
+                argv.push(parts[0].to_string());
+                substituted = true;
+            }
+            "{args}" => {
+                argv.extend(parts[1..].iter().map(|p| p.to_string()));
+                substituted = true;
+            }
+            _ => argv.push(word.to_string()),
+        }
+    }
+    if !substituted {
+        argv.extend(parts.iter().map(|p| p.to_string()));
+    }
+    argv
+}
+
+/// The man page for `cmd`, keeping the raw output in the metadata so its
+/// formatting can be drawn without running `man` again
+fn try_man_page(cmd: &[String], config: &Config) -> Option<Fetched> {
+    let raw = fetch_man_formatting(cmd, config)?;
+    let text = strip_man_formatting(&raw);
+    let metadata = FetchMetadata {
+        man_raw: Some(raw),
+        ..Default::default()
+    };
+    Some((text, ContentSource::Man, metadata))
+}
+
+/// Raw `man` output for `cmd`, with its backspace bold/underline sequences
+/// still in place
+pub fn fetch_man_formatting(cmd: &[String], config: &Config) -> Option<String> {
+    if config.disable_man_fallback || cmd.is_empty() {
+        return None;
+    }
+
     let man_page = cmd.join("-");
+    let manpath = config.man_search_path();
+    let man = |page: &str| {
+        let mut command = child_command("man");
+        command.arg(page);
+        if let Some(ref manpath) = manpath {
+            command.env("MANPATH", manpath);
+        }
+        command
+    };
 
-    let result = Command::new("man")
-        .arg(&man_page)
+    let result = man(&man_page)
         .env("MANPAGER", "cat")
         .env("PAGER", "cat")
         .env("MAN_KEEP_FORMATTING", "0")
@@ -144,14 +655,11 @@ fn try_man_page(cmd: &[String]) -> Option<String> {
         .ok()?;
 
     if result.status.success() {
-        let output = String::from_utf8_lossy(&result.stdout);
-        // Strip man formatting (backspace sequences)
-        Some(strip_man_formatting(&output))
+        Some(String::from_utf8_lossy(&result.stdout).into_owned())
     } else {
         // Try without joining for single commands
         if cmd.len() == 1 {
-            let result = Command::new("man")
-                .arg(&cmd[0])
+            let result = man(&cmd[0])
                 .env("MANPAGER", "cat")
                 .env("PAGER", "cat")
                 .env("MAN_KEEP_FORMATTING", "0")
@@ -159,15 +667,87 @@ fn try_man_page(cmd: &[String]) -> Option<String> {
                 .ok()?;
 
             if result.status.success() {
-                let output = String::from_utf8_lossy(&result.stdout);
-                return Some(strip_man_formatting(&output));
+                return Some(String::from_utf8_lossy(&result.stdout).into_owned());
             }
         }
         None
     }
 }
 
-fn strip_man_formatting(text: &str) -> String {
+/// `cap_content` for fetched content, noting in its metadata when it was cut
+fn cap_fetched((text, source, mut metadata): Fetched, max_bytes: usize) -> Fetched {
+    metadata.truncated = max_bytes > 0 && text.len() > max_bytes;
+    (cap_content(text, max_bytes), source, metadata)
+}
+
+/// Cut `text` to at most `max_bytes` (on a line boundary when possible) and
+/// mark where it was cut. `max_bytes` of 0 means no limit.
+fn cap_content(text: String, max_bytes: usize) -> String {
+    if max_bytes == 0 || text.len() <= max_bytes {
+        return text;
+    }
+
+    let mut end = max_bytes;
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    let end = text[..end].rfind('\n').unwrap_or(end);
+    format!(
+        "{}\n... (output truncated at {}) ...",
+        &text[..end],
+        format_size(max_bytes)
+    )
+}
+
+/// Byte count in the largest unit that divides it evenly, e.g. "1 MiB"
+fn format_size(bytes: usize) -> String {
+    const KIB: usize = 1024;
+    const MIB: usize = 1024 * KIB;
+    if bytes.is_multiple_of(MIB) {
+        format!("{} MiB", bytes / MIB)
+    } else if bytes.is_multiple_of(KIB) {
+        format!("{} KiB", bytes / KIB)
+    } else {
+        format!("{} bytes", bytes)
+    }
+}
+
+/// Fetch a tldr page (`git commit` -> `tldr git-commit`)
+fn try_tldr(cmd: &[String]) -> Option<String> {
+    let result = child_command("tldr").arg(cmd.join("-")).output().ok()?;
+
+    if result.status.success() {
+        let output = String::from_utf8_lossy(&result.stdout);
+        Some(strip_man_formatting(&output))
+    } else {
+        None
+    }
+}
+
+/// Fetch a GNU info node, written to stdout instead of the interactive reader
+fn try_info(cmd: &[String]) -> Option<String> {
+    let result = child_command("info")
+        .args(["--output", "-"])
+        .args(cmd)
+        .output()
+        .ok()?;
+
+    if result.status.success() {
+        let output = String::from_utf8_lossy(&result.stdout);
+        Some(strip_man_formatting(&output))
+    } else {
+        None
+    }
+}
+
+/// Remove terminal formatting from man or help output: backspace overstrikes
+/// (bold `c\x08c`, underline `_\x08c`, and both at once) and ANSI CSI
+/// escape sequences such as colors.
+///
+/// Works on whole characters, so multi-byte UTF-8 text is never split, and
+/// is idempotent: the result contains no backspaces or escapes, so stripping
+/// it again changes nothing.
+pub fn strip_man_formatting(text: &str) -> String {
     let mut result = String::with_capacity(text.len());
     let mut chars = text.chars().peekable();
 
@@ -194,6 +774,45 @@ fn strip_man_formatting(text: &str) -> String {
     result
 }
 
+/// Minimum visible characters for output to count as help rather than noise
+const MEANINGFUL_CONTENT_MIN_CHARS: usize = 10;
+
+/// Whether output has real content once formatting codes and whitespace are removed.
+/// Catches tools that emit only ANSI resets or a few stray bytes.
+fn is_meaningful(text: &str) -> bool {
+    strip_man_formatting(text).trim().len() > MEANINGFUL_CONTENT_MIN_CHARS
+}
+
+/// Most trailing lines `strip_help_trailers` may remove, so real content survives
+const MAX_TRAILER_LINES: usize = 3;
+
+/// Trim boilerplate pointers like "For more information, try '--help'." from the
+/// end of help output. Only the last few non-blank lines are considered, and
+/// content made up of nothing but trailers is left alone.
+pub fn strip_help_trailers(content: &str) -> &str {
+    let trailer_re =
+        Regex::new(r#"(?i)^(for more info|run .*--help|use ["'].*["'] for|see .* for)"#).unwrap();
+
+    let mut end = content.len();
+    for _ in 0..MAX_TRAILER_LINES {
+        let body = content[..end].trim_end();
+        let (rest, last) = match body.rfind('\n') {
+            Some(i) => (&body[..i], &body[i + 1..]),
+            None => ("", body),
+        };
+        if rest.trim().is_empty() || !trailer_re.is_match(last.trim()) {
+            break;
+        }
+        end = rest.trim_end().len();
+    }
+
+    if end == content.len() {
+        content
+    } else {
+        &content[..end]
+    }
+}
+
 const THIN_CONTENT_THRESHOLD: usize = 10;
 
 fn is_thin(content: &str) -> bool {
@@ -204,7 +823,10 @@ fn is_thin(content: &str) -> bool {
     non_blank_lines < THIN_CONTENT_THRESHOLD
 }
 
-fn looks_like_help(text: &str) -> bool {
+/// Whether `text` reads like help output (a usage line, options or commands
+/// heading, `--help` mention or man SYNOPSIS), case-insensitively. Used to
+/// tell help printed to stderr apart from an error message.
+pub fn looks_like_help(text: &str) -> bool {
     let lower = text.to_lowercase();
     lower.contains("usage:")
         || lower.contains("options:")
@@ -217,6 +839,278 @@ fn looks_like_help(text: &str) -> bool {
 mod tests {
     use super::*;
 
+    // ========================================
+    // cap_content tests
+    // ========================================
+
+    fn git() -> Vec<String> {
+        vec!["git".to_string()]
+    }
+
+    #[test]
+    fn cap_content_leaves_small_output_alone() {
+        let text = "usage: git\n".to_string();
+        assert_eq!(cap_content(text.clone(), 1024), text);
+        assert_eq!(cap_content(text.clone(), 0), text);
+    }
+
+    #[test]
+    fn cap_content_truncates_at_line_boundary() {
+        let text = "line one\nline two\nline three\n".repeat(100_000);
+        let capped = cap_content(text, 1_048_576);
+        assert!(capped.ends_with("\n... (output truncated at 1 MiB) ..."));
+        let body = capped.rsplit_once("\n...").unwrap().0;
+        assert!(body.len() <= 1_048_576);
+        assert!(
+            body.ends_with("line one")
+                || body.ends_with("line two")
+                || body.ends_with("line three")
+        );
+    }
+
+    #[test]
+    fn cap_content_handles_multibyte_text_without_newlines() {
+        // 3-byte chars, so the limit lands mid-character
+        let capped = cap_content("─".repeat(1000), 1000);
+        assert!(capped.starts_with('─'));
+        assert!(capped.ends_with("... (output truncated at 1000 bytes) ..."));
+    }
+
+    #[test]
+    fn cap_fetched_flags_truncation() {
+        let fetched = |text: &str| {
+            (
+                text.to_string(),
+                ContentSource::Help,
+                FetchMetadata::default(),
+            )
+        };
+        let (_, _, metadata) = cap_fetched(fetched("usage: git\n"), 1024);
+        assert!(!metadata.truncated);
+        let (text, _, metadata) = cap_fetched(fetched(&"usage: git\n".repeat(200)), 1024);
+        assert!(metadata.truncated);
+        assert!(text.ends_with("(output truncated at 1 KiB) ..."));
+    }
+
+    #[test]
+    fn thin_help_upgrade_is_capped() {
+        let help = (
+            "usage: tool".to_string(),
+            ContentSource::Help,
+            FetchMetadata::default(),
+        );
+        let man_page = "TOOL(1)\n       tool - does things\n".repeat(100);
+        let man = || {
+            Some((
+                man_page.clone(),
+                ContentSource::Man,
+                FetchMetadata::default(),
+            ))
+        };
+
+        let (text, source, metadata) = upgrade_thin_help(help, man, 1024);
+        assert_eq!(source, ContentSource::Man);
+        assert!(metadata.truncated);
+        assert!(text.ends_with("(output truncated at 1 KiB) ..."));
+    }
+
+    #[test]
+    fn format_size_picks_even_unit() {
+        assert_eq!(format_size(1_048_576), "1 MiB");
+        assert_eq!(format_size(2048), "2 KiB");
+        assert_eq!(format_size(1500), "1500 bytes");
+    }
+
+    // ========================================
+    // expand_help_template tests
+    // ========================================
+
+    fn cmd(parts: &[&str]) -> Vec<String> {
+        parts.iter().map(|p| p.to_string()).collect()
+    }
+
+    #[test]
+    fn template_fills_cmd_base_and_sub() {
+        let git = cmd(&["git", "remote", "add"]);
+        assert_eq!(
+            expand_help_template("{cmd} --help", &git),
+            "git remote add --help"
+        );
+        assert_eq!(
+            expand_help_template("{base} help {sub}", &git),
+            "git help remote add"
+        );
+        assert_eq!(
+            expand_help_template("{base} help {sub_path}", &git),
+            "git help remote add"
+        );
+    }
+
+    #[test]
+    fn template_fills_indexed_subcommands() {
+        let git = cmd(&["git", "remote", "add"]);
+        assert_eq!(
+            expand_help_template("{base} {sub_0} --help {sub_1}", &git),
+            "git remote --help add"
+        );
+        assert_eq!(
+            expand_help_template("{base} help {sub_0}", &cmd(&["git"])),
+            "git help "
+        );
+        assert_eq!(expand_help_template("{sub_2}{sub_10}", &git), "");
+    }
+
+    // ========================================
+    // proxy_argv tests
+    // ========================================
+
+    #[test]
+    fn proxy_argv_places_cmd_and_args() {
+        assert_eq!(
+            proxy_argv(
+                "corp-wrapper run --tool {cmd} {args}",
+                &["git", "log", "--help"]
+            ),
+            vec!["corp-wrapper", "run", "--tool", "git", "log", "--help"]
+        );
+    }
+
+    #[test]
+    fn proxy_argv_allows_args_before_cmd() {
+        assert_eq!(
+            proxy_argv("wrap {args} -- {cmd}", &["kubectl", "--help"]),
+            vec!["wrap", "--help", "--", "kubectl"]
+        );
+    }
+
+    #[test]
+    fn proxy_argv_appends_command_without_placeholders() {
+        assert_eq!(
+            proxy_argv("sudo -u docs", &["man", "ls"]),
+            vec!["sudo", "-u", "docs", "man", "ls"]
+        );
+    }
+
+    #[test]
+    fn help_proxy_wraps_help_command() {
+        // `env` runs the wrapped command, so the output comes through unchanged
+        let output = run_help_pattern(
+            &["echo".to_string()],
+            "{cmd} proxied help output",
+            &HashMap::new(),
+            Some("env {cmd} {args}"),
+        )
+        .unwrap();
+        assert_eq!(output.stdout, b"proxied help output\n");
+    }
+
+    // ========================================
+    // Help stream choice tests
+    // ========================================
+
+    #[cfg(unix)]
+    fn output(stdout: &str, stderr: &str, code: i32) -> Output {
+        use std::os::unix::process::ExitStatusExt;
+        Output {
+            status: std::process::ExitStatus::from_raw(code << 8),
+            stdout: stdout.as_bytes().to_vec(),
+            stderr: stderr.as_bytes().to_vec(),
+        }
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn small_outputs_prefer_stdout_without_ambiguity() {
+        let result = output("usage: tool [options]", "warning: deprecated flag used", 0);
+        let (text, source, metadata) = choose_help_output(&result, 100).unwrap();
+        assert_eq!(text, "usage: tool [options]");
+        assert_eq!(source, ContentSource::Help);
+        assert!(!metadata.ambiguous_streams);
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn large_outputs_on_both_streams_are_ambiguous() {
+        let help = format!("Usage: tool\n{}", "  -x  an option\n".repeat(10));
+        let noise = "loading module...\n".repeat(10);
+
+        let (text, source, metadata) = choose_help_output(&output(&help, &noise, 0), 100).unwrap();
+        assert_eq!(text, help);
+        assert_eq!(source, ContentSource::Help);
+        assert!(metadata.ambiguous_streams);
+
+        // Stdout that doesn't look like help loses to stderr
+        let (text, source, metadata) = choose_help_output(&output(&noise, &help, 0), 100).unwrap();
+        assert_eq!(text, help);
+        assert_eq!(source, ContentSource::Stderr);
+        assert!(metadata.ambiguous_streams);
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn unlimited_content_is_never_ambiguous() {
+        let noise = "loading module...\n".repeat(10);
+        let (_, source, metadata) = choose_help_output(&output(&noise, &noise, 0), 0).unwrap();
+        assert_eq!(source, ContentSource::Help);
+        assert!(!metadata.ambiguous_streams);
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn failing_command_needs_help_like_stderr() {
+        assert!(choose_help_output(&output("", "error: unknown flag --help", 1), 100).is_some());
+        assert!(choose_help_output(&output("", "segmentation fault (core)", 1), 100).is_none());
+    }
+
+    #[test]
+    fn stderr_source_is_available_with_help_flags() {
+        let order = source_order(&Config::default(), "git");
+        assert!(is_available(&ContentSource::Stderr, &order));
+
+        let config = Config {
+            disable_help_fallback: true,
+            ..Default::default()
+        };
+        let order = source_order(&config, "git");
+        assert!(!is_available(&ContentSource::Stderr, &order));
+        assert!(fetch_help_stream(&git(), &config, HelpStream::Stderr).is_err());
+    }
+
+    // ========================================
+    // read_help_file tests
+    // ========================================
+
+    #[test]
+    fn is_troff_detects_man_source() {
+        assert!(is_troff(".\\\" generated by pandoc\n.TH LS 1\n"));
+        assert!(is_troff(".TH GIT 1 2024\n.SH NAME\n"));
+        assert!(!is_troff("Usage: ls [OPTION]... [FILE]...\n"));
+    }
+
+    #[test]
+    fn read_help_file_strips_rendered_man_formatting() {
+        let dir = std::env::temp_dir().join(format!("helpv-file-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let plain = dir.join("plain.txt");
+        std::fs::write(&plain, "Usage: tool\n  build  Build it\n").unwrap();
+        let rendered = dir.join("rendered.txt");
+        std::fs::write(&rendered, "N\x08NA\x08AM\x08ME\x08E\n").unwrap();
+
+        assert_eq!(
+            read_help_file(&plain).unwrap(),
+            "Usage: tool\n  build  Build it\n"
+        );
+        assert_eq!(read_help_file(&rendered).unwrap(), "NAME\n");
+        assert!(read_help_file(&dir.join("missing.txt")).is_err());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn file_source_shows_path() {
+        let source = ContentSource::File(PathBuf::from("/tmp/kubectl.txt"));
+        assert_eq!(source.to_string(), "file /tmp/kubectl.txt");
+    }
+
     // ========================================
     // strip_man_formatting tests
     // ========================================
@@ -271,6 +1165,27 @@ mod tests {
         assert_eq!(result, "Line one\nLine two\nLine three");
     }
 
+    #[test]
+    fn strip_bold_underline_and_utf8() {
+        // groff marks bold+underline as `_\x08c\x08c`; overstrikes can hit non-ASCII
+        let input = "_\x08é\x08é_\x08t\x08t_\x08é\x08é";
+        assert_eq!(strip_man_formatting(input), "été");
+    }
+
+    #[test]
+    fn strip_is_idempotent() {
+        let inputs = [
+            "N\x08NA\x08AM\x08ME\x08E",
+            "\x1b[1mH\x08He\x08el\x08lp\x08p\x1b[0m - description",
+            "stray \x1b escape and trailing \x08",
+            "plain ünïcode — text",
+        ];
+        for input in inputs {
+            let once = strip_man_formatting(input);
+            assert_eq!(strip_man_formatting(&once), once);
+        }
+    }
+
     #[test]
     fn strip_complex_ansi_sequences() {
         // ANSI with multiple parameters: \x1b[38;5;196m (256-color red)
@@ -343,6 +1258,316 @@ mod tests {
         assert!(looks_like_help("options: bar"));
     }
 
+    // ========================================
+    // source_order tests
+    // ========================================
+
+    #[test]
+    fn default_order_is_help_then_man() {
+        let config = Config::default();
+        assert_eq!(
+            source_order(&config, "git"),
+            vec![ContentSource::Help, ContentSource::Man]
+        );
+    }
+
+    #[test]
+    fn order_follows_config() {
+        let config = Config {
+            help_flag_order: vec!["tldr".to_string(), "man".to_string(), "info".to_string()],
+            ..Default::default()
+        };
+        assert_eq!(
+            source_order(&config, "git"),
+            vec![ContentSource::Tldr, ContentSource::Man, ContentSource::Info]
+        );
+    }
+
+    #[test]
+    fn order_skips_disabled_sources() {
+        let config = Config {
+            disable_man_fallback: true,
+            ..Default::default()
+        };
+        assert_eq!(source_order(&config, "git"), vec![ContentSource::Help]);
+
+        let config = Config {
+            disable_help_fallback: true,
+            ..Default::default()
+        };
+        assert_eq!(source_order(&config, "git"), vec![ContentSource::Man]);
+    }
+
+    #[test]
+    fn order_ignores_unknown_and_duplicate_entries() {
+        let config = Config {
+            help_flag_order: vec![
+                "man".to_string(),
+                "bogus".to_string(),
+                "man".to_string(),
+                "help".to_string(),
+            ],
+            ..Default::default()
+        };
+        assert_eq!(
+            source_order(&config, "git"),
+            vec![ContentSource::Man, ContentSource::Help]
+        );
+    }
+
+    #[test]
+    fn order_uses_tool_pack_help_order() {
+        let mut config = Config {
+            toolpacks: crate::toolpacks::ToolPacks::load_toml(
+                r#"
+[ansible-doc]
+help_order = ["man", "cheat", "help"]
+"#,
+            )
+            .unwrap(),
+            ..Default::default()
+        };
+        assert_eq!(
+            source_order(&config, "ansible-doc"),
+            vec![ContentSource::Man, ContentSource::Help]
+        );
+        assert_eq!(
+            source_order(&config, "git"),
+            vec![ContentSource::Help, ContentSource::Man]
+        );
+
+        // Disabling a source still applies to per-tool orders
+        config.disable_man_fallback = true;
+        assert_eq!(
+            source_order(&config, "ansible-doc"),
+            vec![ContentSource::Help]
+        );
+    }
+
+    #[test]
+    fn all_sources_disabled_is_an_error() {
+        let config = Config {
+            disable_man_fallback: true,
+            disable_help_fallback: true,
+            ..Default::default()
+        };
+        assert!(fetch_help(&["git".to_string()], &config).is_err());
+    }
+
+    #[test]
+    fn man_disabled_returns_none() {
+        let config = Config {
+            disable_man_fallback: true,
+            ..Default::default()
+        };
+        assert!(try_man_page(&["git".to_string()], &config).is_none());
+    }
+
+    #[test]
+    fn man_runs_only_when_in_order() {
+        let mut config = Config {
+            toolpacks: crate::toolpacks::ToolPacks::load_toml(
+                r#"
+[ansible-doc]
+help_order = ["help", "tldr"]
+"#,
+            )
+            .unwrap(),
+            ..Default::default()
+        };
+        let cmd = |tool: &str| vec![tool.to_string(), "sub".to_string()];
+        assert!(!in_order(&cmd("ansible-doc"), &config, &ContentSource::Man));
+        assert!(in_order(&cmd("git"), &config, &ContentSource::Man));
+        assert!(!in_order(&[], &config, &ContentSource::Man));
+
+        config.help_flag_order = vec!["help".to_string()];
+        assert!(!in_order(&cmd("git"), &config, &ContentSource::Man));
+        assert!(in_order(&cmd("git"), &config, &ContentSource::Help));
+    }
+
+    #[test]
+    fn parallel_fetch_with_all_sources_disabled_is_an_error() {
+        let config = Config {
+            parallel_fetch: true,
+            disable_man_fallback: true,
+            disable_help_fallback: true,
+            ..Default::default()
+        };
+        assert!(fetch_best_content(&["git".to_string()], &config).is_err());
+    }
+
+    #[test]
+    fn source_hint_outside_configured_order_is_ignored() {
+        let config = Config {
+            disable_man_fallback: true,
+            disable_help_fallback: true,
+            ..Default::default()
+        };
+        let cmd = ["git".to_string()];
+        assert!(fetch_with_source_hint(&cmd, &config, Some(ContentSource::Tldr)).is_err());
+        assert!(fetch_with_source_hint(&cmd, &config, Some(ContentSource::Man)).is_err());
+    }
+
+    #[test]
+    fn cache_source_displays_age() {
+        let cached = |secs| ContentSource::Cache {
+            age: Duration::from_secs(secs),
+        };
+        assert_eq!(cached(30).to_string(), "cached 30s ago");
+        assert_eq!(cached(45 * 60).to_string(), "cached 45m ago");
+        assert_eq!(cached(2 * 3600 + 59).to_string(), "cached 2h ago");
+        assert_eq!(cached(3 * 86400).to_string(), "cached 3d ago");
+        assert_eq!(ContentSource::Man.to_string(), "man");
+    }
+
+    // ========================================
+    // is_meaningful tests
+    // ========================================
+
+    #[test]
+    fn meaningful_regular_help() {
+        assert!(is_meaningful("usage: foo [options]\n  -h  Show help"));
+    }
+
+    #[test]
+    fn not_meaningful_empty() {
+        assert!(!is_meaningful(""));
+    }
+
+    #[test]
+    fn not_meaningful_whitespace_only() {
+        assert!(!is_meaningful("   \n\t\n   \n"));
+    }
+
+    #[test]
+    fn not_meaningful_ansi_only() {
+        assert!(!is_meaningful("\x1b[0m\x1b[0m\n\x1b[39;49m\x1b[0m"));
+    }
+
+    #[test]
+    fn not_meaningful_ansi_wrapped_short_text() {
+        assert!(!is_meaningful("\x1b[1mError\x1b[0m\n"));
+    }
+
+    #[test]
+    fn meaningful_ansi_wrapped_help() {
+        assert!(is_meaningful(
+            "\x1b[1mUsage:\x1b[0m tool [OPTIONS] <COMMAND>"
+        ));
+    }
+
+    // ========================================
+    // strip_noise tests
+    // ========================================
+
+    fn patterns(list: &[&str]) -> Vec<String> {
+        list.iter().map(|p| p.to_string()).collect()
+    }
+
+    #[test]
+    fn strip_noise_removes_matching_lines() {
+        let content = "WARNING: terminal is not fully functional\nUsage: tool\n  run  Run it";
+        assert_eq!(
+            strip_noise(content, &patterns(&["^WARNING:"]), 0),
+            "Usage: tool\n  run  Run it"
+        );
+    }
+
+    #[test]
+    fn strip_noise_drops_prefix_lines_first() {
+        let content = "banner\nWARNING: x\nUsage: tool";
+        assert_eq!(
+            strip_noise(content, &patterns(&["^WARNING:"]), 1),
+            "Usage: tool"
+        );
+    }
+
+    #[test]
+    fn strip_noise_ignores_invalid_patterns() {
+        let content = "Usage: tool\nnoise";
+        assert_eq!(
+            strip_noise(content, &patterns(&["(", "^noise$"]), 0),
+            "Usage: tool"
+        );
+    }
+
+    #[test]
+    fn strip_noise_without_rules_is_unchanged() {
+        assert_eq!(strip_noise("a\n\nb\n", &[], 0), "a\n\nb\n");
+    }
+
+    // ========================================
+    // strip_help_trailers tests
+    // ========================================
+
+    #[test]
+    fn strips_clap_trailer() {
+        let help = "Usage: tool [OPTIONS]\n\nOptions:\n  -h, --help  Print help\n\nFor more information, try '--help'.\n";
+        assert_eq!(
+            strip_help_trailers(help),
+            "Usage: tool [OPTIONS]\n\nOptions:\n  -h, --help  Print help"
+        );
+    }
+
+    #[test]
+    fn strips_docker_run_trailer() {
+        let help = "Commands:\n  ps    List containers\n\nRun 'docker COMMAND --help' for more information on a command.\n";
+        assert_eq!(
+            strip_help_trailers(help),
+            "Commands:\n  ps    List containers"
+        );
+    }
+
+    #[test]
+    fn strips_git_see_also_trailers() {
+        let help = "   clone     Clone a repository\n\n'git help -a' and 'git help -g' list available subcommands.\nSee 'git help <command>' or 'git help <concept>'\nto read about a specific subcommand or concept.\nSee 'git help git' for an overview of the system.\n";
+        // Only the matching last line goes; the lines above it aren't trailers
+        assert_eq!(
+            strip_help_trailers(help),
+            "   clone     Clone a repository\n\n'git help -a' and 'git help -g' list available subcommands.\nSee 'git help <command>' or 'git help <concept>'\nto read about a specific subcommand or concept."
+        );
+    }
+
+    #[test]
+    fn strips_go_and_kubectl_use_trailers() {
+        let help = "The commands are:\n\n\tbuild       compile packages\n\nUse \"go help <command>\" for more information about a command.\n";
+        assert_eq!(
+            strip_help_trailers(help),
+            "The commands are:\n\n\tbuild       compile packages"
+        );
+
+        let help = "Basic Commands:\n  get    Display resources\n\nUse \"kubectl <command> --help\" for more information about a given command.\nUse \"kubectl options\" for a list of global command-line options.\n";
+        assert_eq!(
+            strip_help_trailers(help),
+            "Basic Commands:\n  get    Display resources"
+        );
+    }
+
+    #[test]
+    fn strips_at_most_three_trailer_lines() {
+        let help =
+            "Usage: tool\nFor more info a\nFor more info b\nFor more info c\nFor more info d\n";
+        assert_eq!(strip_help_trailers(help), "Usage: tool\nFor more info a");
+    }
+
+    #[test]
+    fn keeps_content_without_trailers() {
+        let help = "Usage: tool\n  -v  verbose\n\n";
+        assert_eq!(strip_help_trailers(help), help);
+    }
+
+    #[test]
+    fn keeps_content_that_is_only_a_trailer() {
+        let help = "Run 'tool --help' for more info.\n";
+        assert_eq!(strip_help_trailers(help), help);
+    }
+
+    #[test]
+    fn ignores_trailer_words_mid_document() {
+        let help = "For more information, see below.\nUsage: tool\n  -v  verbose";
+        assert_eq!(strip_help_trailers(help), help);
+    }
+
     // ========================================
     // is_thin tests
     // ========================================
@@ -382,4 +1607,29 @@ mod tests {
             .join("\n");
         assert!(!is_thin(&content));
     }
+
+    // ========================================
+    // child_command tests
+    // ========================================
+
+    #[test]
+    fn child_command_guards_against_recursion() {
+        let command = child_command("git");
+        let envs: HashMap<_, _> = command
+            .get_envs()
+            .map(|(k, v)| (k.to_owned(), v.map(|v| v.to_owned())))
+            .collect();
+        assert_eq!(
+            envs[OsStr::new(RUNNING_ENV)].as_deref(),
+            Some(OsStr::new("1"))
+        );
+        assert_eq!(
+            envs[OsStr::new("PAGER")].as_deref(),
+            Some(OsStr::new("less"))
+        );
+        assert_eq!(
+            envs[OsStr::new("MANPAGER")].as_deref(),
+            Some(OsStr::new("less"))
+        );
+    }
 }
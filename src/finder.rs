@@ -7,17 +7,104 @@ use ratatui::{
     text::Span,
     widgets::{Block, Borders, Clear, Widget},
 };
+use std::collections::HashSet;
+use std::time::{Duration, Instant};
 
 use crate::parser::Subcommand;
 
+/// How long to wait after the last keypress before re-filtering
+const DEBOUNCE_DELAY: Duration = Duration::from_millis(50);
+
+/// Braille spinner shown while background discovery runs
+const SPINNER_FRAMES: [&str; 10] = ["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
+const SPINNER_FRAME_TIME: Duration = Duration::from_millis(100);
+
+/// Widest the name column gets before names are truncated
+const MAX_NAME_COL_WIDTH: usize = 30;
+
+/// Progress of background subcommand discovery, shown below the list
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DiscoveryStatus {
+    /// Nothing running and nothing to report
+    #[default]
+    Idle,
+    Running {
+        started: Instant,
+    },
+    /// Discovery finished while the finder was open, adding this many items
+    Done {
+        added: usize,
+    },
+}
+
+/// Ordering of the finder's filtered results
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SortOrder {
+    /// Best fuzzy match first
+    #[default]
+    Score,
+    /// By name
+    Alpha,
+    /// Grouped by label, then by name
+    Category,
+}
+
+impl SortOrder {
+    pub fn next(self) -> Self {
+        match self {
+            Self::Score => Self::Alpha,
+            Self::Alpha => Self::Category,
+            Self::Category => Self::Score,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Score => "score",
+            Self::Alpha => "alpha",
+            Self::Category => "category",
+        }
+    }
+}
+
 pub struct Finder {
     items: Vec<Subcommand>,
     pub query: String,
     filtered: Vec<(u16, usize)>, // (score, index)
+    pub sort_order: SortOrder,
     pub selected: usize,
     pub scroll_offset: usize,
     visible_height: usize,
     matcher: Matcher,
+    debounce_pending: bool,
+    last_keypress: Instant,
+    pub discovery: DiscoveryStatus,
+    /// Width of the name column: the longest filtered title plus a gap
+    name_col_width: usize,
+    /// Labels whose groups show only their header (toggled with Ctrl-G)
+    pub collapsed_groups: HashSet<String>,
+    /// For each position in `filtered`, the bounds of its run of same-labeled
+    /// items, rebuilt whenever `filtered` changes
+    group_spans: Vec<(usize, usize)>,
+}
+
+/// A line of the finder list: a label group header, or the item at a
+/// position in `filtered`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Row {
+    Header { start: usize, len: usize },
+    Item(usize),
+}
+
+impl Row {
+    /// Position in `filtered` the row belongs to; a header belongs to its
+    /// group's first item
+    fn pos(self) -> usize {
+        match self {
+            Row::Header { start, .. } => start,
+            Row::Item(pos) => pos,
+        }
+    }
 }
 
 impl Finder {
@@ -26,10 +113,17 @@ impl Finder {
             items,
             query: String::new(),
             filtered: Vec::new(),
+            sort_order: SortOrder::default(),
             selected: 0,
             scroll_offset: 0,
             visible_height: 10, // Default, updated during render
             matcher: Matcher::new(NucleoConfig::DEFAULT),
+            debounce_pending: false,
+            last_keypress: Instant::now(),
+            discovery: DiscoveryStatus::Idle,
+            name_col_width: 0,
+            collapsed_groups: HashSet::new(),
+            group_spans: Vec::new(),
         };
         finder.update_filtered();
         finder
@@ -45,14 +139,83 @@ impl Finder {
 
     pub fn push_char(&mut self, c: char) {
         self.query.push(c);
-        self.update_filtered();
-        self.selected = 0;
-        self.scroll_offset = 0;
+        self.schedule_update();
     }
 
     pub fn pop_char(&mut self) {
         self.query.pop();
+        self.schedule_update();
+    }
+
+    /// Defer filtering until typing pauses; see `tick`
+    fn schedule_update(&mut self) {
+        self.debounce_pending = true;
+        self.last_keypress = Instant::now();
+        self.selected = 0;
+        self.scroll_offset = 0;
+    }
+
+    /// Apply a pending query update once the debounce delay has elapsed.
+    /// Called from the event loop before each draw.
+    pub fn tick(&mut self) {
+        if self.debounce_pending && self.last_keypress.elapsed() > DEBOUNCE_DELAY {
+            self.flush();
+        }
+    }
+
+    /// Apply a pending query update immediately
+    pub fn flush(&mut self) {
+        if self.debounce_pending {
+            self.debounce_pending = false;
+            self.update_filtered();
+        }
+    }
+
+    /// Show the discovery spinner until `set_items` delivers the results
+    pub fn set_discovering(&mut self) {
+        self.discovery = DiscoveryStatus::Running {
+            started: Instant::now(),
+        };
+    }
+
+    /// Replace the items (e.g. once discovery finishes), keeping the query
+    /// and the selection where possible
+    pub fn set_items(&mut self, items: Vec<Subcommand>) {
+        if matches!(self.discovery, DiscoveryStatus::Running { .. }) {
+            self.discovery = DiscoveryStatus::Done {
+                added: items.len().saturating_sub(self.items.len()),
+            };
+        }
+        self.items = items;
         self.update_filtered();
+        self.selected = self.selected.min(self.filtered.len().saturating_sub(1));
+        if self.is_collapsed(self.selected) {
+            self.selected = self.group_start(self.selected);
+        }
+    }
+
+    /// Text for the line below the list, if discovery has anything to say
+    fn discovery_line(&self) -> Option<String> {
+        match self.discovery {
+            DiscoveryStatus::Idle => None,
+            DiscoveryStatus::Running { started } => Some(format!(
+                "{} Discovering more subcommands...",
+                spinner_frame(started.elapsed())
+            )),
+            DiscoveryStatus::Done { added: 0 } => {
+                Some("No more subcommands discovered".to_string())
+            }
+            DiscoveryStatus::Done { added: 1 } => Some("1 more subcommand discovered".to_string()),
+            DiscoveryStatus::Done { added } => {
+                Some(format!("{} more subcommands discovered", added))
+            }
+        }
+    }
+
+    /// Cycle to the next sort order and re-sort the current results
+    pub fn cycle_sort(&mut self) {
+        self.sort_order = self.sort_order.next();
+        self.sort_filtered();
         self.selected = 0;
         self.scroll_offset = 0;
     }
@@ -60,18 +223,14 @@ impl Finder {
     fn update_filtered(&mut self) {
         self.filtered.clear();
 
-        if self.query.is_empty() {
-            // Show all items when query is empty
-            self.filtered = self.items.iter().enumerate().map(|(i, _)| (0, i)).collect();
-            return;
-        }
-
         // Split query into space-separated terms (fzf style)
         let terms: Vec<&str> = self.query.split_whitespace().collect();
 
         if terms.is_empty() {
-            // Query is all whitespace - show all
+            // Empty or all-whitespace query - show all
             self.filtered = self.items.iter().enumerate().map(|(i, _)| (0, i)).collect();
+            self.sort_filtered();
+            self.update_name_col_width();
             return;
         }
 
@@ -83,6 +242,10 @@ impl Finder {
                     s.push(' ');
                 }
                 s.push_str(&item.name);
+                for alias in &item.aliases {
+                    s.push(' ');
+                    s.push_str(alias);
+                }
                 if let Some(desc) = &item.description {
                     s.push(' ');
                     s.push_str(desc);
@@ -116,41 +279,98 @@ impl Finder {
             }
         }
 
-        // Sort by score (highest first)
-        self.filtered.sort_by(|a, b| b.0.cmp(&a.0));
+        self.sort_filtered();
+        self.update_name_col_width();
+    }
+
+    fn update_name_col_width(&mut self) {
+        let longest = self
+            .filtered
+            .iter()
+            .map(|(_, idx)| item_title(&self.items[*idx]).chars().count())
+            .max()
+            .unwrap_or(0);
+        self.name_col_width = (longest + 2).min(MAX_NAME_COL_WIDTH);
+    }
+
+    fn sort_filtered(&mut self) {
+        let items = &self.items;
+        match self.sort_order {
+            // Sort by score (highest first); stable, so ties keep help-text order
+            SortOrder::Score => self
+                .filtered
+                .sort_by_key(|&(score, _)| std::cmp::Reverse(score)),
+            SortOrder::Alpha => self
+                .filtered
+                .sort_by(|&(_, a), &(_, b)| items[a].name.cmp(&items[b].name)),
+            SortOrder::Category => self.filtered.sort_by(|&(_, a), &(_, b)| {
+                (&items[a].label, &items[a].name).cmp(&(&items[b].label, &items[b].name))
+            }),
+        }
+        self.update_group_spans();
+    }
+
+    fn update_group_spans(&mut self) {
+        self.group_spans.clear();
+        let mut start = 0;
+        for pos in 1..=self.filtered.len() {
+            if pos == self.filtered.len() || self.label_at(pos) != self.label_at(start) {
+                self.group_spans.extend((start..pos).map(|_| (start, pos)));
+                start = pos;
+            }
+        }
     }
 
+    /// Move to the previous visible row; a collapsed group counts as one
     pub fn move_up(&mut self) {
-        if self.selected > 0 {
-            self.selected -= 1;
+        if let Some(pos) = (0..self.selected).rev().find(|&i| self.is_reachable(i)) {
+            self.selected = pos;
         }
     }
 
+    /// Move to the next visible row; a collapsed group counts as one
     pub fn move_down(&mut self) {
-        if self.selected + 1 < self.filtered.len() {
-            self.selected += 1;
+        if let Some(pos) = (self.selected + 1..self.filtered.len()).find(|&i| self.is_reachable(i))
+        {
+            self.selected = pos;
         }
     }
 
     pub fn move_up_by(&mut self, n: usize) {
-        self.selected = self.selected.saturating_sub(n);
+        for _ in 0..n.min(self.selected) {
+            self.move_up();
+        }
     }
 
     pub fn move_down_by(&mut self, n: usize) {
-        let max_idx = self.filtered.len().saturating_sub(1);
-        self.selected = (self.selected + n).min(max_idx);
+        let remaining = self.filtered.len().saturating_sub(self.selected + 1);
+        for _ in 0..n.min(remaining) {
+            self.move_down();
+        }
     }
 
     pub fn set_visible_height(&mut self, h: usize) {
         self.visible_height = h;
     }
 
+    /// The selected item; `None` when the selection is a collapsed group's header
     pub fn selected_item(&self) -> Option<&Subcommand> {
+        if self.is_collapsed(self.selected) {
+            return None;
+        }
         self.filtered
             .get(self.selected)
             .map(|(_, idx)| &self.items[*idx])
     }
 
+    /// Items matching the query, in display order
+    pub fn filtered_items(&self) -> Vec<Subcommand> {
+        self.filtered
+            .iter()
+            .map(|(_, idx)| self.items[*idx].clone())
+            .collect()
+    }
+
     #[allow(dead_code)]
     pub fn is_empty(&self) -> bool {
         self.items.is_empty()
@@ -160,10 +380,161 @@ impl Finder {
         self.filtered.len()
     }
 
+    /// Label of the topmost visible item; `None` for parsed subcommands
+    pub fn current_category(&self) -> Option<&str> {
+        self.filtered
+            .get(self.scroll_offset)
+            .and_then(|&(_, idx)| self.items[idx].label.as_deref())
+    }
+
+    /// Label of the item at `filtered[pos]`
+    fn label_at(&self, pos: usize) -> Option<&str> {
+        self.items[self.filtered[pos].1].label.as_deref()
+    }
+
+    /// Index into `filtered` where the run of same-labeled items containing
+    /// `pos` begins
+    fn group_start(&self, pos: usize) -> usize {
+        self.group_spans[pos].0
+    }
+
+    /// Index into `filtered` just past the run of same-labeled items
+    /// containing `pos`
+    fn group_end(&self, pos: usize) -> usize {
+        self.group_spans[pos].1
+    }
+
+    /// Whether `filtered[pos]` belongs to a collapsed label group
+    fn is_collapsed(&self, pos: usize) -> bool {
+        pos < self.filtered.len()
+            && self
+                .label_at(pos)
+                .is_some_and(|label| self.collapsed_groups.contains(label))
+    }
+
+    /// Whether the selection can rest on `filtered[pos]`. Only the first
+    /// position of a collapsed group can, standing for its header.
+    fn is_reachable(&self, pos: usize) -> bool {
+        !self.is_collapsed(pos) || self.group_start(pos) == pos
+    }
+
+    /// Collapse the selected item's label group, or expand it if collapsed.
+    /// Unlabeled items have no group to collapse.
+    pub fn toggle_group(&mut self) {
+        let Some(label) = self
+            .filtered
+            .get(self.selected)
+            .and_then(|&(_, idx)| self.items[idx].label.clone())
+        else {
+            return;
+        };
+        if !self.collapsed_groups.remove(&label) {
+            self.collapsed_groups.insert(label);
+            self.selected = self.group_start(self.selected);
+        }
+    }
+
+    /// List rows starting at `filtered[start]`. Each labeled group begins
+    /// with a header; a collapsed group is only its header.
+    fn rows_from(&self, start: usize) -> Vec<Row> {
+        let mut rows = Vec::new();
+        let mut pos = start;
+        while pos < self.filtered.len() {
+            if self.label_at(pos).is_some() && self.group_start(pos) == pos {
+                let end = self.group_end(pos);
+                rows.push(Row::Header {
+                    start: pos,
+                    len: end - pos,
+                });
+                if self.is_collapsed(pos) {
+                    pos = end;
+                    continue;
+                }
+            }
+            rows.push(Row::Item(pos));
+            pos += 1;
+        }
+        rows
+    }
+
+    /// Whether `row` shows the selection
+    fn row_is_selected(&self, row: Row) -> bool {
+        match row {
+            Row::Item(pos) => pos == self.selected,
+            Row::Header { start, .. } => start == self.selected && self.is_collapsed(start),
+        }
+    }
+
+    /// Move `scroll_offset` so the selected row is among the first `height` rows
+    fn scroll_to_selection(&mut self, height: usize) {
+        if !self.is_reachable(self.scroll_offset) {
+            self.scroll_offset = self.group_start(self.scroll_offset);
+        }
+        if self.selected < self.scroll_offset {
+            self.scroll_offset = self.selected;
+        }
+        if self.scroll_offset == self.selected {
+            return;
+        }
+        let rows = self.rows_from(self.scroll_offset);
+        let Some(selected_row) = rows.iter().position(|&row| self.row_is_selected(row)) else {
+            return;
+        };
+        if selected_row < height {
+            return;
+        }
+        // Starting at a later position drops the rows before its first row, so
+        // take the earliest reachable position whose rows leave the selection
+        // within `height`
+        let first_kept = selected_row + 1 - height;
+        self.scroll_offset = (first_kept..=selected_row)
+            .find(|&i| {
+                let pos = rows[i].pos();
+                (i == 0 || rows[i - 1].pos() != pos) && self.is_reachable(pos)
+            })
+            .map_or(self.selected, |i| rows[i].pos());
+    }
+
+    /// First item of the label group after the selected one, wrapping to the
+    /// top. `None` when all results share one label.
+    pub fn next_group_start(&self) -> Option<usize> {
+        if self.filtered.is_empty() {
+            return None;
+        }
+        let label = self.label_at(self.selected);
+        let next = (self.selected + 1..self.filtered.len()).find(|&i| self.label_at(i) != label);
+        match next {
+            Some(i) => Some(i),
+            None if self.group_start(self.selected) > 0 => Some(0),
+            None => None,
+        }
+    }
+
+    /// First item of the label group before the selected one, wrapping to the
+    /// last group. `None` when all results share one label.
+    pub fn prev_group_start(&self) -> Option<usize> {
+        if self.filtered.is_empty() {
+            return None;
+        }
+        let start = self.group_start(self.selected);
+        if start > 0 {
+            return Some(self.group_start(start - 1));
+        }
+        let last_start = self.group_start(self.filtered.len() - 1);
+        (last_start > 0).then_some(last_start)
+    }
+
+    /// Whether any item carries a category label
+    fn has_categories(&self) -> bool {
+        self.items.iter().any(|item| item.label.is_some())
+    }
+
     pub fn handle_key(&mut self, key: KeyEvent) -> FinderAction {
         match key.code {
             KeyCode::Esc => FinderAction::Close,
             KeyCode::Enter => {
+                // Don't select from stale results if typing hasn't settled yet
+                self.flush();
                 if self.selected_item().is_some() {
                     FinderAction::Select
                 } else {
@@ -202,6 +573,33 @@ impl Finder {
                 self.move_down_by(self.visible_height);
                 FinderAction::None
             }
+            KeyCode::Char('s') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.cycle_sort();
+                FinderAction::None
+            }
+            KeyCode::Char('g') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.flush();
+                self.toggle_group();
+                FinderAction::None
+            }
+            KeyCode::Char('x') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.flush();
+                FinderAction::Export
+            }
+            KeyCode::Tab => {
+                self.flush();
+                if let Some(i) = self.next_group_start() {
+                    self.selected = i;
+                }
+                FinderAction::None
+            }
+            KeyCode::BackTab => {
+                self.flush();
+                if let Some(i) = self.prev_group_start() {
+                    self.selected = i;
+                }
+                FinderAction::None
+            }
             KeyCode::Backspace => {
                 self.pop_char();
                 FinderAction::None
@@ -220,11 +618,240 @@ pub enum FinderAction {
     None,
     Close,
     Select,
+    /// Write out the filtered items (Ctrl-X)
+    Export,
+}
+
+pub struct FinderWidget<'a> {
+    finder: &'a mut Finder,
+}
+
+impl<'a> FinderWidget<'a> {
+    pub fn new(finder: &'a mut Finder) -> Self {
+        Self { finder }
+    }
+}
+
+impl Widget for FinderWidget<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        // Use most of the screen - 90% width and height with reasonable minimums
+        let width = (area.width * 9 / 10).max(40);
+        let height = (area.height * 9 / 10).max(10);
+
+        let x = area.x + (area.width - width) / 2;
+        let y = area.y + (area.height - height) / 2;
+
+        let overlay_area = Rect::new(x, y, width, height);
+
+        // Clear the area
+        Clear.render(overlay_area, buf);
+
+        // Draw border
+        let title = format!(
+            " Subcommands ({}/{}) [{}] ",
+            self.finder.filtered_count(),
+            self.finder.items.len(),
+            self.finder.sort_order.label()
+        );
+        let block = Block::default()
+            .title(title)
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Cyan))
+            .style(Style::default().bg(Color::Black));
+
+        let inner = block.inner(overlay_area);
+        block.render(overlay_area, buf);
+
+        // Draw search input
+        let input_line = format!("> {}", self.finder.query);
+        let input_span = Span::styled(&input_line, Style::default().fg(Color::Yellow));
+        buf.set_span(inner.x, inner.y, &input_span, inner.width);
+
+        // Discovery progress gets the bottom row of the overlay
+        let discovery_line = self.finder.discovery_line();
+        if let Some(ref text) = discovery_line {
+            let style = match self.finder.discovery {
+                DiscoveryStatus::Running { .. } => Style::default().fg(Color::Yellow),
+                _ => Style::default().fg(Color::DarkGray),
+            };
+            let y = inner.y + inner.height.saturating_sub(1);
+            buf.set_span(inner.x + 2, y, &Span::styled(text, style), inner.width);
+        }
+        let footer_height = u16::from(discovery_line.is_some());
+
+        // Draw items with scrolling. When the list overflows, reserve a row above
+        // and below it for "N more" indicators so the layout doesn't jump.
+        let list_height = inner.height.saturating_sub(2 + footer_height) as usize;
+        let overflows = self.finder.rows_from(0).len() > list_height && list_height > 2;
+        let (items_start_y, items_height) = if overflows {
+            (inner.y + 3, list_height - 2)
+        } else {
+            (inner.y + 2, list_height)
+        };
+
+        // Update visible height for page navigation
+        self.finder.set_visible_height(items_height);
+
+        // Adjust scroll offset to keep selection visible
+        self.finder.scroll_to_selection(items_height);
+
+        let scroll_offset = self.finder.scroll_offset;
+        let rows: Vec<Row> = self
+            .finder
+            .rows_from(scroll_offset)
+            .into_iter()
+            .take(items_height)
+            .collect();
+
+        // Draw separator, doubling as a sticky header for the top item's category
+        let separator = if self.finder.has_categories() && !self.finder.filtered.is_empty() {
+            let category = self.finder.current_category().unwrap_or("Subcommands");
+            let header = format!("── {} ", category);
+            let fill = (inner.width as usize).saturating_sub(header.chars().count());
+            header + &"─".repeat(fill)
+        } else {
+            "─".repeat(inner.width as usize)
+        };
+        let sep_span = Span::styled(separator, Style::default().fg(Color::DarkGray));
+        buf.set_span(inner.x, inner.y + 1, &sep_span, inner.width);
+
+        // Render visible rows
+        let selected_style = Style::default()
+            .fg(Color::Black)
+            .bg(Color::Cyan)
+            .add_modifier(Modifier::BOLD);
+        for (render_idx, &row) in rows.iter().enumerate() {
+            let y = items_start_y + render_idx as u16;
+            let is_selected = self.finder.row_is_selected(row);
+
+            let pos = match row {
+                Row::Item(pos) => pos,
+                Row::Header { start, len } => {
+                    let collapsed = self.finder.is_collapsed(start);
+                    let label = self.finder.label_at(start).unwrap_or_default();
+                    let mut line = format!("{} {}", if collapsed { "▶" } else { "▼" }, label);
+                    if collapsed {
+                        line.push_str(&match len {
+                            1 => " (1 item)".to_string(),
+                            n => format!(" ({} items)", n),
+                        });
+                    }
+                    let width = line.chars().count();
+                    line.push_str(&" ".repeat((inner.width as usize).saturating_sub(width)));
+                    let style = if is_selected {
+                        selected_style
+                    } else {
+                        Style::default()
+                            .fg(Color::White)
+                            .add_modifier(Modifier::BOLD)
+                    };
+                    buf.set_span(inner.x, y, &Span::styled(line, style), inner.width);
+                    continue;
+                }
+            };
+            let item = &self.finder.items[self.finder.filtered[pos].1];
+
+            let style = if is_selected {
+                selected_style
+            } else {
+                Style::default().fg(Color::White)
+            };
+
+            // Format: [label] name, padded to the name column, then description
+            let name_width = self.finder.name_col_width;
+            let mut line = if is_selected { "▶ " } else { "  " }.to_string();
+            let title = truncate_to_width(&item_title(item), name_width.saturating_sub(2));
+            line.push_str(&format!("{:<width$}", title, width = name_width));
+
+            if let Some(ref desc) = item.description {
+                let remaining = (inner.width as usize).saturating_sub(name_width + 2);
+                line.push_str(&truncate_to_width(desc, remaining));
+            }
+
+            // Pad to full width for selection highlight
+            let width = line.chars().count();
+            line.push_str(&" ".repeat((inner.width as usize).saturating_sub(width)));
+
+            let span = Span::styled(line, style);
+            buf.set_span(inner.x, y, &span, inner.width);
+        }
+
+        if overflows {
+            // Filtered items the drawn rows account for; a collapsed header covers its group
+            let shown_end = match rows.last() {
+                Some(Row::Item(pos)) => pos + 1,
+                Some(&Row::Header { start, len }) if self.finder.is_collapsed(start) => start + len,
+                Some(&Row::Header { start, .. }) => start,
+                None => scroll_offset,
+            };
+            let (above, below) = hidden_counts(
+                self.finder.filtered.len(),
+                scroll_offset,
+                shown_end - scroll_offset,
+            );
+            let dim = Style::default().fg(Color::DarkGray);
+            if above > 0 {
+                let text = format!("  ─── {} more above ───", above);
+                buf.set_span(inner.x, inner.y + 2, &Span::styled(text, dim), inner.width);
+            }
+            if below > 0 {
+                let text = format!("  ─── {} more below ───", below);
+                let y = items_start_y + items_height as u16;
+                buf.set_span(inner.x, y, &Span::styled(text, dim), inner.width);
+            }
+        }
+
+        // Show "no matches" if empty
+        if self.finder.filtered.is_empty() && !self.finder.query.is_empty() {
+            let msg = "No matching subcommands";
+            let msg_span = Span::styled(msg, Style::default().fg(Color::DarkGray));
+            buf.set_span(inner.x + 2, items_start_y, &msg_span, inner.width);
+        }
+    }
+}
+
+/// An item's name column text: its abbreviated category label, if any, then
+/// its name with aliases
+fn item_title(item: &Subcommand) -> String {
+    match item.label {
+        Some(ref label) => {
+            let short_label: String = label.chars().take(8).collect();
+            format!("[{}] {}", short_label, item.display_name())
+        }
+        None => item.display_name(),
+    }
+}
+
+/// `text` cut to `width` characters, ending in `…` when anything was cut
+fn truncate_to_width(text: &str, width: usize) -> String {
+    if text.chars().count() <= width {
+        return text.to_string();
+    }
+    if width == 0 {
+        return String::new();
+    }
+    let mut truncated: String = text.chars().take(width - 1).collect();
+    truncated.push('…');
+    truncated
+}
+
+/// Spinner frame for an animation that has been running for `elapsed`
+fn spinner_frame(elapsed: Duration) -> &'static str {
+    let frame = elapsed.as_millis() / SPINNER_FRAME_TIME.as_millis();
+    SPINNER_FRAMES[frame as usize % SPINNER_FRAMES.len()]
+}
+
+/// Items scrolled out of view as `(above, below)`
+fn hidden_counts(total: usize, scroll_offset: usize, visible: usize) -> (usize, usize) {
+    let above = scroll_offset.min(total);
+    let below = total.saturating_sub(scroll_offset + visible);
+    (above, below)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::toolpacks::DedupeStrategy;
 
     fn make_item(name: &str, description: Option<&str>) -> Subcommand {
         Subcommand {
@@ -232,6 +859,10 @@ mod tests {
             description: description.map(|s| s.to_string()),
             label: None,
             invoke_command: None,
+            url: None,
+            aliases: Vec::new(),
+            dedupe_strategy: DedupeStrategy::Name,
+            confidence: 1.0,
         }
     }
 
@@ -417,6 +1048,38 @@ mod tests {
         assert_eq!(finder.query, "ab");
     }
 
+    #[test]
+    fn push_char_defers_filtering() {
+        let mut finder = Finder::new(make_items());
+        finder.push_char('z');
+        finder.push_char('z');
+        assert_eq!(finder.filtered_count(), 5);
+        finder.tick();
+        // Debounce delay has not elapsed yet
+        assert_eq!(finder.filtered_count(), 5);
+    }
+
+    #[test]
+    fn tick_applies_update_after_delay() {
+        let mut finder = Finder::new(make_items());
+        finder.push_char('z');
+        finder.push_char('z');
+        std::thread::sleep(DEBOUNCE_DELAY + Duration::from_millis(10));
+        finder.tick();
+        assert_eq!(finder.filtered_count(), 0);
+    }
+
+    #[test]
+    fn enter_flushes_pending_update() {
+        let mut finder = Finder::new(make_items());
+        for c in "binary".chars() {
+            finder.push_char(c);
+        }
+        let action = finder.handle_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+        assert_eq!(action, FinderAction::Select);
+        assert_eq!(finder.selected_item().map(|s| s.name.as_str()), Some("run"));
+    }
+
     #[test]
     fn push_char_resets_selection() {
         let mut finder = Finder::new(make_items());
@@ -437,12 +1100,20 @@ mod tests {
                 description: Some("Clone a repo".to_string()),
                 label: Some("Git Commands".to_string()),
                 invoke_command: None,
+                url: None,
+                aliases: Vec::new(),
+                dedupe_strategy: DedupeStrategy::Name,
+                confidence: 1.0,
             },
             Subcommand {
                 name: "init".to_string(),
                 description: Some("Initialize".to_string()),
                 label: Some("Setup".to_string()),
                 invoke_command: None,
+                url: None,
+                aliases: Vec::new(),
+                dedupe_strategy: DedupeStrategy::Name,
+                confidence: 1.0,
             },
         ];
         let mut finder = Finder::new(items);
@@ -450,137 +1121,459 @@ mod tests {
         // Should match clone (has "Git Commands" label)
         assert!(finder.filtered_count() >= 1);
     }
-}
 
-pub struct FinderWidget<'a> {
-    finder: &'a mut Finder,
-}
+    // ========================================
+    // Sort order tests
+    // ========================================
 
-impl<'a> FinderWidget<'a> {
-    pub fn new(finder: &'a mut Finder) -> Self {
-        Self { finder }
+    fn labeled_item(name: &str, label: &str) -> Subcommand {
+        Subcommand {
+            label: Some(label.to_string()),
+            ..make_item(name, None)
+        }
     }
-}
 
-impl Widget for FinderWidget<'_> {
-    fn render(self, area: Rect, buf: &mut Buffer) {
-        // Use most of the screen - 90% width and height with reasonable minimums
-        let width = (area.width * 9 / 10).max(40);
-        let height = (area.height * 9 / 10).max(10);
+    fn filtered_names(finder: &Finder) -> Vec<&str> {
+        finder
+            .filtered
+            .iter()
+            .map(|&(_, idx)| finder.items[idx].name.as_str())
+            .collect()
+    }
 
-        let x = area.x + (area.width - width) / 2;
-        let y = area.y + (area.height - height) / 2;
+    #[test]
+    fn sort_defaults_to_score() {
+        let finder = Finder::new(make_items());
+        assert_eq!(finder.sort_order, SortOrder::Score);
+        // Empty query keeps help-text order
+        assert_eq!(
+            filtered_names(&finder),
+            vec!["build", "test", "run", "clean", "doc"]
+        );
+    }
 
-        let overlay_area = Rect::new(x, y, width, height);
+    #[test]
+    fn sort_alpha_orders_by_name() {
+        let mut finder = Finder::new(make_items());
+        finder.cycle_sort();
+        assert_eq!(finder.sort_order, SortOrder::Alpha);
+        assert_eq!(
+            filtered_names(&finder),
+            vec!["build", "clean", "doc", "run", "test"]
+        );
+    }
 
-        // Clear the area
-        Clear.render(overlay_area, buf);
+    #[test]
+    fn sort_category_groups_by_label_then_name() {
+        let items = vec![
+            labeled_item("zeta", "Guides"),
+            labeled_item("push", "Commands"),
+            labeled_item("alpha", "Guides"),
+            labeled_item("add", "Commands"),
+        ];
+        let mut finder = Finder::new(items);
+        finder.cycle_sort();
+        finder.cycle_sort();
+        assert_eq!(finder.sort_order, SortOrder::Category);
+        assert_eq!(
+            filtered_names(&finder),
+            vec!["add", "push", "alpha", "zeta"]
+        );
+    }
 
-        // Draw border
-        let title = format!(
-            " Subcommands ({}/{}) ",
-            self.finder.filtered_count(),
-            self.finder.items.len()
+    #[test]
+    fn sort_cycles_back_to_score() {
+        let mut finder = Finder::new(make_items());
+        for _ in 0..3 {
+            finder.cycle_sort();
+        }
+        assert_eq!(finder.sort_order, SortOrder::Score);
+    }
+
+    #[test]
+    fn sort_order_survives_query_changes() {
+        let mut finder = Finder::new(make_items());
+        finder.cycle_sort();
+        finder.set_query("b".to_string());
+        let names = filtered_names(&finder);
+        let mut sorted = names.clone();
+        sorted.sort();
+        assert_eq!(names, sorted);
+    }
+
+    #[test]
+    fn ctrl_s_cycles_sort() {
+        let mut finder = Finder::new(make_items());
+        let action = finder.handle_key(KeyEvent::new(KeyCode::Char('s'), KeyModifiers::CONTROL));
+        assert_eq!(action, FinderAction::None);
+        assert_eq!(finder.sort_order, SortOrder::Alpha);
+        assert!(finder.query.is_empty());
+    }
+
+    #[test]
+    fn ctrl_x_exports_pending_filter() {
+        let mut finder = Finder::new(make_items());
+        for c in "remove".chars() {
+            finder.push_char(c);
+        }
+        let action = finder.handle_key(KeyEvent::new(KeyCode::Char('x'), KeyModifiers::CONTROL));
+        assert_eq!(action, FinderAction::Export);
+        let names: Vec<String> = finder
+            .filtered_items()
+            .into_iter()
+            .map(|i| i.name)
+            .collect();
+        assert_eq!(names, vec!["clean"]);
+    }
+
+    // ========================================
+    // Scroll indicator tests
+    // ========================================
+
+    #[test]
+    fn hidden_counts_when_everything_fits() {
+        assert_eq!(hidden_counts(5, 0, 10), (0, 0));
+    }
+
+    #[test]
+    fn hidden_counts_at_top() {
+        assert_eq!(hidden_counts(30, 0, 10), (0, 20));
+    }
+
+    #[test]
+    fn hidden_counts_in_middle() {
+        assert_eq!(hidden_counts(30, 5, 10), (5, 15));
+    }
+
+    #[test]
+    fn hidden_counts_at_bottom() {
+        assert_eq!(hidden_counts(30, 20, 10), (20, 0));
+    }
+
+    // ========================================
+    // Name column tests
+    // ========================================
+
+    #[test]
+    fn name_col_width_fits_longest_filtered_name() {
+        let mut finder = Finder::new(make_items());
+        assert_eq!(finder.name_col_width, "build".len() + 2);
+
+        finder.set_query("docu".to_string());
+        assert_eq!(filtered_names(&finder), vec!["doc"]);
+        assert_eq!(finder.name_col_width, "doc".len() + 2);
+    }
+
+    #[test]
+    fn name_col_width_counts_label_and_is_clamped() {
+        let finder = Finder::new(vec![labeled_item("git-log", "Man Pages")]);
+        assert_eq!(finder.name_col_width, "[Man Page] git-log".len() + 2);
+
+        let long = "x".repeat(60);
+        let finder = Finder::new(vec![make_item(&long, None)]);
+        assert_eq!(finder.name_col_width, MAX_NAME_COL_WIDTH);
+    }
+
+    #[test]
+    fn truncate_to_width_adds_ellipsis() {
+        assert_eq!(truncate_to_width("build", 10), "build");
+        assert_eq!(truncate_to_width("build", 5), "build");
+        assert_eq!(truncate_to_width("install", 5), "inst…");
+        assert_eq!(truncate_to_width("─────", 3), "──…");
+        assert_eq!(truncate_to_width("install", 0), "");
+    }
+
+    // ========================================
+    // Sticky category tests
+    // ========================================
+
+    #[test]
+    fn current_category_follows_scroll_offset() {
+        let mut finder = Finder::new(vec![
+            make_item("add", None),
+            labeled_item("git-log", "Man Pages"),
+            labeled_item("tutorial", "Guides"),
+        ]);
+        assert_eq!(finder.current_category(), None);
+
+        finder.scroll_offset = 1;
+        assert_eq!(finder.current_category(), Some("Man Pages"));
+
+        finder.scroll_offset = 2;
+        assert_eq!(finder.current_category(), Some("Guides"));
+    }
+
+    #[test]
+    fn current_category_empty_when_nothing_matches() {
+        let mut finder = Finder::new(vec![labeled_item("git-log", "Man Pages")]);
+        finder.set_query("zzz".to_string());
+        assert_eq!(finder.current_category(), None);
+    }
+
+    // ========================================
+    // Discovery indicator tests
+    // ========================================
+
+    #[test]
+    fn spinner_frame_advances_and_wraps() {
+        assert_eq!(spinner_frame(Duration::ZERO), SPINNER_FRAMES[0]);
+        assert_eq!(spinner_frame(Duration::from_millis(250)), SPINNER_FRAMES[2]);
+        assert_eq!(
+            spinner_frame(Duration::from_millis(1000)),
+            SPINNER_FRAMES[0]
         );
-        let block = Block::default()
-            .title(title)
-            .borders(Borders::ALL)
-            .border_style(Style::default().fg(Color::Cyan))
-            .style(Style::default().bg(Color::Black));
+    }
 
-        let inner = block.inner(overlay_area);
-        block.render(overlay_area, buf);
+    #[test]
+    fn discovery_line_shows_spinner_while_running() {
+        let mut finder = Finder::new(make_items());
+        assert_eq!(finder.discovery_line(), None);
 
-        // Draw search input
-        let input_line = format!("> {}", self.finder.query);
-        let input_span = Span::styled(&input_line, Style::default().fg(Color::Yellow));
-        buf.set_span(inner.x, inner.y, &input_span, inner.width);
+        finder.set_discovering();
+        let line = finder.discovery_line().unwrap();
+        assert!(line.ends_with("Discovering more subcommands..."));
+    }
 
-        // Draw separator
-        let separator = "─".repeat(inner.width as usize);
-        let sep_span = Span::styled(separator, Style::default().fg(Color::DarkGray));
-        buf.set_span(inner.x, inner.y + 1, &sep_span, inner.width);
+    #[test]
+    fn set_items_reports_discovered_count() {
+        let mut finder = Finder::new(make_items());
+        finder.set_discovering();
 
-        // Draw items with scrolling
-        let items_start_y = inner.y + 2;
-        let items_height = inner.height.saturating_sub(2) as usize;
+        let mut items = make_items();
+        items.push(labeled_item("git-log", "Man Pages"));
+        items.push(labeled_item("tutorial", "Guides"));
+        finder.set_items(items);
 
-        // Update visible height for page navigation
-        self.finder.set_visible_height(items_height);
+        assert_eq!(finder.discovery, DiscoveryStatus::Done { added: 2 });
+        assert_eq!(
+            finder.discovery_line().as_deref(),
+            Some("2 more subcommands discovered")
+        );
+        assert_eq!(finder.filtered_count(), 7);
+    }
 
-        // Adjust scroll offset to keep selection visible
-        if self.finder.selected < self.finder.scroll_offset {
-            self.finder.scroll_offset = self.finder.selected;
-        } else if self.finder.selected >= self.finder.scroll_offset + items_height {
-            self.finder.scroll_offset = self.finder.selected.saturating_sub(items_height - 1);
-        }
+    #[test]
+    fn set_items_keeps_query_and_clamps_selection() {
+        let mut finder = Finder::new(make_items());
+        finder.selected = 4;
+        finder.set_items(vec![make_item("build", None), make_item("bench", None)]);
+        assert_eq!(finder.selected, 1);
+        assert_eq!(finder.discovery, DiscoveryStatus::Idle);
 
-        let scroll_offset = self.finder.scroll_offset;
+        finder.set_query("ben".to_string());
+        finder.set_items(vec![make_item("bench", None), make_item("run", None)]);
+        assert_eq!(filtered_names(&finder), vec!["bench"]);
+    }
 
-        // Render visible items
-        for (render_idx, (_, idx)) in self
-            .finder
-            .filtered
-            .iter()
-            .skip(scroll_offset)
-            .take(items_height)
-            .enumerate()
-        {
-            let item = &self.finder.items[*idx];
-            let y = items_start_y + render_idx as u16;
-            let actual_idx = scroll_offset + render_idx;
+    // ========================================
+    // Label group navigation tests
+    // ========================================
 
-            let is_selected = actual_idx == self.finder.selected;
-            let style = if is_selected {
-                Style::default()
-                    .fg(Color::Black)
-                    .bg(Color::Cyan)
-                    .add_modifier(Modifier::BOLD)
-            } else {
-                Style::default().fg(Color::White)
-            };
+    /// Parsed items, then two "Man Pages", then a "Guides" item
+    fn grouped_finder() -> Finder {
+        Finder::new(vec![
+            make_item("add", None),
+            make_item("commit", None),
+            labeled_item("git-log", "Man Pages"),
+            labeled_item("git-show", "Man Pages"),
+            labeled_item("tutorial", "Guides"),
+        ])
+    }
 
-            // Format: [label] name - description (truncated)
-            let mut line = if is_selected { "▶ " } else { "  " }.to_string();
+    #[test]
+    fn next_group_start_finds_label_boundaries() {
+        let mut finder = grouped_finder();
+        assert_eq!(finder.next_group_start(), Some(2));
+        finder.selected = 3;
+        assert_eq!(finder.next_group_start(), Some(4));
+    }
 
-            // Show category label for discovered items
-            if let Some(ref label) = item.label {
-                line.push('[');
-                // Abbreviate long labels
-                let short_label = if label.len() > 8 { &label[..8] } else { label };
-                line.push_str(short_label);
-                line.push_str("] ");
-            }
+    #[test]
+    fn next_group_start_wraps_from_last_group() {
+        let mut finder = grouped_finder();
+        finder.selected = 4;
+        assert_eq!(finder.next_group_start(), Some(0));
+    }
 
-            line.push_str(&item.name);
+    #[test]
+    fn prev_group_start_goes_to_previous_group_and_wraps() {
+        let mut finder = grouped_finder();
+        finder.selected = 3;
+        assert_eq!(finder.prev_group_start(), Some(0));
+        finder.selected = 4;
+        assert_eq!(finder.prev_group_start(), Some(2));
+        finder.selected = 1;
+        assert_eq!(finder.prev_group_start(), Some(4));
+    }
 
-            if let Some(ref desc) = item.description {
-                let remaining = inner.width as usize - line.len() - 3;
-                if remaining > 10 {
-                    line.push_str(" - ");
-                    if desc.len() > remaining {
-                        line.push_str(&desc[..remaining - 3]);
-                        line.push_str("...");
-                    } else {
-                        line.push_str(desc);
-                    }
-                }
-            }
+    #[test]
+    fn group_navigation_needs_more_than_one_label() {
+        let finder = Finder::new(make_items());
+        assert_eq!(finder.next_group_start(), None);
+        assert_eq!(finder.prev_group_start(), None);
 
-            // Pad to full width for selection highlight
-            while line.len() < inner.width as usize {
-                line.push(' ');
-            }
+        let mut finder = grouped_finder();
+        finder.set_query("zzz".to_string());
+        assert_eq!(finder.next_group_start(), None);
+    }
 
-            let span = Span::styled(line, style);
-            buf.set_span(inner.x, y, &span, inner.width);
-        }
+    #[test]
+    fn tab_and_backtab_move_between_groups() {
+        let mut finder = grouped_finder();
+        finder.handle_key(KeyEvent::new(KeyCode::Tab, KeyModifiers::NONE));
+        assert_eq!(finder.selected, 2);
+        finder.handle_key(KeyEvent::new(KeyCode::Tab, KeyModifiers::NONE));
+        assert_eq!(finder.selected, 4);
+        finder.handle_key(KeyEvent::new(KeyCode::BackTab, KeyModifiers::SHIFT));
+        assert_eq!(finder.selected, 2);
+    }
 
-        // Show "no matches" if empty
-        if self.finder.filtered.is_empty() && !self.finder.query.is_empty() {
-            let msg = "No matching subcommands";
-            let msg_span = Span::styled(msg, Style::default().fg(Color::DarkGray));
-            buf.set_span(inner.x + 2, items_start_y, &msg_span, inner.width);
-        }
+    // ========================================
+    // Collapsible group tests
+    // ========================================
+
+    fn ctrl_g(finder: &mut Finder) {
+        finder.handle_key(KeyEvent::new(KeyCode::Char('g'), KeyModifiers::CONTROL));
+    }
+
+    fn render_finder(finder: &mut Finder, width: u16, height: u16) -> Vec<String> {
+        let area = Rect::new(0, 0, width, height);
+        let mut buf = Buffer::empty(area);
+        FinderWidget::new(finder).render(area, &mut buf);
+        (0..height)
+            .map(|y| (0..width).map(|x| buf[(x, y)].symbol()).collect())
+            .collect()
+    }
+
+    #[test]
+    fn rows_put_headers_before_labeled_groups() {
+        let finder = grouped_finder();
+        assert_eq!(
+            finder.rows_from(0),
+            vec![
+                Row::Item(0),
+                Row::Item(1),
+                Row::Header { start: 2, len: 2 },
+                Row::Item(2),
+                Row::Item(3),
+                Row::Header { start: 4, len: 1 },
+                Row::Item(4),
+            ]
+        );
+    }
+
+    #[test]
+    fn ctrl_g_collapses_selected_group() {
+        let mut finder = grouped_finder();
+        finder.selected = 3;
+        ctrl_g(&mut finder);
+
+        assert!(finder.collapsed_groups.contains("Man Pages"));
+        assert_eq!(finder.selected, 2);
+        assert!(finder.selected_item().is_none());
+        assert_eq!(
+            finder.rows_from(0),
+            vec![
+                Row::Item(0),
+                Row::Item(1),
+                Row::Header { start: 2, len: 2 },
+                Row::Header { start: 4, len: 1 },
+                Row::Item(4),
+            ]
+        );
+
+        ctrl_g(&mut finder);
+        assert!(finder.collapsed_groups.is_empty());
+        assert_eq!(finder.selected_item().unwrap().name, "git-log");
+    }
+
+    #[test]
+    fn ctrl_g_ignores_unlabeled_items() {
+        let mut finder = grouped_finder();
+        ctrl_g(&mut finder);
+        assert!(finder.collapsed_groups.is_empty());
+        assert_eq!(finder.selected, 0);
+    }
+
+    #[test]
+    fn navigation_skips_collapsed_items() {
+        let mut finder = grouped_finder();
+        finder.collapsed_groups.insert("Man Pages".to_string());
+        finder.selected = 1;
+
+        finder.move_down();
+        assert_eq!(finder.selected, 2);
+        finder.move_down();
+        assert_eq!(finder.selected, 4);
+        finder.move_up();
+        assert_eq!(finder.selected, 2);
+
+        finder.move_down_by(10);
+        assert_eq!(finder.selected, 4);
+        finder.move_up_by(2);
+        assert_eq!(finder.selected, 1);
+    }
+
+    #[test]
+    fn collapsed_group_renders_header_with_count() {
+        let mut finder = grouped_finder();
+        finder.collapsed_groups.insert("Man Pages".to_string());
+        let lines = render_finder(&mut finder, 50, 14);
+
+        assert!(lines.iter().any(|l| l.contains("▶ Man Pages (2 items)")));
+        assert!(lines.iter().any(|l| l.contains("▼ Guides ")));
+        assert!(!lines.iter().any(|l| l.contains("git-show")));
+    }
+
+    #[test]
+    fn selection_stays_visible_below_headers() {
+        let mut finder = grouped_finder();
+        finder.selected = 4;
+        // Only four rows fit, so the list scrolls past the unlabeled items
+        let lines = render_finder(&mut finder, 50, 10);
+        assert!(lines.iter().any(|l| l.contains("▼ Guides")));
+        assert!(lines.iter().any(|l| l.contains("▶ [Guides] tutorial")));
+    }
+
+    #[test]
+    fn scroll_to_selection_keeps_last_item_in_view() {
+        let items = (0..5000)
+            .map(|i| labeled_item(&format!("page{}", i), "Man Pages"))
+            .collect();
+        let mut finder = Finder::new(items);
+        finder.selected = 4999;
+        finder.scroll_to_selection(10);
+        // Mid-group, so no header: exactly the last ten items
+        assert_eq!(finder.scroll_offset, 4990);
+
+        finder.selected = 5;
+        finder.scroll_to_selection(10);
+        assert_eq!(finder.scroll_offset, 5);
+    }
+
+    #[test]
+    fn scroll_to_selection_skips_collapsed_groups() {
+        let mut finder = grouped_finder();
+        finder.collapsed_groups.insert("Man Pages".to_string());
+        finder.selected = 4;
+        // Rows: add, commit, Man Pages header, Guides header, tutorial
+        finder.scroll_to_selection(2);
+        assert_eq!(finder.scroll_offset, 4);
+        finder.scroll_offset = 0;
+        finder.scroll_to_selection(3);
+        assert_eq!(finder.scroll_offset, 2);
+    }
+
+    // ========================================
+    // Alias tests
+    // ========================================
+
+    #[test]
+    fn aliases_are_searchable() {
+        let mut items = make_items();
+        items[1].aliases = vec!["xq".to_string()];
+        let mut finder = Finder::new(items);
+        finder.set_query("xq".to_string());
+        assert_eq!(filtered_names(&finder), vec!["test"]);
     }
 }
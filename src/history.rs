@@ -1,13 +1,48 @@
+use anyhow::{Result, anyhow};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::time::SystemTime;
+
 use crate::fetcher::ContentSource;
+use crate::paths::{Paths, check_file_name};
 
-#[derive(Debug, Clone)]
+/// Serialized for session persistence; every field but `command` may be
+/// missing, and unknown fields are ignored, so older and newer files load
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct HistoryEntry {
     pub command: Vec<String>,
+    #[serde(default)]
     pub scroll_position: usize,
+    #[serde(default)]
     pub source: ContentSource,
+    /// Page text when the entry was pushed, shown if a re-fetch fails
+    #[serde(default)]
+    pub cached_content: String,
+    /// When `cached_content` was saved; the epoch if unknown
+    #[serde(default = "unknown_time")]
+    pub pushed_at: SystemTime,
+    /// The item whose `invoke_command` opened the page, e.g. a guide or
+    /// `man git-log` from the finder, so it is fetched the same way again
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub invoked: Option<Invocation>,
+}
+
+/// An item page opened through its tool pack `invoke` command rather than
+/// as a subcommand of the current command
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Invocation {
+    /// The item's name, substituted for `{name}`
+    pub name: String,
+    /// The template it was opened with, e.g. `git help {name}`
+    pub invoke_command: String,
 }
 
-#[derive(Debug, Default)]
+fn unknown_time() -> SystemTime {
+    SystemTime::UNIX_EPOCH
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
 pub struct History {
     entries: Vec<HistoryEntry>,
 }
@@ -17,19 +52,48 @@ impl History {
         Self::default()
     }
 
-    pub fn push(&mut self, command: Vec<String>, scroll_position: usize, source: ContentSource) {
+    pub fn push(
+        &mut self,
+        command: Vec<String>,
+        scroll_position: usize,
+        source: ContentSource,
+        cached_content: String,
+        invoked: Option<Invocation>,
+    ) {
         self.entries.push(HistoryEntry {
             command,
             scroll_position,
             source,
+            cached_content,
+            pushed_at: SystemTime::now(),
+            invoked,
         });
     }
 
+    /// Write the history to `paths.snapshot_file(name)`, for `--restore`
+    pub fn save_snapshot(&self, paths: &Paths, name: &str) -> Result<PathBuf> {
+        check_file_name("snapshot", name)?;
+        let path = paths.snapshot_file(name);
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        std::fs::write(&path, serde_json::to_string_pretty(self)? + "\n")?;
+        Ok(path)
+    }
+
+    /// Read a history saved by `save_snapshot`
+    pub fn load_snapshot(paths: &Paths, name: &str) -> Result<History> {
+        check_file_name("snapshot", name)?;
+        let path = paths.snapshot_file(name);
+        let text = std::fs::read_to_string(&path)
+            .map_err(|e| anyhow!("Could not read snapshot '{}': {}", name, e))?;
+        Ok(serde_json::from_str(&text)?)
+    }
+
     pub fn pop(&mut self) -> Option<HistoryEntry> {
         self.entries.pop()
     }
 
-    #[allow(dead_code)]
     pub fn current(&self) -> Option<&HistoryEntry> {
         self.entries.last()
     }
@@ -63,7 +127,200 @@ impl History {
         }
     }
 
+    /// Every command on the way to the current one, e.g.
+    /// `git > git remote > git remote add`. Pages opened through a custom invoke
+    /// keep the same command, so repeated neighbours are shown once.
     pub fn full_breadcrumb(&self, current_cmd: &[String]) -> String {
-        current_cmd.join(" ")
+        let mut parts: Vec<String> = self
+            .entries
+            .iter()
+            .map(|e| e.command.join(" "))
+            .chain(std::iter::once(current_cmd.join(" ")))
+            .filter(|part| !part.is_empty())
+            .collect();
+        parts.dedup();
+        parts.join(" > ")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cmd(parts: &[&str]) -> Vec<String> {
+        parts.iter().map(|s| s.to_string()).collect()
+    }
+
+    fn push(history: &mut History, parts: &[&str]) {
+        history.push(cmd(parts), 0, ContentSource::Help, String::new(), None);
+    }
+
+    // ========================================
+    // full_breadcrumb tests
+    // ========================================
+
+    #[test]
+    fn breadcrumb_single_level() {
+        let history = History::new();
+        assert_eq!(history.full_breadcrumb(&cmd(&["git"])), "git");
+    }
+
+    #[test]
+    fn breadcrumb_shows_full_path_per_level() {
+        let mut history = History::new();
+        push(&mut history, &["git"]);
+        push(&mut history, &["git", "remote"]);
+        assert_eq!(
+            history.full_breadcrumb(&cmd(&["git", "remote", "add"])),
+            "git > git remote > git remote add"
+        );
+    }
+
+    #[test]
+    fn breadcrumb_collapses_same_command_pages() {
+        let mut history = History::new();
+        push(&mut history, &["git"]);
+        push(&mut history, &["git"]);
+        assert_eq!(
+            history.full_breadcrumb(&cmd(&["git", "log"])),
+            "git > git log"
+        );
+    }
+
+    #[test]
+    fn breadcrumb_empty_without_command() {
+        assert_eq!(History::new().full_breadcrumb(&[]), "");
+    }
+
+    // ========================================
+    // Serialization tests
+    // ========================================
+
+    #[test]
+    fn content_source_round_trips() {
+        let sources = [
+            ContentSource::Help,
+            ContentSource::Man,
+            ContentSource::Tldr,
+            ContentSource::Info,
+            ContentSource::Cache {
+                age: std::time::Duration::from_secs(90),
+            },
+            ContentSource::File("/tmp/kubectl.txt".into()),
+        ];
+        for source in sources {
+            let json = serde_json::to_string(&source).unwrap();
+            assert_eq!(
+                serde_json::from_str::<ContentSource>(&json).unwrap(),
+                source
+            );
+        }
+        assert_eq!(
+            serde_json::to_string(&ContentSource::Man).unwrap(),
+            r#""man""#
+        );
+    }
+
+    #[test]
+    fn history_entry_round_trips() {
+        let mut history = History::new();
+        history.push(
+            cmd(&["git", "log"]),
+            12,
+            ContentSource::Man,
+            "LOG".to_string(),
+            Some(Invocation {
+                name: "log".to_string(),
+                invoke_command: "man {base}-{name}".to_string(),
+            }),
+        );
+        let entry = history.pop().unwrap();
+
+        let json = serde_json::to_string(&entry).unwrap();
+        let back: HistoryEntry = serde_json::from_str(&json).unwrap();
+        assert_eq!(back.command, entry.command);
+        assert_eq!(back.scroll_position, 12);
+        assert_eq!(back.source, ContentSource::Man);
+        assert_eq!(back.cached_content, "LOG");
+        assert_eq!(back.pushed_at, entry.pushed_at);
+        assert_eq!(back.invoked, entry.invoked);
+    }
+
+    #[test]
+    fn history_entry_without_invocation_loads() {
+        let json = r#"{"command":["git"],"scroll_position":0,"source":"help"}"#;
+        let entry: HistoryEntry = serde_json::from_str(json).unwrap();
+        assert_eq!(entry.invoked, None);
+    }
+
+    #[test]
+    fn history_round_trips() {
+        let mut history = History::new();
+        push(&mut history, &["git"]);
+        push(&mut history, &["git", "remote"]);
+
+        let json = serde_json::to_string(&history).unwrap();
+        let back: History = serde_json::from_str(&json).unwrap();
+        assert_eq!(back.len(), 2);
+        assert_eq!(
+            back.full_breadcrumb(&cmd(&["git", "remote", "add"])),
+            "git > git remote > git remote add"
+        );
+    }
+
+    // ========================================
+    // Snapshot tests
+    // ========================================
+
+    #[test]
+    fn snapshot_round_trips_through_data_dir() {
+        let dir = std::env::temp_dir().join(format!("helpv-snapshot-{}", std::process::id()));
+        let paths = Paths {
+            data_dir: dir.clone(),
+            ..Paths::default()
+        };
+        let mut history = History::new();
+        push(&mut history, &["git"]);
+        history.push(
+            cmd(&["git", "remote"]),
+            7,
+            ContentSource::Man,
+            "REMOTE".to_string(),
+            None,
+        );
+
+        let path = history.save_snapshot(&paths, "docs-session").unwrap();
+        assert_eq!(path, dir.join("snapshots").join("docs-session.json"));
+        let mut back = History::load_snapshot(&paths, "docs-session").unwrap();
+        assert!(History::load_snapshot(&paths, "missing").is_err());
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        let deepest = back.pop().unwrap();
+        assert_eq!(deepest.command, cmd(&["git", "remote"]));
+        assert_eq!(deepest.scroll_position, 7);
+        assert_eq!(back.len(), 1);
+    }
+
+    #[test]
+    fn snapshot_names_cannot_be_paths() {
+        let paths = Paths::default();
+        for name in ["", "../escape", "a/b", ".hidden"] {
+            assert!(History::new().save_snapshot(&paths, name).is_err());
+            assert!(History::load_snapshot(&paths, name).is_err());
+        }
+    }
+
+    #[test]
+    fn history_entry_missing_fields_use_defaults() {
+        let entry: HistoryEntry =
+            serde_json::from_str(r#"{"command": ["git"], "added_later": true}"#).unwrap();
+        assert_eq!(entry.command, cmd(&["git"]));
+        assert_eq!(entry.scroll_position, 0);
+        assert_eq!(entry.source, ContentSource::Help);
+        assert_eq!(entry.cached_content, "");
+        assert_eq!(entry.pushed_at, SystemTime::UNIX_EPOCH);
+
+        let history: History = serde_json::from_str("{}").unwrap();
+        assert!(history.is_empty());
     }
 }
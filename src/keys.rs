@@ -1,4 +1,5 @@
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use std::time::{Duration, Instant};
 
 use crate::config::KeyConfig;
 
@@ -20,180 +21,232 @@ pub enum Action {
     OpenCommand,
     Back,
     ShowHelp,
+    ToggleRecord,
+    PlayMacro,
+    VisualLine,
+    ToggleFold,
+    FoldAll,
+    UnfoldAll,
+    OpenUrl,
+    ToggleDiff,
+    Reload,
+    CopyCommand,
+    ShowStats,
+    ExportSession,
+    CommandMode,
+    ShowExamples,
+    HighlightWord,
+    SaveSnapshot,
+    /// Forget the back history and recent commands, after asking
+    ClearHistory,
+    /// Open the config file in an editor, reloading it afterwards
+    EditConfig,
+    Suspend,
+    Redraw,
+    /// Jump to the base command of a subcommand chain
+    OpenParent,
+    /// Switch a `--help` page between its stdout and stderr output
+    FetchStderr,
+    /// Show the man page in place of `--help` output, or the reverse
+    ToggleSource,
+}
+
+impl Action {
+    /// Name used by `:help` and `--describe-key`, e.g. `scroll-up`
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Quit => "quit",
+            Self::ScrollUp => "scroll-up",
+            Self::ScrollDown => "scroll-down",
+            Self::HalfPageUp => "half-page-up",
+            Self::HalfPageDown => "half-page-down",
+            Self::PageUp => "page-up",
+            Self::PageDown => "page-down",
+            Self::Top => "top",
+            Self::Bottom => "bottom",
+            Self::Search => "search",
+            Self::NextMatch => "next-match",
+            Self::PrevMatch => "prev-match",
+            Self::OpenFinder => "open-finder",
+            Self::OpenCommand => "open-command",
+            Self::Back => "back",
+            Self::ShowHelp => "show-help",
+            Self::ToggleRecord => "toggle-record",
+            Self::PlayMacro => "play-macro",
+            Self::VisualLine => "visual-line",
+            Self::ToggleFold => "toggle-fold",
+            Self::FoldAll => "fold-all",
+            Self::UnfoldAll => "unfold-all",
+            Self::OpenUrl => "open-url",
+            Self::ToggleDiff => "toggle-diff",
+            Self::Reload => "reload",
+            Self::CopyCommand => "copy-command",
+            Self::ShowStats => "show-stats",
+            Self::ExportSession => "export-session",
+            Self::CommandMode => "command-mode",
+            Self::ShowExamples => "show-examples",
+            Self::HighlightWord => "highlight-word",
+            Self::SaveSnapshot => "save-snapshot",
+            Self::ClearHistory => "clear-history",
+            Self::EditConfig => "edit-config",
+            Self::Suspend => "suspend",
+            Self::Redraw => "redraw",
+            Self::OpenParent => "open-parent",
+            Self::FetchStderr => "fetch-stderr",
+            Self::ToggleSource => "toggle-source",
+        }
+    }
 }
 
 pub struct KeyHandler {
     config: KeyConfig,
-    pending_g: bool,
+    /// First key of a two-key sequence (e.g. the `g` of `gg`) awaiting its second key
+    pending_key: Option<char>,
+    pending_since: Option<Instant>,
+    dead_key_timeout: Duration,
+    /// One line per key bound to more than one action
+    warnings: Vec<String>,
 }
 
 impl KeyHandler {
     pub fn new(config: KeyConfig) -> Self {
+        let warnings = config
+            .find_conflicts()
+            .iter()
+            .map(ToString::to_string)
+            .collect();
         Self {
             config,
-            pending_g: false,
+            pending_key: None,
+            pending_since: None,
+            dead_key_timeout: Duration::from_millis(1000),
+            warnings,
         }
     }
 
+    /// Problems found in the key config, such as conflicting bindings
+    pub fn warnings(&self) -> &[String] {
+        &self.warnings
+    }
+
+    /// Set how long a pending prefix key waits for the rest of its sequence
+    pub fn set_dead_key_timeout(&mut self, timeout: Duration) {
+        self.dead_key_timeout = timeout;
+    }
+
     pub fn handle(&mut self, key: KeyEvent) -> Option<Action> {
-        // Handle 'gg' sequence for going to top
-        if self.pending_g {
-            self.pending_g = false;
-            if key.code == KeyCode::Char('g') {
-                return Some(Action::Top);
+        // A stale prefix is dropped so this key is processed fresh
+        if self.pending_key.is_some()
+            && self
+                .pending_since
+                .is_some_and(|t| t.elapsed() > self.dead_key_timeout)
+        {
+            self.reset_pending();
+        }
+
+        let plain_char = match key.code {
+            KeyCode::Char(c) if (key.modifiers - KeyModifiers::SHIFT).is_empty() => Some(c),
+            _ => None,
+        };
+
+        // Complete a two-key sequence like 'gg'
+        if let Some(first) = self.pending_key {
+            self.reset_pending();
+            if let Some(second) = plain_char {
+                let sequence: String = [first, second].iter().collect();
+                if let Some(action) = self.match_sequence(&sequence) {
+                    return Some(action);
+                }
             }
         }
 
-        // Check for 'g' to start 'gg' sequence
-        if key.code == KeyCode::Char('g') && key.modifiers.is_empty() {
-            self.pending_g = true;
+        // Start a sequence if this key is the prefix of one
+        if let Some(c) = plain_char
+            && self.starts_sequence(c)
+        {
+            self.pending_key = Some(c);
+            self.pending_since = Some(Instant::now());
             return None;
         }
 
         self.match_key(key)
     }
 
+    fn bindings(&self) -> [(&[String], Action); 39] {
+        self.config.bindings()
+    }
+
     fn match_key(&self, key: KeyEvent) -> Option<Action> {
         let key_str = key_to_string(&key);
+        let bindings = self.bindings();
 
-        if self
-            .config
-            .quit
-            .iter()
-            .any(|k| matches_key(k, &key_str, &key))
-        {
-            return Some(Action::Quit);
-        }
-        if self
-            .config
-            .scroll_up
-            .iter()
-            .any(|k| matches_key(k, &key_str, &key))
-        {
-            return Some(Action::ScrollUp);
-        }
-        if self
-            .config
-            .scroll_down
-            .iter()
-            .any(|k| matches_key(k, &key_str, &key))
-        {
-            return Some(Action::ScrollDown);
-        }
-        if self
-            .config
-            .half_page_up
-            .iter()
-            .any(|k| matches_key(k, &key_str, &key))
-        {
-            return Some(Action::HalfPageUp);
-        }
-        if self
-            .config
-            .half_page_down
-            .iter()
-            .any(|k| matches_key(k, &key_str, &key))
-        {
-            return Some(Action::HalfPageDown);
-        }
-        if self
-            .config
-            .page_up
-            .iter()
-            .any(|k| matches_key(k, &key_str, &key))
-        {
-            return Some(Action::PageUp);
-        }
-        if self
-            .config
-            .page_down
-            .iter()
-            .any(|k| matches_key(k, &key_str, &key))
-        {
-            return Some(Action::PageDown);
-        }
-        if self
-            .config
-            .top
-            .iter()
-            .any(|k| matches_key(k, &key_str, &key))
-        {
-            return Some(Action::Top);
-        }
-        if self
-            .config
-            .bottom
-            .iter()
-            .any(|k| matches_key(k, &key_str, &key))
-        {
-            return Some(Action::Bottom);
-        }
-        if self
-            .config
-            .search
-            .iter()
-            .any(|k| matches_key(k, &key_str, &key))
-        {
-            return Some(Action::Search);
-        }
-        if self
-            .config
-            .next_match
-            .iter()
-            .any(|k| matches_key(k, &key_str, &key))
-        {
-            return Some(Action::NextMatch);
-        }
-        if self
-            .config
-            .prev_match
-            .iter()
-            .any(|k| matches_key(k, &key_str, &key))
-        {
-            return Some(Action::PrevMatch);
-        }
-        if self
-            .config
-            .find_subcommand
-            .iter()
-            .any(|k| matches_key(k, &key_str, &key))
-        {
-            return Some(Action::OpenFinder);
-        }
-        if self
-            .config
-            .open_command
+        // Exact matches win over the case-insensitive fallback so that e.g.
+        // `N` isn't swallowed by a binding for `n`
+        bindings
             .iter()
-            .any(|k| matches_key(k, &key_str, &key))
-        {
-            return Some(Action::OpenCommand);
-        }
-        if self
-            .config
-            .back
-            .iter()
-            .any(|k| matches_key(k, &key_str, &key))
-        {
-            return Some(Action::Back);
-        }
-        if self
-            .config
-            .help
+            .find(|(keys, _)| keys.contains(&key_str))
+            .or_else(|| {
+                bindings
+                    .iter()
+                    .find(|(keys, _)| keys.iter().any(|k| matches_key(k, &key_str, &key)))
+            })
+            .map(|(_, action)| *action)
+    }
+
+    fn match_sequence(&self, sequence: &str) -> Option<Action> {
+        self.bindings()
             .iter()
-            .any(|k| matches_key(k, &key_str, &key))
-        {
-            return Some(Action::ShowHelp);
-        }
+            .find(|(keys, _)| keys.iter().any(|k| k == sequence))
+            .map(|(_, action)| *action)
+    }
 
-        None
+    fn starts_sequence(&self, c: char) -> bool {
+        self.bindings()
+            .iter()
+            .any(|(keys, _)| keys.iter().any(|k| is_sequence(k) && k.starts_with(c)))
     }
 
     pub fn reset_pending(&mut self) {
-        self.pending_g = false;
+        self.pending_key = None;
+        self.pending_since = None;
     }
 }
 
-fn key_to_string(key: &KeyEvent) -> String {
+/// Whether a binding is a two-key sequence like `gg` rather than a single named key
+pub fn is_sequence(binding: &str) -> bool {
+    let mut chars = binding.chars();
+    match (chars.next(), chars.next(), chars.next()) {
+        (Some(first), Some(second), None) => {
+            // Two-character key names that are not sequences: "Up", "F1".."F9"
+            let is_named = binding == "Up" || (first == 'F' && second.is_ascii_digit());
+            !is_named && !first.is_whitespace() && !second.is_whitespace()
+        }
+        _ => false,
+    }
+}
+
+/// A binding spelled one way for every spelling `matches_key` treats alike:
+/// `Esc` and `Escape`, `Ctrl-U` and `Ctrl-u`, `Alt-X` and `Alt-x`. Single
+/// characters and sequences keep their case, since `N` and `n` differ.
+pub fn normalize_key(binding: &str) -> String {
+    if let Some(rest) = binding.strip_prefix("Ctrl-") {
+        return format!("Ctrl-{}", rest.to_lowercase());
+    }
+    if let Some(rest) = binding.strip_prefix("Alt-") {
+        return format!("Alt-{}", normalize_key(rest).to_lowercase());
+    }
+    if binding.chars().count() == 1 || is_sequence(binding) {
+        return binding.to_string();
+    }
+    match binding.to_lowercase().as_str() {
+        "esc" => "escape".to_string(),
+        "space" => " ".to_string(),
+        named => named.to_string(),
+    }
+}
+
+/// Key in config notation, e.g. `Ctrl-u` or `Shift-Tab`
+pub fn key_to_string(key: &KeyEvent) -> String {
     let mut s = String::new();
 
     if key.modifiers.contains(KeyModifiers::CONTROL) {
@@ -249,6 +302,12 @@ fn matches_key(pattern: &str, key_str: &str, key: &KeyEvent) -> bool {
                 false
             }
         }
+        // Match the rest of the pattern against the key with Alt removed
+        _ if pattern.starts_with("Alt-") => {
+            let without_alt = KeyEvent::new(key.code, key.modifiers - KeyModifiers::ALT);
+            key.modifiers.contains(KeyModifiers::ALT)
+                && matches_key(&pattern[4..], &key_to_string(&without_alt), &without_alt)
+        }
         _ => pattern.to_lowercase() == key_str.to_lowercase(),
     }
 }
@@ -265,6 +324,10 @@ mod tests {
         KeyEvent::new(KeyCode::Char(c), KeyModifiers::CONTROL)
     }
 
+    fn make_key_alt(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::ALT)
+    }
+
     fn make_key_shift(code: KeyCode) -> KeyEvent {
         KeyEvent::new(code, KeyModifiers::SHIFT)
     }
@@ -342,6 +405,12 @@ mod tests {
         assert_eq!(key_to_string(&key), "Shift-Up");
     }
 
+    #[test]
+    fn key_to_string_alt_modifier() {
+        assert_eq!(key_to_string(&make_key_alt(KeyCode::Char('x'))), "Alt-x");
+        assert_eq!(key_to_string(&make_key_alt(KeyCode::Right)), "Alt-Right");
+    }
+
     // ========================================
     // matches_key tests
     // ========================================
@@ -402,29 +471,76 @@ mod tests {
         assert!(!matches_key("b", &key_str, &key));
     }
 
+    #[test]
+    fn matches_key_alt_char_case_insensitive() {
+        let key = make_key_alt(KeyCode::Char('x'));
+        let key_str = key_to_string(&key);
+        assert!(matches_key("Alt-X", &key_str, &key));
+    }
+
+    #[test]
+    fn matches_key_alt_named_key() {
+        let key = make_key_alt(KeyCode::Left);
+        let key_str = key_to_string(&key);
+        assert!(matches_key("Alt-left", &key_str, &key));
+        assert!(!matches_key("Alt-Right", &key_str, &key));
+    }
+
+    #[test]
+    fn matches_key_alt_requires_alt() {
+        let plain = make_key(KeyCode::Char('x'));
+        assert!(!matches_key("Alt-x", &key_to_string(&plain), &plain));
+
+        let alt = make_key_alt(KeyCode::Char('x'));
+        assert!(!matches_key("x", &key_to_string(&alt), &alt));
+    }
+
     // ========================================
     // KeyHandler gg sequence tests
     // ========================================
 
     fn default_key_config() -> KeyConfig {
-        let mut config = KeyConfig::default();
-        config.quit = vec!["q".to_string()];
-        config.scroll_up = vec!["k".to_string()];
-        config.scroll_down = vec!["j".to_string()];
-        config.top = vec!["gg".to_string()];
-        config.bottom = vec!["G".to_string()];
-        config.half_page_up = vec!["Ctrl-u".to_string()];
-        config.half_page_down = vec!["Ctrl-d".to_string()];
-        config.page_up = vec!["Ctrl-b".to_string()];
-        config.page_down = vec!["Ctrl-f".to_string()];
-        config.search = vec!["/".to_string()];
-        config.next_match = vec!["n".to_string()];
-        config.prev_match = vec!["N".to_string()];
-        config.find_subcommand = vec!["f".to_string()];
-        config.open_command = vec!["o".to_string()];
-        config.back = vec!["Backspace".to_string()];
-        config.help = vec!["?".to_string()];
-        config
+        KeyConfig {
+            quit: vec!["q".to_string()],
+            scroll_up: vec!["k".to_string()],
+            scroll_down: vec!["j".to_string()],
+            top: vec!["gg".to_string()],
+            bottom: vec!["G".to_string()],
+            half_page_up: vec!["Ctrl-u".to_string()],
+            half_page_down: vec!["Ctrl-d".to_string()],
+            page_up: vec!["Ctrl-b".to_string()],
+            page_down: vec!["Ctrl-f".to_string()],
+            search: vec!["/".to_string()],
+            next_match: vec!["n".to_string()],
+            prev_match: vec!["N".to_string()],
+            find_subcommand: vec!["f".to_string()],
+            open_command: vec!["o".to_string()],
+            back: vec!["Backspace".to_string()],
+            help: vec!["?".to_string()],
+            record_macro: vec!["Q".to_string()],
+            play_macro: vec!["@q".to_string()],
+            visual_line: vec!["V".to_string()],
+            toggle_fold: vec!["za".to_string()],
+            fold_all: vec!["zM".to_string()],
+            unfold_all: vec!["zR".to_string()],
+            open_url: vec!["U".to_string()],
+            toggle_diff: vec!["Alt-D".to_string()],
+            reload: vec!["r".to_string()],
+            copy_command: vec!["yc".to_string()],
+            show_stats: vec!["Ctrl-i".to_string(), "Tab".to_string()],
+            export_session: vec!["Ctrl-e".to_string()],
+            command_mode: vec![":".to_string()],
+            show_examples: vec!["E".to_string()],
+            highlight_word: vec!["*".to_string()],
+            save_snapshot: vec!["ms".to_string()],
+            clear_history: Vec::new(),
+            edit_config: Vec::new(),
+            suspend: vec!["Ctrl-z".to_string()],
+            redraw: vec!["Ctrl-l".to_string()],
+            open_parent: vec!["P".to_string()],
+            fetch_stderr: vec!["F5".to_string()],
+            toggle_source: vec!["Alt-M".to_string()],
+        }
     }
 
     #[test]
@@ -432,7 +548,7 @@ mod tests {
         let mut handler = KeyHandler::new(default_key_config());
         let result = handler.handle(make_key(KeyCode::Char('g')));
         assert!(result.is_none());
-        assert!(handler.pending_g);
+        assert_eq!(handler.pending_key, Some('g'));
     }
 
     #[test]
@@ -441,7 +557,7 @@ mod tests {
         handler.handle(make_key(KeyCode::Char('g'))); // First g
         let result = handler.handle(make_key(KeyCode::Char('g'))); // Second g
         assert_eq!(result, Some(Action::Top));
-        assert!(!handler.pending_g);
+        assert!(handler.pending_key.is_none());
     }
 
     #[test]
@@ -450,7 +566,7 @@ mod tests {
         handler.handle(make_key(KeyCode::Char('g'))); // First g
         let result = handler.handle(make_key(KeyCode::Char('j'))); // j instead of g
         assert_eq!(result, Some(Action::ScrollDown));
-        assert!(!handler.pending_g);
+        assert!(handler.pending_key.is_none());
     }
 
     #[test]
@@ -458,7 +574,75 @@ mod tests {
         let mut handler = KeyHandler::new(default_key_config());
         handler.handle(make_key(KeyCode::Char('g'))); // Set pending
         handler.reset_pending();
-        assert!(!handler.pending_g);
+        assert!(handler.pending_key.is_none());
+    }
+
+    #[test]
+    fn gg_stale_pending_is_dropped_after_timeout() {
+        let mut handler = KeyHandler::new(default_key_config());
+        handler.set_dead_key_timeout(Duration::from_millis(10));
+        handler.handle(make_key(KeyCode::Char('g'))); // First g
+        std::thread::sleep(Duration::from_millis(20));
+        // Second g starts a fresh sequence instead of completing the stale one
+        let result = handler.handle(make_key(KeyCode::Char('g')));
+        assert!(result.is_none());
+        assert_eq!(handler.pending_key, Some('g'));
+    }
+
+    #[test]
+    fn gg_within_timeout_returns_top() {
+        let mut handler = KeyHandler::new(default_key_config());
+        handler.set_dead_key_timeout(Duration::from_secs(60));
+        handler.handle(make_key(KeyCode::Char('g')));
+        let result = handler.handle(make_key(KeyCode::Char('g')));
+        assert_eq!(result, Some(Action::Top));
+    }
+
+    #[test]
+    fn z_sequences_map_to_fold_actions() {
+        let mut handler = KeyHandler::new(default_key_config());
+        handler.handle(make_key(KeyCode::Char('z')));
+        assert_eq!(
+            handler.handle(make_key(KeyCode::Char('a'))),
+            Some(Action::ToggleFold)
+        );
+
+        handler.handle(make_key(KeyCode::Char('z')));
+        let shift_m = KeyEvent::new(KeyCode::Char('M'), KeyModifiers::SHIFT);
+        assert_eq!(handler.handle(shift_m), Some(Action::FoldAll));
+
+        handler.handle(make_key(KeyCode::Char('z')));
+        assert_eq!(
+            handler.handle(make_key(KeyCode::Char('R'))),
+            Some(Action::UnfoldAll)
+        );
+    }
+
+    // ========================================
+    // Conflict warning tests
+    // ========================================
+
+    #[test]
+    fn handler_warns_about_conflicting_keys() {
+        let mut config = default_key_config();
+        config.search.push("q".to_string());
+        let mut handler = KeyHandler::new(config);
+
+        assert_eq!(
+            handler.warnings(),
+            ["Key 'q' is bound to both quit and search; quit wins"]
+        );
+        // The earlier binding still takes the key
+        assert_eq!(
+            handler.handle(make_key(KeyCode::Char('q'))),
+            Some(Action::Quit)
+        );
+    }
+
+    #[test]
+    fn handler_without_conflicts_has_no_warnings() {
+        let handler = KeyHandler::new(default_key_config());
+        assert!(handler.warnings().is_empty());
     }
 
     // ========================================
@@ -493,6 +677,41 @@ mod tests {
         assert_eq!(result, Some(Action::HalfPageUp));
     }
 
+    #[test]
+    fn handler_ctrl_z_suspends() {
+        let mut handler = KeyHandler::new(default_key_config());
+        let result = handler.handle(make_key_ctrl('z'));
+        assert_eq!(result, Some(Action::Suspend));
+    }
+
+    #[test]
+    fn handler_capital_p_opens_parent() {
+        let mut handler = KeyHandler::new(default_key_config());
+        let result = handler.handle(make_key(KeyCode::Char('P')));
+        assert_eq!(result, Some(Action::OpenParent));
+    }
+
+    #[test]
+    fn handler_f5_fetches_stderr() {
+        let mut handler = KeyHandler::new(default_key_config());
+        let result = handler.handle(make_key(KeyCode::F(5)));
+        assert_eq!(result, Some(Action::FetchStderr));
+    }
+
+    #[test]
+    fn handler_alt_m_toggles_source() {
+        let mut handler = KeyHandler::new(default_key_config());
+        let result = handler.handle(make_key_alt(KeyCode::Char('m')));
+        assert_eq!(result, Some(Action::ToggleSource));
+    }
+
+    #[test]
+    fn handler_ctrl_l_redraws() {
+        let mut handler = KeyHandler::new(default_key_config());
+        let result = handler.handle(make_key_ctrl('l'));
+        assert_eq!(result, Some(Action::Redraw));
+    }
+
     #[test]
     fn handler_half_page_down() {
         let mut handler = KeyHandler::new(default_key_config());
@@ -521,6 +740,85 @@ mod tests {
         assert_eq!(result, Some(Action::OpenFinder));
     }
 
+    #[test]
+    fn handler_exact_match_beats_case_insensitive_fallback() {
+        let mut handler = KeyHandler::new(default_key_config());
+        assert_eq!(
+            handler.handle(make_key(KeyCode::Char('N'))),
+            Some(Action::PrevMatch)
+        );
+        assert_eq!(
+            handler.handle(make_key(KeyCode::Char('Q'))),
+            Some(Action::ToggleRecord)
+        );
+    }
+
+    #[test]
+    fn handler_open_url_does_not_shadow_half_page_up() {
+        let mut config = default_key_config();
+        config.half_page_up.push("u".to_string());
+        let mut handler = KeyHandler::new(config);
+        assert_eq!(
+            handler.handle(make_key(KeyCode::Char('U'))),
+            Some(Action::OpenUrl)
+        );
+        assert_eq!(
+            handler.handle(make_key(KeyCode::Char('u'))),
+            Some(Action::HalfPageUp)
+        );
+    }
+
+    #[test]
+    fn handler_diff_key_is_not_half_page_down() {
+        let mut handler = KeyHandler::new(default_key_config());
+        assert_eq!(
+            handler.handle(make_key_alt(KeyCode::Char('d'))),
+            Some(Action::ToggleDiff)
+        );
+        assert_eq!(
+            handler.handle(make_key_ctrl('d')),
+            Some(Action::HalfPageDown)
+        );
+    }
+
+    #[test]
+    fn handler_alt_binding() {
+        let mut config = default_key_config();
+        config.back.push("Alt-Left".to_string());
+        let mut handler = KeyHandler::new(config);
+        assert_eq!(
+            handler.handle(make_key_alt(KeyCode::Left)),
+            Some(Action::Back)
+        );
+        assert_eq!(handler.handle(make_key(KeyCode::Left)), None);
+    }
+
+    #[test]
+    fn handler_configured_sequence() {
+        let mut handler = KeyHandler::new(default_key_config());
+        assert!(handler.handle(make_key(KeyCode::Char('@'))).is_none());
+        let result = handler.handle(make_key(KeyCode::Char('q')));
+        assert_eq!(result, Some(Action::PlayMacro));
+    }
+
+    #[test]
+    fn handler_sequence_prefix_then_other_key() {
+        let mut handler = KeyHandler::new(default_key_config());
+        handler.handle(make_key(KeyCode::Char('@')));
+        let result = handler.handle(make_key(KeyCode::Char('j')));
+        assert_eq!(result, Some(Action::ScrollDown));
+    }
+
+    #[test]
+    fn is_sequence_detection() {
+        assert!(is_sequence("gg"));
+        assert!(is_sequence("@q"));
+        assert!(!is_sequence("g"));
+        assert!(!is_sequence("Up"));
+        assert!(!is_sequence("F1"));
+        assert!(!is_sequence("Home"));
+    }
+
     #[test]
     fn handler_unmapped_key_returns_none() {
         let mut handler = KeyHandler::new(default_key_config());
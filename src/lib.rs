@@ -1,10 +1,16 @@
 pub mod app;
+pub mod clipboard;
 pub mod config;
+pub mod debug_log;
 pub mod fetcher;
 pub mod finder;
 pub mod history;
 pub mod keys;
+pub mod opener;
 pub mod pager;
 pub mod parser;
+pub mod paths;
 pub mod switcher;
+pub mod text_util;
 pub mod toolpacks;
+pub mod watcher;
@@ -5,10 +5,19 @@ use crossterm::{
     terminal::{EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode},
 };
 use ratatui::prelude::*;
-use std::io::{self, stdout};
+use std::io::{self, Write, stdout};
+use std::panic::{self, AssertUnwindSafe};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime};
 
-use helpv::app::App;
-use helpv::config::Config;
+#[cfg(unix)]
+use helpv::app::register_quit_signals;
+use helpv::app::{App, format_subcommand_list};
+use helpv::config::{Config, Overrides};
+use helpv::fetcher::RUNNING_ENV;
+use helpv::history::History;
+use helpv::paths::{Paths, tilde_path};
 
 #[derive(Parser, Debug)]
 #[command(name = "helpv")]
@@ -28,10 +37,27 @@ to drill into them, and Backspace to go back."
     gg, G             Jump to top/bottom
     /                 Search in help text
     n/N               Next/previous search match
+    *                 Search for the first word on the top line
     f                 Fuzzy find subcommands
+    Ctrl-s            Cycle finder sort order (in finder)
+    Ctrl-x            Export the filtered subcommands as TSV (in finder)
+    Tab, Shift-Tab    Jump between label groups (in finder)
     o                 Open arbitrary command
     Enter             Drill into selected subcommand
     Backspace         Go back to parent command
+    za, zM/zR         Fold current section, fold/unfold all
+    U                 Open the last finder selection's docs URL
+    V, then y         Select lines and copy them to the clipboard
+    Alt-D             Diff --help output against the man page
+    E                 Append the tool's examples to the page
+    r                 Reload the page, keeping its source (help/man/tldr/info)
+    yc                Copy the current command, shell-quoted
+    Ctrl-i, Tab       Show page statistics (size, source, fetch time)
+    Ctrl-e            Save a script that reopens this page and position
+    ms                Save the navigation as a named snapshot (--restore)
+    Q                 Start/stop recording a macro
+    @q                Play back the recorded macro
+    :help ACTION      Show the keys bound to an action
     ?                 Show keybindings help
     q, Escape         Quit
 
@@ -39,26 +65,214 @@ EXAMPLES:
     helpv git                View git help
     helpv git commit         View git commit help
     helpv cargo build        View cargo build help
+    helpv git --subcommand-list | fzf
+                             Pick a git subcommand from a script
+    helpv --file kubectl.txt View help saved by another machine
+    helpv --restore docs     Reopen the pages saved with `ms` as docs
+    helpv --verify-pack git  Check git's tool pack discovery sources
+    helpv --describe-key search
+                             Show the keys bound to search
+    helpv --clear-command-history
+                             Forget the recent commands offered by `o`
 
 CONFIGURATION:
-    Config file: ~/.config/helpv/config.toml
+    Config file: $XDG_CONFIG_HOME/helpv/config.toml (default ~/.config/helpv),
+    overridden by $HELPV_CONFIG or --config PATH
     Customize keybindings, help flags, and subcommand patterns.")]
 struct Args {
-    /// Command (and optional subcommands) to show help for
-    #[arg(required = true, value_name = "COMMAND")]
+    /// Command (and optional subcommands) to show help for; defaults to
+    /// `default_command` from the config
+    #[arg(value_name = "COMMAND")]
     command: Vec<String>,
+
+    /// Print subcommands as tab-separated lines (name, description, label, invoke) and exit
+    #[arg(long)]
+    subcommand_list: bool,
+
+    /// Read config from PATH instead of $HELPV_CONFIG or the default location
+    #[arg(long, value_name = "PATH")]
+    config: Option<PathBuf>,
+
+    /// Apply the [profiles.NAME] section of config.toml on top of the base config
+    #[arg(long, value_name = "NAME")]
+    profile: Option<String>,
+
+    /// Print subcommands as JSON and exit
+    #[arg(long, conflicts_with = "subcommand_list")]
+    subcommand_list_json: bool,
+
+    /// Run TEMPLATE through the shell on finder selection instead of drilling in
+    /// ({cmd}, {base}, and {sub} are substituted); helpv exits first
+    #[arg(long, value_name = "TEMPLATE")]
+    exec_on_select: Option<String>,
+
+    /// Ignore exec_on_select from config.toml
+    #[arg(long, conflicts_with = "exec_on_select")]
+    no_exec_on_select: bool,
+
+    /// Write the finder's Ctrl-X export to PATH instead of the terminal
+    #[arg(long, value_name = "PATH")]
+    finder_export_file: Option<PathBuf>,
+
+    /// Start scrolled to this line (1-based)
+    #[arg(long, value_name = "N")]
+    line: Option<usize>,
+
+    /// Start with this search active
+    #[arg(long, value_name = "QUERY", allow_hyphen_values = true)]
+    search: Option<String>,
+
+    /// Show help saved in PATH (plain text or a troff man page) instead of
+    /// running COMMAND, which defaults to the file name
+    #[arg(long, value_name = "PATH")]
+    file: Option<PathBuf>,
+
+    /// Reopen the navigation saved with `ms` as NAME, at its deepest page
+    #[arg(long, value_name = "NAME", conflicts_with_all = ["command", "file"])]
+    restore: Option<String>,
+
+    /// Run TOOL's tool pack discovery sources, print any problems, and exit
+    #[arg(long, value_name = "TOOL")]
+    verify_pack: Option<String>,
+
+    /// Print the keys bound to ACTION (e.g. search, scroll-up) and exit
+    #[arg(long, value_name = "ACTION")]
+    describe_key: Option<String>,
+
+    /// Log app state transitions to $XDG_DATA_HOME/helpv/debug.jsonl (also
+    /// HELPV_DEBUG=1)
+    #[arg(long)]
+    debug: bool,
+
+    /// Forget the command switcher's saved recent commands and exit
+    #[arg(long)]
+    clear_command_history: bool,
+}
+
+/// XDG paths, with `--config` taking precedence over `$HELPV_CONFIG`
+fn resolve_paths(config: Option<&Path>) -> Paths {
+    let paths = Paths::from_env();
+    match config {
+        Some(path) => paths.with_config_file(path),
+        None => paths,
+    }
+}
+
+/// Load the config, printing anything wrong with it to stderr
+fn load_config(paths: &Paths, profile: Option<&str>) -> Result<Config> {
+    let config = Config::load_with_profile(paths, profile)?;
+    for warning in &config.warnings {
+        eprintln!("Warning: {}", warning);
+    }
+    Ok(config)
 }
 
+/// How long to wait for background discovery when listing subcommands
+const DISCOVERY_TIMEOUT: Duration = Duration::from_secs(5);
+
 fn main() -> Result<()> {
+    // A command helpv ran wants a pager and helpv is the user's $PAGER:
+    // hand off to a plain pager rather than starting helpv inside itself
+    if std::env::var(RUNNING_ENV).is_ok() {
+        return exec_fallback_pager();
+    }
+
     let args = Args::parse();
 
-    if args.command.is_empty() {
-        eprintln!("Usage: helpv <COMMAND> [SUBCOMMANDS...]");
-        eprintln!("Example: helpv git");
-        std::process::exit(1);
+    if let Some(ref tool) = args.verify_pack {
+        let paths = resolve_paths(args.config.as_deref());
+        let config = load_config(&paths, args.profile.as_deref())?;
+        return verify_pack(&config, tool);
+    }
+
+    if let Some(ref action) = args.describe_key {
+        let paths = resolve_paths(args.config.as_deref());
+        let config = load_config(&paths, args.profile.as_deref())?;
+        return describe_key(&config, action);
+    }
+
+    if args.clear_command_history {
+        let paths = resolve_paths(args.config.as_deref());
+        if let Some(path) = paths.command_history_file()
+            && path.exists()
+        {
+            std::fs::remove_file(&path)?;
+        }
+        println!("Command history cleared");
+        return Ok(());
+    }
+
+    let mut command = args.command;
+    if command.is_empty()
+        && let Some(ref file) = args.file
+    {
+        command = file_command(file);
     }
 
-    let config = Config::load()?;
+    let paths = resolve_paths(args.config.as_deref());
+    let mut config = load_config(&paths, args.profile.as_deref())?;
+
+    if command.is_empty() && args.restore.is_none() {
+        match config.default_command.clone() {
+            Some(default) if !default.is_empty() => command = default,
+            _ => {
+                eprintln!("Usage: helpv <COMMAND> [SUBCOMMANDS...]");
+                eprintln!("Example: helpv git");
+                std::process::exit(1);
+            }
+        }
+    }
+    config.apply_overrides(Overrides {
+        exec_on_select: if args.no_exec_on_select {
+            Some(None)
+        } else {
+            args.exec_on_select.clone().map(Some)
+        },
+        finder_export_file: args.finder_export_file.clone(),
+        help_proxy: std::env::var("HELPV_PROXY").ok().filter(|p| !p.is_empty()),
+        debug_log: args.debug || std::env::var("HELPV_DEBUG").is_ok_and(|v| v == "1"),
+    });
+
+    let load = |command: Vec<String>, config: Config| match (&args.restore, &args.file) {
+        (Some(name), _) => App::restore(History::load_snapshot(&paths, name)?, config),
+        (None, Some(file)) => App::from_file(command, file, config),
+        (None, None) => App::new(command, config),
+    };
+
+    if args.subcommand_list || args.subcommand_list_json {
+        let mut app = load(command, config)?;
+        app.wait_for_discovery(DISCOVERY_TIMEOUT);
+        let output = if args.subcommand_list_json {
+            serde_json::to_string_pretty(&app.subcommands)? + "\n"
+        } else {
+            format_subcommand_list(&app.subcommands)
+        };
+        // A closed pipe (e.g. `| head`) is not an error for scripting use
+        if let Err(e) = stdout().lock().write_all(output.as_bytes())
+            && e.kind() != io::ErrorKind::BrokenPipe
+        {
+            return Err(e.into());
+        }
+        return Ok(());
+    }
+
+    if let Some(report) = paths.take_new_crash_report() {
+        eprintln!("A crash report was saved to {}", tilde_path(&report));
+    }
+
+    let mut app = match load(command, config) {
+        Ok(app) => app,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        }
+    };
+    app.open_at(args.line, args.search.as_deref());
+
+    // Quit through the normal path on SIGHUP/SIGTERM so the terminal gets
+    // restored; registered before raw mode so an early signal can't leave it on
+    #[cfg(unix)]
+    register_quit_signals(&app.signal_quit)?;
 
     // Initialize terminal
     enable_raw_mode()?;
@@ -69,7 +283,25 @@ fn main() -> Result<()> {
     let mut terminal = Terminal::new(backend)?;
 
     // Run the app
-    let result = run_app(&mut terminal, args.command, config);
+    // `app.exec_command` then holds any `exec_on_select` command to run once
+    // the TUI is gone
+    // Keep the panic message for the crash report instead of printing it
+    // onto the alternate screen. Panics on the fetch and discovery threads
+    // aren't a crash of the app and go to the default hook as before.
+    let panic_message = Arc::new(Mutex::new(None));
+    let hook_message = Arc::clone(&panic_message);
+    let main_thread = std::thread::current().id();
+    let default_hook = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        if std::thread::current().id() != main_thread {
+            default_hook(info);
+        } else if let Ok(mut message) = hook_message.lock() {
+            *message = Some(info.to_string());
+        }
+    }));
+    let result = panic::catch_unwind(AssertUnwindSafe(|| app.run(&mut terminal)));
+    // Back to the default hook for anything after the TUI
+    drop(panic::take_hook());
 
     // Restore terminal
     disable_raw_mode()?;
@@ -77,19 +309,126 @@ fn main() -> Result<()> {
     terminal.show_cursor()?;
 
     // Handle any errors
-    if let Err(e) = result {
-        eprintln!("Error: {}", e);
+    match result {
+        Ok(Ok(())) => match app.exec_command {
+            Some(command) => exec_shell(&command),
+            None => Ok(()),
+        },
+        Ok(Err(e)) => {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        }
+        Err(_) => {
+            let message = panic_message
+                .lock()
+                .ok()
+                .and_then(|mut m| m.take())
+                .unwrap_or_else(|| "unknown panic".to_string());
+            eprintln!("helpv crashed: {}", message);
+            match write_crash_report(&paths, &app, &message) {
+                Ok(path) => eprintln!("A crash report was saved to {}", tilde_path(&path)),
+                Err(e) => eprintln!("Could not save crash report: {}", e),
+            }
+            std::process::exit(101);
+        }
+    }
+}
+
+/// The command name for `--file` without COMMAND: the file name minus its
+/// extension, e.g. `kubectl` for `kubectl.txt`
+fn file_command(path: &Path) -> Vec<String> {
+    path.file_stem()
+        .map(|stem| vec![stem.to_string_lossy().into_owned()])
+        .unwrap_or_default()
+}
+
+/// Save `App::crash_context` plus the panic message as JSON in the data directory
+fn write_crash_report(paths: &Paths, app: &App, error: &str) -> Result<PathBuf> {
+    let mut report = app.crash_context();
+    report["error"] = error.into();
+
+    let path = paths.crash_file(SystemTime::now());
+    std::fs::create_dir_all(&paths.data_dir)?;
+    std::fs::write(&path, serde_json::to_string_pretty(&report)? + "\n")?;
+    Ok(path)
+}
+
+/// Print `ToolPack::verify` results for `--verify-pack`; exits non-zero on problems
+fn verify_pack(config: &Config, tool: &str) -> Result<()> {
+    let Some(pack) = config.toolpacks.get(tool) else {
+        eprintln!("No tool pack for '{}'", tool);
         std::process::exit(1);
+    };
+
+    let warnings = pack.verify(tool);
+    if warnings.is_empty() {
+        println!("{}: {} discovery source(s) OK", tool, pack.discover.len());
+        return Ok(());
+    }
+
+    for warning in &warnings {
+        println!("{}: {}", tool, warning);
+    }
+    std::process::exit(1);
+}
+
+/// Print `KeyConfig::describe_action` for `--describe-key`; an unknown action
+/// lists the valid names and exits non-zero
+fn describe_key(config: &Config, action: &str) -> Result<()> {
+    match config.keys.describe_action(action) {
+        Some(description) => {
+            println!("{}", description);
+            Ok(())
+        }
+        None => {
+            eprintln!("Unknown action '{}'", action);
+            eprintln!("Actions: {}", config.keys.action_names().join(", "));
+            std::process::exit(1);
+        }
     }
+}
+
+/// Replace helpv with `sh -c COMMAND`
+#[cfg(unix)]
+fn exec_shell(command: &str) -> Result<()> {
+    use std::os::unix::process::CommandExt;
 
+    let err = std::process::Command::new("sh")
+        .args(["-c", command])
+        .exec();
+    Err(anyhow::anyhow!("Could not run '{}': {}", command, err))
+}
+
+/// Replace helpv with `less`, or `more` without it, passing on our arguments.
+/// With neither installed the input is copied through unpaged.
+#[cfg(unix)]
+fn exec_fallback_pager() -> Result<()> {
+    use std::os::unix::process::CommandExt;
+
+    for pager in ["less", "more"] {
+        // exec only returns if the pager couldn't be started
+        let _ = std::process::Command::new(pager)
+            .args(std::env::args_os().skip(1))
+            .exec();
+    }
+    io::copy(&mut io::stdin(), &mut stdout())?;
     Ok(())
 }
 
-fn run_app(
-    terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
-    command: Vec<String>,
-    config: Config,
-) -> Result<()> {
-    let mut app = App::new(command, config)?;
-    app.run(terminal)
+/// Run `more` on our arguments and exit with its status
+#[cfg(not(unix))]
+fn exec_fallback_pager() -> Result<()> {
+    let status = std::process::Command::new("more")
+        .args(std::env::args_os().skip(1))
+        .status()?;
+    std::process::exit(status.code().unwrap_or(1));
+}
+
+/// Run `cmd /C COMMAND` and exit with its status
+#[cfg(not(unix))]
+fn exec_shell(command: &str) -> Result<()> {
+    let status = std::process::Command::new("cmd")
+        .args(["/C", command])
+        .status()?;
+    std::process::exit(status.code().unwrap_or(1));
 }
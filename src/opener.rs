@@ -0,0 +1,26 @@
+use anyhow::{Result, anyhow};
+use std::process::{Command, Stdio};
+
+/// URL openers to try, in order: macOS, then freedesktop
+const OPEN_COMMANDS: &[&str] = &["open", "xdg-open"];
+
+/// Open a URL in the default browser using the first available opener
+pub fn open_url(url: &str) -> Result<()> {
+    for program in OPEN_COMMANDS {
+        let Ok(status) = Command::new(program)
+            .arg(url)
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status()
+        else {
+            continue;
+        };
+
+        if status.success() {
+            return Ok(());
+        }
+    }
+
+    Err(anyhow!("No URL opener found (tried open, xdg-open)"))
+}
@@ -6,8 +6,12 @@ use ratatui::{
     widgets::{Block, Borders, Clear, Paragraph, Widget, Wrap},
 };
 use regex::Regex;
+use std::borrow::Cow;
+use std::collections::{HashMap, HashSet};
+use std::ops::Range;
 
-use crate::fetcher::ContentSource;
+use crate::fetcher::{ContentSource, strip_man_formatting};
+use crate::parser::ParseMethod;
 
 pub struct Pager {
     pub content: Vec<String>,
@@ -15,32 +19,108 @@ pub struct Pager {
     pub search_query: Option<String>,
     pub search_matches: Vec<usize>,
     pub current_match: usize,
+    /// Non-matching lines within `search_context` lines of a match
+    pub context_lines: HashSet<usize>,
+    /// Lines of context around matches; 0 disables the grep-like view
+    pub search_context: usize,
+    /// Match the search query's case exactly
+    pub case_sensitive: bool,
+    /// Section heading line index -> whether that section is folded
+    pub folds: HashMap<usize, bool>,
+    /// Heading line indices, from `detect_sections`
+    sections: Vec<usize>,
     search_regex: Option<Regex>,
+    /// Raw `man` output for content lines that carry bold/underline, by line index
+    man_formatting: HashMap<usize, String>,
 }
 
+/// Counts shown in the statistics overlay
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ContentStats {
+    pub lines: usize,
+    pub words: usize,
+    pub characters: usize,
+    /// Section headings, as used for folding
+    pub sections: usize,
+}
+
+/// One row of the pager as displayed once folds are applied
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Row {
+    Line(usize),
+    /// Placeholder for the hidden body of a folded section
+    Fold {
+        heading: usize,
+        hidden: usize,
+    },
+}
+
+impl Row {
+    /// First content line this row stands for
+    fn first_line(self) -> usize {
+        match self {
+            Row::Line(line) => line,
+            Row::Fold { heading, .. } => heading + 1,
+        }
+    }
+}
+
+/// Blank lines appended so the last lines of content aren't pinned to the viewport bottom
+const BOTTOM_PADDING: usize = 5;
+
 impl Pager {
     pub fn new(content: String) -> Self {
         let mut lines: Vec<String> = content.lines().map(|s| s.to_string()).collect();
-        // Add padding so the last lines of content aren't pinned to the viewport bottom
-        for _ in 0..5 {
+        for _ in 0..BOTTOM_PADDING {
             lines.push(String::new());
         }
+        let sections = detect_sections(&lines);
         Self {
             content: lines,
             scroll: 0,
             search_query: None,
             search_matches: Vec::new(),
             current_match: 0,
+            context_lines: HashSet::new(),
+            search_context: 0,
+            case_sensitive: false,
+            folds: HashMap::new(),
+            sections,
             search_regex: None,
+            man_formatting: HashMap::new(),
         }
     }
 
+    pub fn with_search_context(mut self, lines: usize) -> Self {
+        self.search_context = lines;
+        self
+    }
+
+    pub fn with_case_sensitive(mut self, case_sensitive: bool) -> Self {
+        self.case_sensitive = case_sensitive;
+        self
+    }
+
     pub fn scroll_down(&mut self, amount: usize) {
-        self.scroll = self.scroll.saturating_add(amount);
+        if !self.has_folds() {
+            self.scroll = self.scroll.saturating_add(amount);
+            return;
+        }
+
+        let rows = self.rows();
+        let target = (row_of(&rows, self.scroll) + amount).min(rows.len().saturating_sub(1));
+        self.scroll = rows.get(target).map_or(0, |r| r.first_line());
     }
 
     pub fn scroll_up(&mut self, amount: usize) {
-        self.scroll = self.scroll.saturating_sub(amount);
+        if !self.has_folds() {
+            self.scroll = self.scroll.saturating_sub(amount);
+            return;
+        }
+
+        let rows = self.rows();
+        let target = row_of(&rows, self.scroll).saturating_sub(amount);
+        self.scroll = rows.get(target).map_or(0, |r| r.first_line());
     }
 
     pub fn scroll_to_top(&mut self) {
@@ -48,14 +128,115 @@ impl Pager {
     }
 
     pub fn scroll_to_bottom(&mut self, viewport_height: usize) {
-        if self.content.len() > viewport_height {
-            self.scroll = self.content.len() - viewport_height;
+        let rows = self.rows();
+        if rows.len() > viewport_height {
+            self.scroll = rows[rows.len() - viewport_height].first_line();
         }
     }
 
     pub fn clamp_scroll(&mut self, viewport_height: usize) {
-        let max_scroll = self.content.len().saturating_sub(viewport_height);
-        self.scroll = self.scroll.min(max_scroll);
+        if !self.has_folds() {
+            let max_scroll = self.content.len().saturating_sub(viewport_height);
+            self.scroll = self.scroll.min(max_scroll);
+            return;
+        }
+
+        let rows = self.rows();
+        let max_row = rows.len().saturating_sub(viewport_height);
+        if row_of(&rows, self.scroll) > max_row {
+            self.scroll = rows.get(max_row).map_or(0, |r| r.first_line());
+        }
+    }
+
+    // ========================================
+    // Section folding
+    // ========================================
+
+    fn has_folds(&self) -> bool {
+        self.folds.values().any(|&folded| folded)
+    }
+
+    fn is_folded(&self, heading: usize) -> bool {
+        self.folds.get(&heading).copied().unwrap_or(false)
+    }
+
+    /// Heading of the section containing `line`
+    pub fn section_at(&self, line: usize) -> Option<usize> {
+        self.sections.iter().rev().find(|&&h| h <= line).copied()
+    }
+
+    /// Lines hidden when the section at `heading` is folded. Blank lines before
+    /// the next heading stay visible so folded sections keep their spacing.
+    fn section_body(&self, heading: usize) -> Range<usize> {
+        let next = self
+            .sections
+            .iter()
+            .find(|&&h| h > heading)
+            .copied()
+            .unwrap_or(self.content.len());
+        let mut end = next;
+        while end > heading + 1 && self.content[end - 1].trim().is_empty() {
+            end -= 1;
+        }
+        heading + 1..end
+    }
+
+    /// Fold or unfold the section at the top of the viewport
+    pub fn toggle_fold(&mut self) {
+        let Some(heading) = self.section_at(self.scroll) else {
+            return;
+        };
+        let folded = !self.is_folded(heading);
+        self.folds.insert(heading, folded);
+        if folded && self.section_body(heading).contains(&self.scroll) {
+            self.scroll = heading;
+        }
+    }
+
+    pub fn fold_all(&mut self) {
+        for &heading in &self.sections {
+            self.folds.insert(heading, true);
+        }
+        if let Some(heading) = self.section_at(self.scroll)
+            && self.section_body(heading).contains(&self.scroll)
+        {
+            self.scroll = heading;
+        }
+    }
+
+    pub fn unfold_all(&mut self) {
+        self.folds.clear();
+    }
+
+    /// Unfold the section hiding `line`, if any
+    fn reveal(&mut self, line: usize) {
+        if let Some(heading) = self.section_at(line)
+            && self.section_body(heading).contains(&line)
+        {
+            self.folds.remove(&heading);
+        }
+    }
+
+    /// Rows to display, with each folded section body collapsed to one row
+    fn rows(&self) -> Vec<Row> {
+        let mut rows = Vec::with_capacity(self.content.len());
+        let mut line = 0;
+        while line < self.content.len() {
+            rows.push(Row::Line(line));
+            if self.is_folded(line) {
+                let body = self.section_body(line);
+                if !body.is_empty() {
+                    rows.push(Row::Fold {
+                        heading: line,
+                        hidden: body.len(),
+                    });
+                    line = body.end;
+                    continue;
+                }
+            }
+            line += 1;
+        }
+        rows
     }
 
     pub fn set_search(&mut self, query: &str) {
@@ -71,18 +252,50 @@ impl Pager {
 
         // Find all matching lines
         if self.search_regex.is_some() {
+            let lower_query = query.to_lowercase();
             for (i, line) in self.content.iter().enumerate() {
-                if line.to_lowercase().contains(&query.to_lowercase()) {
+                let found = if self.case_sensitive {
+                    line.contains(query)
+                } else {
+                    line.to_lowercase().contains(&lower_query)
+                };
+                if found {
                     self.search_matches.push(i);
                 }
             }
         }
+
+        self.update_context_lines();
+    }
+
+    /// Recompute the `±search_context` neighborhood around each match
+    fn update_context_lines(&mut self) {
+        self.context_lines.clear();
+        if self.search_context == 0 {
+            return;
+        }
+
+        let last = self.content.len().saturating_sub(1);
+        for &m in &self.search_matches {
+            let start = m.saturating_sub(self.search_context);
+            let end = (m + self.search_context).min(last);
+            self.context_lines.extend(start..=end);
+        }
+        for m in &self.search_matches {
+            self.context_lines.remove(m);
+        }
+    }
+
+    /// Whether the grep-like context view is active
+    pub fn showing_context(&self) -> bool {
+        self.search_context > 0 && !self.search_matches.is_empty()
     }
 
     pub fn clear_search(&mut self) {
         self.search_query = None;
         self.search_regex = None;
         self.search_matches.clear();
+        self.context_lines.clear();
         self.current_match = 0;
     }
 
@@ -93,6 +306,7 @@ impl Pager {
 
         self.current_match = (self.current_match + 1) % self.search_matches.len();
         self.scroll = self.search_matches[self.current_match];
+        self.reveal(self.scroll);
     }
 
     pub fn prev_match(&mut self) {
@@ -106,6 +320,25 @@ impl Pager {
             self.current_match -= 1;
         }
         self.scroll = self.search_matches[self.current_match];
+        self.reveal(self.scroll);
+    }
+
+    /// Scroll to the first content line `f` accepts, unfolding its section.
+    /// Returns `false`, leaving the view alone, when no line matches.
+    pub fn jump_to_first_line_matching<F: Fn(&str) -> bool>(&mut self, f: F) -> bool {
+        let end = self.content.len().saturating_sub(BOTTOM_PADDING);
+        let Some(line) = self.content[..end].iter().position(|line| f(line)) else {
+            return false;
+        };
+        self.scroll = line;
+        self.reveal(line);
+        true
+    }
+
+    /// Scroll to the first line matching the regex `pattern`
+    pub fn scroll_to_regex(&mut self, pattern: &str) -> anyhow::Result<bool> {
+        let re = Regex::new(pattern)?;
+        Ok(self.jump_to_first_line_matching(|line| re.is_match(line)))
     }
 
     pub fn match_count(&self) -> usize {
@@ -116,13 +349,309 @@ impl Pager {
         self.current_match
     }
 
+    /// The page text as given to `new`, without the bottom padding. Anything
+    /// that copies, saves or exports the whole page should use this.
+    pub fn as_plain_text(&self) -> String {
+        self.content[..self.text_end()].join("\n")
+    }
+
+    /// The rows a `viewport_height`-line viewport shows at the current
+    /// scroll, with folded sections as their placeholder line. The
+    /// grep-style search context view is not applied.
+    pub fn visible_as_plain_text(&self, viewport_height: usize) -> String {
+        let rows = self.rows();
+        let end = self.text_end();
+        rows.iter()
+            .skip(row_of(&rows, self.scroll))
+            .take(viewport_height)
+            .take_while(|row| row.first_line() < end)
+            .map(|&row| match row {
+                Row::Line(line) => self.content[line].clone(),
+                Row::Fold { heading, hidden } => fold_placeholder(&self.content[heading], hidden),
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Index of the first bottom padding line
+    fn text_end(&self) -> usize {
+        self.content.len().saturating_sub(BOTTOM_PADDING)
+    }
+
+    /// Lines `start..=end` joined with newlines, clamped to the content
+    pub fn text_range(&self, start: usize, end: usize) -> String {
+        let end = end.min(self.content.len().saturating_sub(1));
+        self.content
+            .get(start..=end)
+            .map(|lines| lines.join("\n"))
+            .unwrap_or_default()
+    }
+
+    /// Add `text` to the end of the page under a `────── title ──────`
+    /// divider, returning the divider's line
+    pub fn append_section(&mut self, title: &str, text: &str) -> usize {
+        let end = self.content.len().saturating_sub(BOTTOM_PADDING);
+        let divider = end + 1;
+        let mut added = vec![String::new(), section_divider(title)];
+        added.extend(text.lines().map(str::to_string));
+        self.content.splice(end..end, added);
+
+        self.sections = detect_sections(&self.content);
+        if let Some(query) = self.search_query.clone() {
+            self.set_search(&query);
+        }
+        divider
+    }
+
+    /// Draw content lines bold and underlined, from raw `man` lines matched
+    /// by `match_man_formatting`
+    pub fn set_man_formatting(&mut self, formatting: HashMap<usize, String>) {
+        self.man_formatting = formatting;
+    }
+
+    /// First word on line `line_idx` for `*`: the first whitespace-separated
+    /// token that isn't a flag, with surrounding punctuation removed
+    pub fn word_at_line(&self, line_idx: usize) -> Option<String> {
+        self.content
+            .get(line_idx)?
+            .split_whitespace()
+            .filter(|token| !token.starts_with('-'))
+            .map(|token| token.trim_matches(|c: char| !c.is_alphanumeric()))
+            .find(|word| !word.is_empty())
+            .map(str::to_string)
+    }
+
+    /// Line of the divider added by `append_section(title, ..)`, if any
+    pub fn appended_section(&self, title: &str) -> Option<usize> {
+        let divider = section_divider(title);
+        self.content.iter().position(|line| *line == divider)
+    }
+
+    /// Size of the page text, excluding the bottom padding
+    pub fn statistics(&self) -> ContentStats {
+        let end = self.content.len().saturating_sub(BOTTOM_PADDING);
+        let lines = &self.content[..end];
+        ContentStats {
+            lines: lines.len(),
+            words: lines.iter().map(|l| l.split_whitespace().count()).sum(),
+            characters: lines.iter().map(|l| l.chars().count()).sum(),
+            sections: self.sections.iter().filter(|&&h| h < end).count(),
+        }
+    }
+
+    /// Words in `content[start..end]`, skipping lines with no letters or digits
+    /// (rules, separators, punctuation)
+    pub fn words_in_range(&self, start: usize, end: usize) -> usize {
+        let end = end.min(self.content.len());
+        self.content
+            .get(start..end)
+            .unwrap_or_default()
+            .iter()
+            .map(|line| line_words(line))
+            .sum()
+    }
+
+    /// Words on the `viewport_height` rows from the scroll position. Lines
+    /// hidden in a fold aren't counted.
+    pub fn words_visible(&self, viewport_height: usize) -> usize {
+        let rows = self.rows();
+        rows.iter()
+            .skip(row_of(&rows, self.scroll))
+            .take(viewport_height)
+            .map(|&row| match row {
+                Row::Line(line) => line_words(&self.content[line]),
+                Row::Fold { .. } => 0,
+            })
+            .sum()
+    }
+
+    /// Content lines with hard tabs expanded to `tab_width`-column tab stops
+    pub fn expand_tabs(&self, tab_width: usize) -> Vec<String> {
+        self.content
+            .iter()
+            .map(|line| expand_tabs(line, tab_width).into_owned())
+            .collect()
+    }
+
     pub fn scroll_percentage(&self, viewport_height: usize) -> u16 {
-        if self.content.len() <= viewport_height {
+        let rows = self.rows();
+        if rows.len() <= viewport_height {
             return 100;
         }
 
-        let max_scroll = self.content.len() - viewport_height;
-        ((self.scroll as f64 / max_scroll as f64) * 100.0) as u16
+        let max_scroll = rows.len() - viewport_height;
+        ((row_of(&rows, self.scroll) as f64 / max_scroll as f64) * 100.0) as u16
+    }
+
+    /// Line diff from this page to `other`. Lines are compared with surrounding
+    /// whitespace ignored, since man and `--help` indent differently.
+    pub fn diff(&self, other: &Pager) -> DiffView {
+        let end = self.content.len().saturating_sub(BOTTOM_PADDING);
+        let other_end = other.content.len().saturating_sub(BOTTOM_PADDING);
+        let mut lines = Vec::new();
+        diff_lines(
+            &self.content[..end],
+            &other.content[..other_end],
+            &mut lines,
+        );
+        DiffView { lines, scroll: 0 }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DiffLine {
+    Same(String),
+    Added(String),
+    Removed(String),
+}
+
+/// A unified line diff between two pages, with its own scroll position
+#[derive(Debug, Clone)]
+pub struct DiffView {
+    pub lines: Vec<DiffLine>,
+    pub scroll: usize,
+}
+
+impl DiffView {
+    pub fn scroll_down(&mut self, amount: usize) {
+        self.scroll = self.scroll.saturating_add(amount);
+    }
+
+    pub fn scroll_up(&mut self, amount: usize) {
+        self.scroll = self.scroll.saturating_sub(amount);
+    }
+
+    pub fn clamp_scroll(&mut self, viewport_height: usize) {
+        self.scroll = self
+            .scroll
+            .min(self.lines.len().saturating_sub(viewport_height));
+    }
+
+    /// Number of added and removed lines
+    pub fn counts(&self) -> (usize, usize) {
+        self.lines
+            .iter()
+            .fold((0, 0), |(added, removed), line| match line {
+                DiffLine::Added(_) => (added + 1, removed),
+                DiffLine::Removed(_) => (added, removed + 1),
+                DiffLine::Same(_) => (added, removed),
+            })
+    }
+}
+
+/// Append the diff of `a` to `b` to `out`, using Hirschberg's LCS so memory
+/// stays linear for long man pages
+fn diff_lines(a: &[String], b: &[String], out: &mut Vec<DiffLine>) {
+    let same = |x: &String, y: &String| x.trim() == y.trim();
+
+    if a.is_empty() {
+        out.extend(b.iter().cloned().map(DiffLine::Added));
+        return;
+    }
+    if b.is_empty() {
+        out.extend(a.iter().cloned().map(DiffLine::Removed));
+        return;
+    }
+    if a.len() == 1 {
+        match b.iter().position(|line| same(&a[0], line)) {
+            Some(j) => {
+                out.extend(b[..j].iter().cloned().map(DiffLine::Added));
+                out.push(DiffLine::Same(a[0].clone()));
+                out.extend(b[j + 1..].iter().cloned().map(DiffLine::Added));
+            }
+            None => {
+                out.push(DiffLine::Removed(a[0].clone()));
+                out.extend(b.iter().cloned().map(DiffLine::Added));
+            }
+        }
+        return;
+    }
+
+    let mid = a.len() / 2;
+    let forward = lcs_row(a[..mid].iter(), b.iter().collect(), same);
+    let backward = lcs_row(a[mid..].iter().rev(), b.iter().rev().collect(), same);
+    let split = (0..=b.len())
+        .max_by_key(|&j| forward[j] + backward[b.len() - j])
+        .unwrap_or(0);
+
+    diff_lines(&a[..mid], &b[..split], out);
+    diff_lines(&a[mid..], &b[split..], out);
+}
+
+/// Last row of the LCS length table: entry `j` is the LCS of all of `a` and `b[..j]`
+fn lcs_row<'a>(
+    a: impl Iterator<Item = &'a String>,
+    b: Vec<&'a String>,
+    same: impl Fn(&String, &String) -> bool,
+) -> Vec<usize> {
+    let mut row = vec![0; b.len() + 1];
+    for x in a {
+        let mut diagonal = 0;
+        for (j, y) in b.iter().enumerate() {
+            let above = row[j + 1];
+            row[j + 1] = if same(x, y) {
+                diagonal + 1
+            } else {
+                above.max(row[j])
+            };
+            diagonal = above;
+        }
+    }
+    row
+}
+
+/// Full-screen diff with a one-line summary bar
+pub struct DiffWidget<'a> {
+    diff: &'a DiffView,
+    title: &'a str,
+}
+
+impl<'a> DiffWidget<'a> {
+    pub fn new(diff: &'a DiffView, title: &'a str) -> Self {
+        Self { diff, title }
+    }
+}
+
+impl Widget for DiffWidget<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let chunks = Layout::vertical([Constraint::Min(1), Constraint::Length(1)]).split(area);
+        let content_area = chunks[0];
+        let status_area = chunks[1];
+
+        let lines: Vec<Line> = self
+            .diff
+            .lines
+            .iter()
+            .skip(self.diff.scroll)
+            .take(content_area.height as usize)
+            .map(|line| match line {
+                DiffLine::Same(text) => Line::raw(format!("  {}", text)),
+                DiffLine::Added(text) => {
+                    Line::styled(format!("+ {}", text), Style::default().fg(Color::Green))
+                }
+                DiffLine::Removed(text) => {
+                    Line::styled(format!("- {}", text), Style::default().fg(Color::Red))
+                }
+            })
+            .collect();
+        Paragraph::new(lines).render(content_area, buf);
+
+        let (added, removed) = self.diff.counts();
+        let status = format!(
+            " {} │ +{} -{} │ [Alt-D] close diff ",
+            self.title, added, removed
+        );
+        let style = Style::default().bg(Color::DarkGray).fg(Color::White);
+        for x in status_area.left()..status_area.right() {
+            buf[(x, status_area.y)].set_style(style);
+            buf[(x, status_area.y)].set_char(' ');
+        }
+        buf.set_span(
+            status_area.x,
+            status_area.y,
+            &Span::styled(status, style),
+            status_area.width,
+        );
     }
 }
 
@@ -131,8 +660,20 @@ pub struct PagerWidget<'a> {
     breadcrumb: &'a str,
     subcommand_count: usize,
     content_source: ContentSource,
+    show_scrollbar: bool,
+    recording: Option<&'a str>,
+    selection: Option<(usize, usize)>,
+    tab_width: usize,
+    parse_method: ParseMethod,
+    smart_wrap: bool,
+    callout_highlighting: bool,
+    cursor_line: bool,
+    show_word_count: bool,
 }
 
+/// Background of the cursor line, the first visible line
+const CURSOR_LINE_BG: Color = Color::Indexed(235);
+
 impl<'a> PagerWidget<'a> {
     pub fn new(
         pager: &'a Pager,
@@ -145,8 +686,71 @@ impl<'a> PagerWidget<'a> {
             breadcrumb,
             subcommand_count,
             content_source,
+            show_scrollbar: true,
+            recording: None,
+            selection: None,
+            tab_width: 4,
+            parse_method: ParseMethod::None,
+            smart_wrap: true,
+            callout_highlighting: true,
+            cursor_line: false,
+            show_word_count: false,
         }
     }
+
+    pub fn show_scrollbar(mut self, show: bool) -> Self {
+        self.show_scrollbar = show;
+        self
+    }
+
+    /// Highlight lines `start..=end` as a visual-line selection
+    pub fn selection(mut self, selection: Option<(usize, usize)>) -> Self {
+        self.selection = selection;
+        self
+    }
+
+    /// Indent wrapped continuations of indented lines to match the first row
+    pub fn smart_wrap(mut self, smart_wrap: bool) -> Self {
+        self.smart_wrap = smart_wrap;
+        self
+    }
+
+    /// Give `NOTE:`/`WARNING:`-style lines a colored background
+    pub fn callout_highlighting(mut self, enabled: bool) -> Self {
+        self.callout_highlighting = enabled;
+        self
+    }
+
+    /// Shade the first visible line as a position marker, as `less` does
+    pub fn cursor_line(mut self, enabled: bool) -> Self {
+        self.cursor_line = enabled;
+        self
+    }
+
+    /// Columns between tab stops when expanding hard tabs
+    pub fn tab_width(mut self, tab_width: usize) -> Self {
+        self.tab_width = tab_width;
+        self
+    }
+
+    /// Parsing strategy shown next to the subcommand count
+    pub fn parse_method(mut self, parse_method: ParseMethod) -> Self {
+        self.parse_method = parse_method;
+        self
+    }
+
+    /// Show a macro recording indicator in the status bar, naming the key
+    /// that plays the macro back
+    pub fn recording(mut self, recording: Option<&'a str>) -> Self {
+        self.recording = recording;
+        self
+    }
+
+    /// Show `~N words` for the visible rows in the status bar
+    pub fn word_count(mut self, enabled: bool) -> Self {
+        self.show_word_count = enabled;
+        self
+    }
 }
 
 impl Widget for PagerWidget<'_> {
@@ -156,34 +760,99 @@ impl Widget for PagerWidget<'_> {
 
         let chunks = Layout::vertical([Constraint::Min(1), Constraint::Length(1)]).split(area);
 
-        let content_area = chunks[0];
+        let (content_area, scrollbar_area) = if self.show_scrollbar {
+            let cols =
+                Layout::horizontal([Constraint::Min(1), Constraint::Length(1)]).split(chunks[0]);
+            (cols[0], Some(cols[1]))
+        } else {
+            (chunks[0], None)
+        };
         let status_area = chunks[1];
 
         // Render content
         let viewport_height = content_area.height as usize;
-        let visible_lines: Vec<Line> = self
-            .pager
-            .content
-            .iter()
-            .enumerate()
-            .skip(self.pager.scroll)
-            .take(viewport_height)
-            .map(|(line_num, line)| {
-                let is_match_line = self.pager.search_matches.contains(&line_num);
-                let is_current_match = !self.pager.search_matches.is_empty()
-                    && self.pager.search_matches.get(self.pager.current_match) == Some(&line_num);
+        let render_line = |line_num: usize, line: &str| -> Line {
+            let line = expand_tabs(line, self.tab_width);
+            let line = line.as_ref();
+            let is_match_line = self.pager.search_matches.contains(&line_num);
+            let is_current_match = !self.pager.search_matches.is_empty()
+                && self.pager.search_matches.get(self.pager.current_match) == Some(&line_num);
 
-                if let Some(ref query) = self.pager.search_query {
-                    highlight_line(line, query, is_match_line, is_current_match)
-                } else {
-                    Line::raw(line.as_str())
+            let man_formatting = match self.content_source {
+                // Raw lines aren't tab-expanded, so those keep the plain render
+                ContentSource::Man if !is_match_line => self
+                    .pager
+                    .man_formatting
+                    .get(&line_num)
+                    .filter(|raw| !raw.contains('\t')),
+                _ => None,
+            };
+            let rendered = if let Some(raw) = man_formatting {
+                Line::from(parse_man_decorations(raw))
+            } else if let Some(ref query) = self.pager.search_query {
+                highlight_line(
+                    line,
+                    query,
+                    self.pager.case_sensitive,
+                    is_match_line,
+                    is_current_match,
+                )
+            } else {
+                Line::raw(line.to_string())
+            };
+            // Line-level style, so search highlights on the line still show
+            let rendered = match classify_callout(line) {
+                Some(kind) if self.callout_highlighting => rendered.style(kind.style()),
+                _ => rendered,
+            };
+
+            match self.selection {
+                Some((start, end)) if (start..=end).contains(&line_num) => {
+                    rendered.patch_style(Style::default().bg(Color::Blue))
                 }
-            })
-            .collect();
+                // Search matches keep their own highlighting
+                _ if self.cursor_line && line_num == self.pager.scroll && !is_match_line => {
+                    rendered.patch_style(Style::default().bg(CURSOR_LINE_BG))
+                }
+                _ => rendered,
+            }
+        };
+
+        let visible_lines: Vec<Line> = if self.pager.showing_context() {
+            context_view(self.pager, viewport_height, self.tab_width, render_line)
+        } else {
+            let rows = self.pager.rows();
+            let fold_style = Style::default().fg(Color::DarkGray);
+            rows.iter()
+                .skip(row_of(&rows, self.pager.scroll))
+                .take(viewport_height)
+                .map(|&row| match row {
+                    Row::Line(line_num) => render_line(line_num, &self.pager.content[line_num]),
+                    Row::Fold { heading, hidden } => Line::styled(
+                        fold_placeholder(&self.pager.content[heading], hidden),
+                        fold_style,
+                    ),
+                })
+                .collect()
+        };
+
+        let visible_lines = if self.smart_wrap {
+            let width = content_area.width as usize;
+            visible_lines
+                .into_iter()
+                .flat_map(|line| smart_wrap(line, width))
+                .collect()
+        } else {
+            visible_lines
+        };
 
         let content = Paragraph::new(visible_lines).wrap(Wrap { trim: false });
         content.render(content_area, buf);
 
+        if let Some(scrollbar_area) = scrollbar_area {
+            render_scrollbar(scrollbar_area, buf, self.pager, viewport_height);
+        }
+
         // Render status bar
         render_status_bar(
             status_area,
@@ -195,13 +864,373 @@ impl Widget for PagerWidget<'_> {
             self.pager.current_match_index(),
             self.pager.scroll_percentage(viewport_height),
             self.content_source,
+            self.parse_method,
+            self.recording,
+            self.selection.is_some(),
+            self.show_word_count
+                .then(|| self.pager.words_visible(viewport_height)),
         );
     }
 }
 
+/// Build a grep-like view: matches as usual, context lines dimmed, and each run
+/// of other lines collapsed into a single `...` placeholder
+fn context_view<'a>(
+    pager: &Pager,
+    viewport_height: usize,
+    tab_width: usize,
+    render_line: impl Fn(usize, &str) -> Line<'a>,
+) -> Vec<Line<'a>> {
+    let context_style = Style::default().fg(Color::DarkGray);
+    let mut lines = Vec::with_capacity(viewport_height);
+    let mut in_gap = false;
+
+    for (line_num, line) in pager.content.iter().enumerate().skip(pager.scroll) {
+        if lines.len() >= viewport_height {
+            break;
+        }
+
+        if pager.search_matches.contains(&line_num) {
+            lines.push(render_line(line_num, line));
+            in_gap = false;
+        } else if pager.context_lines.contains(&line_num) {
+            lines.push(Line::styled(
+                expand_tabs(line, tab_width).into_owned(),
+                context_style,
+            ));
+            in_gap = false;
+        } else if !in_gap {
+            lines.push(Line::styled("...", context_style));
+            in_gap = true;
+        }
+    }
+
+    lines
+}
+
+/// Whitespace-separated words on a line, or 0 for a line of only
+/// punctuation and separators
+fn line_words(line: &str) -> usize {
+    if line.chars().any(char::is_alphanumeric) {
+        line.split_whitespace().count()
+    } else {
+        0
+    }
+}
+
+/// Index of the row displaying `line`
+fn row_of(rows: &[Row], line: usize) -> usize {
+    rows.partition_point(|r| r.first_line() <= line)
+        .saturating_sub(1)
+}
+
+/// Match raw `man` output (see `fetch_man_formatting`) to the content `lines`
+/// it renders as, keyed by line number. Only lines with bold or underline are
+/// kept, and raw lines whose text no longer appears, e.g. stripped noise, are
+/// skipped.
+pub fn match_man_formatting(lines: &[String], raw: &str) -> HashMap<usize, String> {
+    let raw_lines: Vec<&str> = raw.lines().collect();
+    let mut next = 0;
+    let mut formatting = HashMap::new();
+    for (line_num, line) in lines.iter().enumerate() {
+        let Some(offset) = raw_lines[next..]
+            .iter()
+            .position(|raw_line| strip_man_formatting(raw_line) == *line)
+        else {
+            continue;
+        };
+        let raw_line = raw_lines[next + offset];
+        next += offset + 1;
+        if raw_line.contains(['\x08', '\x1b']) {
+            formatting.insert(line_num, raw_line.to_string());
+        }
+    }
+    formatting
+}
+
+/// Heading lines of a help page: unindented `Title:` lines (`Options:`,
+/// `Available Commands:`) and man-style all-caps headers (`DESCRIPTION`)
+pub fn detect_sections(lines: &[String]) -> Vec<usize> {
+    lines
+        .iter()
+        .enumerate()
+        .filter(|(_, line)| is_section_heading(line))
+        .map(|(i, _)| i)
+        .collect()
+}
+
+fn is_section_heading(line: &str) -> bool {
+    let line = line.trim_end();
+    if line.is_empty() || line.starts_with(char::is_whitespace) {
+        return false;
+    }
+
+    if let Some(title) = line.strip_suffix(':') {
+        return !title.contains(':') && title.split_whitespace().count() <= 4;
+    }
+
+    line.chars().any(char::is_alphabetic)
+        && line
+            .chars()
+            .all(|c| c.is_uppercase() || c == ' ' || c == '-' || c == '_')
+}
+
+/// Placeholder shown in place of a folded section's body
+fn fold_placeholder(heading: &str, hidden: usize) -> String {
+    let name = heading.trim().trim_end_matches(':');
+    match hidden {
+        1 => format!("  ↓ {} (1 line hidden)", name),
+        n => format!("  ↓ {} ({} lines hidden)", name, n),
+    }
+}
+
+/// Word-wrap an indented line to `width` columns, indenting continuation rows
+/// to match so wrapped option descriptions don't read as new entries. Lines
+/// without indentation, or that already fit, are left for `Paragraph` to wrap.
+fn smart_wrap(line: Line<'_>, width: usize) -> Vec<Line<'_>> {
+    let chars: Vec<(char, Style)> = line
+        .spans
+        .iter()
+        .flat_map(|span| span.content.chars().map(move |c| (c, span.style)))
+        .collect();
+    let indent = chars.iter().take_while(|(c, _)| *c == ' ').count();
+    if indent == 0 || chars.len() <= width || indent * 2 >= width {
+        return vec![line];
+    }
+
+    let mut rows = Vec::new();
+    let mut start = 0;
+    while start < chars.len() {
+        let first = rows.is_empty();
+        let available = if first { width } else { width - indent };
+        let rest = &chars[start..];
+
+        let end = if rest.len() <= available {
+            rest.len()
+        } else {
+            // Break at the last space that fits, or hard-break a word too long for the row
+            let min_break = if first { indent } else { 0 };
+            rest[..=available]
+                .iter()
+                .rposition(|(c, _)| *c == ' ')
+                .filter(|&i| i > min_break)
+                .unwrap_or(available)
+        };
+
+        let mut spans = Vec::new();
+        if !first {
+            spans.push(Span::raw(" ".repeat(indent)));
+        }
+        spans.extend(group_spans(&rest[..end]));
+        rows.push(Line::from(spans).style(line.style));
+
+        start += end;
+        while chars.get(start).is_some_and(|(c, _)| *c == ' ') {
+            start += 1;
+        }
+    }
+    rows
+}
+
+/// Rebuild spans from styled characters, merging runs that share a style
+fn group_spans(chars: &[(char, Style)]) -> Vec<Span<'static>> {
+    let mut spans: Vec<Span<'static>> = Vec::new();
+    for &(c, style) in chars {
+        match spans.last_mut() {
+            Some(span) if span.style == style => span.content.to_mut().push(c),
+            _ => spans.push(Span::styled(c.to_string(), style)),
+        }
+    }
+    spans
+}
+
+/// Replace hard tabs with spaces up to the next multiple of `tab_width` columns.
+/// A width of 0 leaves the line untouched.
+fn expand_tabs(line: &str, tab_width: usize) -> Cow<'_, str> {
+    if tab_width == 0 || !line.contains('\t') {
+        return Cow::Borrowed(line);
+    }
+
+    let mut expanded = String::with_capacity(line.len() + tab_width);
+    let mut column = 0;
+    for c in line.chars() {
+        if c == '\t' {
+            let spaces = tab_width - column % tab_width;
+            expanded.extend(std::iter::repeat_n(' ', spaces));
+            column += spaces;
+        } else {
+            expanded.push(c);
+            column += 1;
+        }
+    }
+    Cow::Owned(expanded)
+}
+
+/// Spans for a raw man page line: `c\x08c` overstrikes become bold and
+/// `_\x08c` underlined, as do ANSI bold (1) and underline (4) sequences
+pub fn parse_man_decorations(line: &str) -> Vec<Span<'static>> {
+    let bold = Modifier::BOLD;
+    let underlined = Modifier::UNDERLINED;
+    let mut chars: Vec<(char, Style)> = Vec::new();
+    let mut sgr = Modifier::empty();
+    let mut iter = line.chars().peekable();
+
+    while let Some(mut c) = iter.next() {
+        if c == '\x1b' {
+            if iter.peek() == Some(&'[') {
+                iter.next();
+                let mut params = String::new();
+                for nc in iter.by_ref() {
+                    if nc.is_ascii_alphabetic() {
+                        if nc == 'm' {
+                            sgr = apply_sgr(sgr, &params);
+                        }
+                        break;
+                    }
+                    params.push(nc);
+                }
+            }
+            continue;
+        }
+
+        let mut modifier = sgr;
+        while iter.peek() == Some(&'\x08') {
+            iter.next();
+            let Some(next) = iter.next() else { break };
+            if next == c {
+                modifier |= bold;
+            } else if c == '_' {
+                modifier |= underlined;
+            }
+            // Other overstrikes keep the last character, like `strip_man_formatting`
+            c = next;
+        }
+        chars.push((c, Style::default().add_modifier(modifier)));
+    }
+    group_spans(&chars)
+}
+
+/// Bold and underline state after the ANSI SGR parameters `params` (e.g. `1;4`)
+fn apply_sgr(mut modifier: Modifier, params: &str) -> Modifier {
+    for param in params.split(';') {
+        match param {
+            "" | "0" => modifier = Modifier::empty(),
+            "1" => modifier |= Modifier::BOLD,
+            "4" => modifier |= Modifier::UNDERLINED,
+            "22" => modifier -= Modifier::BOLD,
+            "24" => modifier -= Modifier::UNDERLINED,
+            _ => {}
+        }
+    }
+    modifier
+}
+
+/// Compute the scrollbar thumb as `(start_row, length)` within a track of `track_height` rows
+fn scrollbar_thumb(
+    total_lines: usize,
+    viewport_height: usize,
+    scroll: usize,
+    track_height: usize,
+) -> (usize, usize) {
+    if total_lines <= viewport_height || total_lines == 0 {
+        return (0, track_height);
+    }
+
+    let length = (viewport_height * track_height / total_lines).clamp(1, track_height);
+    let start = (scroll * track_height / total_lines).min(track_height - length);
+    (start, length)
+}
+
+/// Draw a 1-column scrollbar: thumb `█` over a `│` track, with search matches marked `▪`
+fn render_scrollbar(area: Rect, buf: &mut Buffer, pager: &Pager, viewport_height: usize) {
+    let track_height = area.height as usize;
+    if track_height == 0 {
+        return;
+    }
+
+    let rows = pager.rows();
+    let total_lines = rows.len();
+    let (thumb_start, thumb_len) = scrollbar_thumb(
+        total_lines,
+        viewport_height,
+        row_of(&rows, pager.scroll),
+        track_height,
+    );
+
+    let track_style = Style::default().fg(Color::DarkGray);
+    let thumb_style = Style::default().fg(Color::Gray);
+    for row in 0..track_height {
+        let cell = &mut buf[(area.x, area.y + row as u16)];
+        if (thumb_start..thumb_start + thumb_len).contains(&row) {
+            cell.set_char('█').set_style(thumb_style);
+        } else {
+            cell.set_char('│').set_style(track_style);
+        }
+    }
+
+    if total_lines == 0 {
+        return;
+    }
+    let marker_style = Style::default().fg(Color::Yellow);
+    for &line in &pager.search_matches {
+        let row = (row_of(&rows, line) * track_height / total_lines).min(track_height - 1);
+        buf[(area.x, area.y + row as u16)]
+            .set_char('▪')
+            .set_style(marker_style);
+    }
+}
+
+/// Kinds of inline notice recognized by `classify_callout`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CalloutKind {
+    Note,
+    Warning,
+    Caution,
+    Deprecated,
+    Important,
+    Tip,
+}
+
+impl CalloutKind {
+    const PREFIXES: [(&'static str, CalloutKind); 6] = [
+        ("note:", CalloutKind::Note),
+        ("warning:", CalloutKind::Warning),
+        ("caution:", CalloutKind::Caution),
+        ("deprecated:", CalloutKind::Deprecated),
+        ("important:", CalloutKind::Important),
+        ("tip:", CalloutKind::Tip),
+    ];
+
+    fn style(self) -> Style {
+        let (bg, fg) = match self {
+            CalloutKind::Note => (Color::Yellow, Color::Black),
+            CalloutKind::Warning => (Color::Red, Color::White),
+            CalloutKind::Caution => (Color::Indexed(208), Color::Black), // orange
+            CalloutKind::Deprecated => (Color::DarkGray, Color::White),
+            CalloutKind::Important => (Color::Magenta, Color::White),
+            CalloutKind::Tip => (Color::Green, Color::Black),
+        };
+        Style::default().bg(bg).fg(fg)
+    }
+}
+
+/// The callout a line starts with, ignoring indentation and case
+pub fn classify_callout(line: &str) -> Option<CalloutKind> {
+    let trimmed = line.trim_start();
+    CalloutKind::PREFIXES
+        .iter()
+        .find(|(prefix, _)| {
+            trimmed
+                .get(..prefix.len())
+                .is_some_and(|start| start.eq_ignore_ascii_case(prefix))
+        })
+        .map(|&(_, kind)| kind)
+}
+
 fn highlight_line(
     line: &str,
     query: &str,
+    case_sensitive: bool,
     is_match_line: bool,
     is_current_match: bool,
 ) -> Line<'static> {
@@ -210,11 +1239,14 @@ fn highlight_line(
     }
 
     let mut spans = Vec::new();
-    let lower_line = line.to_lowercase();
-    let lower_query = query.to_lowercase();
+    let (haystack, needle) = if case_sensitive {
+        (line.to_string(), query.to_string())
+    } else {
+        (line.to_lowercase(), query.to_lowercase())
+    };
     let mut last_end = 0;
 
-    for (start, _) in lower_line.match_indices(&lower_query) {
+    for (start, _) in haystack.match_indices(&needle) {
         if start > last_end {
             spans.push(Span::raw(line[last_end..start].to_string()));
         }
@@ -253,6 +1285,10 @@ fn render_status_bar(
     current_match: usize,
     scroll_pct: u16,
     content_source: ContentSource,
+    parse_method: ParseMethod,
+    recording: Option<&str>,
+    visual_mode: bool,
+    word_count: Option<usize>,
 ) {
     let status_style = Style::default().bg(Color::DarkGray).fg(Color::White);
 
@@ -263,17 +1299,39 @@ fn render_status_bar(
     }
 
     // Left: breadcrumb + source indicator
-    let source_indicator = match content_source {
-        ContentSource::Man => " [man]",
-        ContentSource::Help => "",
+    let source_indicator = match &content_source {
+        ContentSource::Help | ContentSource::Cache { .. } => String::new(),
+        source => format!(" [{}]", source),
     };
-    let left_text = format!(" {}{} ", breadcrumb, source_indicator);
+    let mut left_text = format!(" {}{} ", breadcrumb, source_indicator);
     let breadcrumb_span = Span::styled(&left_text, status_style);
     buf.set_span(area.x, area.y, &breadcrumb_span, left_text.len() as u16);
 
+    // A cached page is de-emphasized rather than flagged like an error
+    if let ContentSource::Cache { .. } = content_source {
+        let cache_text = format!("[{}] ", content_source);
+        let cache_style = status_style.fg(Color::Gray).add_modifier(Modifier::DIM);
+        let x = area.x + left_text.len() as u16;
+        buf.set_span(
+            x,
+            area.y,
+            &Span::styled(&cache_text, cache_style),
+            cache_text.len() as u16,
+        );
+        left_text.push_str(&cache_text);
+    }
+
     // Build right side info
     let mut right_parts = Vec::new();
 
+    if visual_mode {
+        right_parts.push("-- VISUAL LINE --".to_string());
+    }
+
+    if let Some(play_key) = recording {
+        right_parts.push(format!("recording {}", play_key));
+    }
+
     if let Some(query) = search_query {
         if match_count > 0 {
             right_parts.push(format!(
@@ -288,7 +1346,15 @@ fn render_status_bar(
     }
 
     if subcommand_count > 0 {
-        right_parts.push(format!("[f] {} subcmds", subcommand_count));
+        right_parts.push(format!(
+            "[f] {} subcmds ({})",
+            subcommand_count,
+            parse_method.label()
+        ));
+    }
+
+    if let Some(words) = word_count {
+        right_parts.push(format!("~{} words", words));
     }
 
     right_parts.push(format!("{}%", scroll_pct));
@@ -305,11 +1371,29 @@ fn render_status_bar(
 
 pub struct SearchInput<'a> {
     query: &'a str,
+    prompt: &'a str,
+    case_sensitive: bool,
 }
 
 impl<'a> SearchInput<'a> {
     pub fn new(query: &'a str) -> Self {
-        Self { query }
+        Self {
+            query,
+            prompt: "/",
+            case_sensitive: false,
+        }
+    }
+
+    /// Text shown before the input, `/` by default
+    pub fn prompt(mut self, prompt: &'a str) -> Self {
+        self.prompt = prompt;
+        self
+    }
+
+    /// Show a `(case)` indicator on the right when searching case-sensitively
+    pub fn case_sensitive(mut self, case_sensitive: bool) -> Self {
+        self.case_sensitive = case_sensitive;
+        self
     }
 }
 
@@ -323,9 +1407,15 @@ impl Widget for SearchInput<'_> {
             buf[(x, area.y)].set_char(' ');
         }
 
-        let prompt = format!("/{}", self.query);
+        let prompt = format!("{}{}", self.prompt, self.query);
         let span = Span::styled(prompt, style);
         buf.set_span(area.x, area.y, &span, area.width);
+
+        if self.case_sensitive {
+            let indicator = "(case) ";
+            let x = area.right().saturating_sub(indicator.len() as u16);
+            buf.set_string(x, area.y, indicator, style.fg(Color::Yellow));
+        }
     }
 }
 
@@ -348,6 +1438,7 @@ impl Widget for HelpOverlay {
 
   Search:
     /            Start search
+    Ctrl-c       Toggle case-sensitive search (while typing)
     n            Next match
     N            Previous match
     Escape       Clear search
@@ -355,22 +1446,59 @@ impl Widget for HelpOverlay {
   Subcommands:
     f            Open subcommand finder
     Enter        Select subcommand
+    Ctrl-s       Cycle finder sort (score/alpha/category)
+    Ctrl-x       Export finder results (TSV)
+    Tab          Next label group in finder (Shift-Tab: previous)
+    Ctrl-g       Collapse/expand finder label group
     Backspace    Go back to parent
+    P            Jump to the base command (e.g. git)
     o            Open different command
+    Ctrl-d       Remove entry from command history
+    U            Open URL of last finder selection
+
+  Folding:
+    za           Fold/unfold current section
+    zM           Fold all sections
+    zR           Unfold all sections
+
+  Visual line:
+    V            Start selecting lines
+    y            Copy selection to clipboard
+    q, Escape    Cancel selection
+
+  Macros:
+    Q            Start/stop recording
+    @q           Play back recorded macro
 
   General:
+    r            Reload the page from the same source
+    F5           Switch --help output between stdout and stderr
+    Alt-M        Switch between --help and the man page
+    Ctrl-i, Tab  Show page statistics
+    Ctrl-e       Save a session script for this page
+    ms           Save a named snapshot (helpv --restore NAME)
+    yc           Copy the current command (shell-quoted)
+    Alt-D        Diff --help against the man page
+    E            Append examples to the page
+    :help NAME   Show the keys for an action (e.g. :help search)
+    :clear-history  Forget back history and recent commands
+    :e config    Edit the config file (:e toolpack NAME for a tool pack)
+    *            Search for the first word on the top line
+    Ctrl-l       Redraw the screen
+    Ctrl-z       Suspend (fg to return)
     ?            Show this help
     q, Escape    Quit / Close overlay
 "#;
 
         let lines: Vec<&str> = help_text.lines().collect();
-        let height = lines.len().min(area.height as usize);
+        // Leave room for the border; the text is cut off on short terminals
+        let height = lines.len().min(area.height.saturating_sub(2) as usize);
         let width = lines
             .iter()
             .map(|l| l.len())
             .max()
             .unwrap_or(40)
-            .min(area.width as usize - 4);
+            .min(area.width.saturating_sub(4) as usize);
 
         let x = area.x + (area.width.saturating_sub(width as u16 + 4)) / 2;
         let y = area.y + (area.height.saturating_sub(height as u16 + 2)) / 2;
@@ -399,3 +1527,852 @@ impl Widget for HelpOverlay {
         }
     }
 }
+
+/// Page statistics box; `row` adds facts the pager doesn't know about
+pub struct StatsOverlay {
+    rows: Vec<(&'static str, String)>,
+}
+
+impl StatsOverlay {
+    pub fn new(stats: &ContentStats) -> Self {
+        Self {
+            rows: vec![
+                ("Lines", stats.lines.to_string()),
+                ("Words", stats.words.to_string()),
+                ("Characters", stats.characters.to_string()),
+                ("Sections", stats.sections.to_string()),
+            ],
+        }
+    }
+
+    pub fn row(mut self, label: &'static str, value: impl Into<String>) -> Self {
+        self.rows.push((label, value.into()));
+        self
+    }
+
+    fn lines(&self) -> Vec<String> {
+        let label_width = self.rows.iter().map(|(l, _)| l.len()).max().unwrap_or(0) + 1;
+        self.rows
+            .iter()
+            .map(|(label, value)| {
+                format!(
+                    "{:<width$} {}",
+                    format!("{}:", label),
+                    value,
+                    width = label_width
+                )
+            })
+            .collect()
+    }
+}
+
+impl Widget for StatsOverlay {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let lines = self.lines();
+        let width = lines.iter().map(|l| l.chars().count()).max().unwrap_or(0) as u16 + 4;
+        let height = lines.len() as u16 + 2;
+        let overlay_area = Rect::new(
+            area.x + area.width.saturating_sub(width) / 2,
+            area.y + area.height.saturating_sub(height) / 2,
+            width.min(area.width),
+            height.min(area.height),
+        );
+
+        Clear.render(overlay_area, buf);
+        let block = Block::default()
+            .title(" Statistics ")
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Cyan))
+            .style(Style::default().bg(Color::Black));
+        let inner = block.inner(overlay_area);
+        block.render(overlay_area, buf);
+
+        for (i, line) in lines.iter().take(inner.height as usize).enumerate() {
+            let span = Span::styled(format!(" {}", line), Style::default().fg(Color::White));
+            buf.set_span(inner.x, inner.y + i as u16, &span, inner.width);
+        }
+    }
+}
+
+/// Divider line that `Pager::append_section` puts above added text
+fn section_divider(title: &str) -> String {
+    format!("────── {} ──────", title)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn numbered_pager(lines: usize) -> Pager {
+        let content = (0..lines)
+            .map(|i| format!("line {i}"))
+            .collect::<Vec<_>>()
+            .join("\n");
+        Pager::new(content)
+    }
+
+    #[test]
+    fn text_round_trips_without_padding() {
+        let pager = Pager::new("line one\nline two".to_string());
+        assert_eq!(pager.as_plain_text(), "line one\nline two");
+        assert_eq!(Pager::new(pager.as_plain_text()).content, pager.content);
+    }
+
+    #[test]
+    fn visible_text_follows_scroll() {
+        let mut pager = numbered_pager(20);
+        pager.scroll = 5;
+        assert_eq!(pager.visible_as_plain_text(3), "line 5\nline 6\nline 7");
+    }
+
+    #[test]
+    fn visible_text_stops_before_padding() {
+        let mut pager = numbered_pager(4);
+        pager.scroll = 2;
+        assert_eq!(pager.visible_as_plain_text(10), "line 2\nline 3");
+    }
+
+    // ========================================
+    // Callout tests
+    // ========================================
+
+    #[test]
+    fn classify_callout_recognizes_each_kind() {
+        assert_eq!(
+            classify_callout("NOTE: may change"),
+            Some(CalloutKind::Note)
+        );
+        assert_eq!(
+            classify_callout("Warning: destructive"),
+            Some(CalloutKind::Warning)
+        );
+        assert_eq!(
+            classify_callout("caution: slow"),
+            Some(CalloutKind::Caution)
+        );
+        assert_eq!(
+            classify_callout("DEPRECATED: use -x"),
+            Some(CalloutKind::Deprecated)
+        );
+        assert_eq!(
+            classify_callout("Important: read this"),
+            Some(CalloutKind::Important)
+        );
+        assert_eq!(classify_callout("TIP: try -v"), Some(CalloutKind::Tip));
+    }
+
+    #[test]
+    fn classify_callout_allows_indentation() {
+        assert_eq!(
+            classify_callout("       Note: indented"),
+            Some(CalloutKind::Note)
+        );
+    }
+
+    #[test]
+    fn classify_callout_ignores_other_lines() {
+        assert_eq!(classify_callout("See the note: below"), None);
+        assert_eq!(classify_callout("NOTES"), None);
+        assert_eq!(classify_callout("Notable:"), None);
+        assert_eq!(classify_callout("  ✓"), None);
+        assert_eq!(classify_callout(""), None);
+    }
+
+    #[test]
+    fn callout_lines_rendered_with_background() {
+        let pager = Pager::new("usage: tool\nWARNING: deletes files".to_string());
+        let area = Rect::new(0, 0, 30, 4);
+        let mut buf = Buffer::empty(area);
+        PagerWidget::new(&pager, "tool", 0, ContentSource::Help).render(area, &mut buf);
+        assert_eq!(buf[(0, 0)].bg, Color::Reset);
+        assert_eq!(buf[(0, 1)].bg, Color::Red);
+
+        let mut buf = Buffer::empty(area);
+        PagerWidget::new(&pager, "tool", 0, ContentSource::Help)
+            .callout_highlighting(false)
+            .render(area, &mut buf);
+        assert_eq!(buf[(0, 1)].bg, Color::Reset);
+    }
+
+    #[test]
+    fn cursor_line_shades_first_visible_line() {
+        let mut pager = Pager::new("one\ntwo\nthree".to_string());
+        pager.scroll = 1;
+        let area = Rect::new(0, 0, 30, 4);
+        let mut buf = Buffer::empty(area);
+        PagerWidget::new(&pager, "tool", 0, ContentSource::Help)
+            .cursor_line(true)
+            .render(area, &mut buf);
+        assert_eq!(buf[(0, 0)].bg, CURSOR_LINE_BG);
+        assert_eq!(buf[(0, 1)].bg, Color::Reset);
+
+        let mut buf = Buffer::empty(area);
+        PagerWidget::new(&pager, "tool", 0, ContentSource::Help).render(area, &mut buf);
+        assert_eq!(buf[(0, 0)].bg, Color::Reset);
+    }
+
+    #[test]
+    fn cursor_line_yields_to_search_match() {
+        let mut pager = Pager::new("one\ntwo\nthree".to_string());
+        pager.set_search("one");
+        let area = Rect::new(0, 0, 30, 4);
+        let mut buf = Buffer::empty(area);
+        PagerWidget::new(&pager, "tool", 0, ContentSource::Help)
+            .cursor_line(true)
+            .render(area, &mut buf);
+        assert_eq!(buf[(0, 0)].bg, Color::Cyan);
+        assert_ne!(buf[(3, 0)].bg, CURSOR_LINE_BG);
+    }
+
+    #[test]
+    fn help_overlay_fits_short_terminals() {
+        let area = Rect::new(0, 0, 80, 24);
+        let mut buf = Buffer::empty(area);
+        HelpOverlay.render(area, &mut buf);
+        let top: String = (0..80).map(|x| buf[(x, 0)].symbol()).collect();
+        assert!(top.contains('┌'));
+    }
+
+    // ========================================
+    // Word count tests
+    // ========================================
+
+    #[test]
+    fn words_in_range_skips_separator_lines() {
+        let pager = Pager::new("NAME\n  tool - does things\n-----\n  --  * \nOPTIONS".to_string());
+        assert_eq!(pager.words_in_range(0, 2), 5);
+        assert_eq!(pager.words_in_range(2, 4), 0);
+        assert_eq!(pager.words_in_range(0, 1000), 6);
+        assert_eq!(pager.words_in_range(1000, 1001), 0);
+    }
+
+    #[test]
+    fn words_visible_counts_viewport_rows() {
+        let mut pager = numbered_pager(10);
+        pager.scroll = 2;
+        // "line 2" through "line 4"
+        assert_eq!(pager.words_visible(3), 6);
+    }
+
+    #[test]
+    fn word_count_shown_in_status_bar() {
+        let pager = Pager::new("one two three\nfour".to_string());
+        let area = Rect::new(0, 0, 80, 4);
+        let mut buf = Buffer::empty(area);
+        PagerWidget::new(&pager, "tool", 0, ContentSource::Help)
+            .word_count(true)
+            .render(area, &mut buf);
+        let status: String = (0..80).map(|x| buf[(x, 3)].symbol()).collect();
+        assert!(status.contains("~4 words"), "{}", status);
+
+        let mut buf = Buffer::empty(area);
+        PagerWidget::new(&pager, "tool", 0, ContentSource::Help).render(area, &mut buf);
+        let status: String = (0..80).map(|x| buf[(x, 3)].symbol()).collect();
+        assert!(!status.contains("words"));
+    }
+
+    // ========================================
+    // word_at_line tests
+    // ========================================
+
+    #[test]
+    fn word_at_line_skips_flags_and_punctuation() {
+        let pager = Pager::new(
+            "  clone     Clone a repository\n  -v, --verbose  Be loud\n  (default) --all\n  --  * \n".to_string(),
+        );
+        assert_eq!(pager.word_at_line(0).as_deref(), Some("clone"));
+        assert_eq!(pager.word_at_line(1).as_deref(), Some("Be"));
+        assert_eq!(pager.word_at_line(2).as_deref(), Some("default"));
+        assert_eq!(pager.word_at_line(3), None);
+        assert_eq!(pager.word_at_line(100), None);
+    }
+
+    // ========================================
+    // Statistics tests
+    // ========================================
+
+    #[test]
+    fn statistics_counts_page_text() {
+        let pager = Pager::new("NAME\n    tool - does things\n\nOPTIONS\n    -a  all".to_string());
+        assert_eq!(
+            pager.statistics(),
+            ContentStats {
+                lines: 5,
+                words: 8,
+                characters: 44,
+                sections: 2,
+            }
+        );
+    }
+
+    #[test]
+    fn stats_overlay_aligns_values() {
+        let overlay = StatsOverlay::new(&ContentStats::default()).row("Parse method", "git-style");
+        let lines = overlay.lines();
+        assert_eq!(lines[0], "Lines:        0");
+        assert_eq!(lines[4], "Parse method: git-style");
+    }
+
+    // ========================================
+    // append_section tests
+    // ========================================
+
+    #[test]
+    fn append_section_adds_text_after_divider() {
+        let mut pager = Pager::new("usage: git\n  -v  verbose".to_string());
+        let divider = pager.append_section("Examples", "git log -p\ngit add .");
+
+        assert_eq!(divider, 3);
+        assert_eq!(pager.content[divider], "────── Examples ──────");
+        assert_eq!(
+            pager.as_plain_text(),
+            "usage: git\n  -v  verbose\n\n────── Examples ──────\ngit log -p\ngit add ."
+        );
+        assert_eq!(pager.appended_section("Examples"), Some(divider));
+        assert_eq!(pager.appended_section("Guides"), None);
+    }
+
+    #[test]
+    fn append_section_updates_search_matches() {
+        let mut pager = Pager::new("usage: git log".to_string());
+        pager.set_search("log");
+        pager.append_section("Examples", "git log -p");
+        assert_eq!(pager.search_matches, vec![0, 3]);
+    }
+
+    // ========================================
+    // Jump tests
+    // ========================================
+
+    #[test]
+    fn jump_to_first_line_matching_scrolls_to_first_hit() {
+        let mut pager = Pager::new("one\ntwo\nthree\ntwo again".to_string());
+        assert!(pager.jump_to_first_line_matching(|line| line.starts_with("two")));
+        assert_eq!(pager.scroll, 1);
+    }
+
+    #[test]
+    fn jump_to_first_line_matching_without_match_keeps_scroll() {
+        let mut pager = Pager::new("one\ntwo".to_string());
+        pager.scroll = 1;
+        assert!(!pager.jump_to_first_line_matching(|line| line == "three"));
+        // Bottom padding lines don't count as matches
+        assert!(!pager.jump_to_first_line_matching(str::is_empty));
+        assert_eq!(pager.scroll, 1);
+    }
+
+    #[test]
+    fn jump_to_first_line_matching_unfolds_target() {
+        let mut pager = sectioned_pager();
+        pager.fold_all();
+        assert!(pager.jump_to_first_line_matching(|line| line.contains("-b")));
+        assert_eq!(pager.scroll, 5);
+        assert!(!pager.is_folded(3));
+    }
+
+    #[test]
+    fn scroll_to_regex_matches_and_rejects_bad_patterns() {
+        let mut pager = Pager::new("NAME\n  git\nOPTIONS\n  --verbose".to_string());
+        assert!(pager.scroll_to_regex(r"^\s+--\w+").unwrap());
+        assert_eq!(pager.scroll, 3);
+        assert!(!pager.scroll_to_regex("^EXAMPLES$").unwrap());
+        assert!(pager.scroll_to_regex("(unclosed").is_err());
+    }
+
+    // ========================================
+    // Folding tests
+    // ========================================
+
+    fn sectioned_pager() -> Pager {
+        let content = [
+            "NAME",
+            "    tool - does things",
+            "",
+            "OPTIONS",
+            "    -a  all",
+            "    -b  brief",
+            "    -c  color",
+            "",
+            "Examples:",
+            "    tool -a",
+        ]
+        .join("\n");
+        Pager::new(content)
+    }
+
+    fn row_text(pager: &Pager) -> Vec<String> {
+        pager
+            .rows()
+            .iter()
+            .map(|&row| match row {
+                Row::Line(i) => pager.content[i].clone(),
+                Row::Fold { heading, hidden } => fold_placeholder(&pager.content[heading], hidden),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn visible_text_shows_fold_placeholder() {
+        let mut pager = sectioned_pager();
+        pager.scroll = 4;
+        pager.toggle_fold();
+        assert_eq!(
+            pager.visible_as_plain_text(3),
+            "OPTIONS\n  ↓ OPTIONS (3 lines hidden)\n"
+        );
+    }
+
+    #[test]
+    fn detect_sections_finds_headings() {
+        let pager = sectioned_pager();
+        assert_eq!(detect_sections(&pager.content), vec![0, 3, 8]);
+    }
+
+    #[test]
+    fn detect_sections_ignores_prose_and_entries() {
+        let lines: Vec<String> = [
+            "Usage: tool [OPTIONS]",
+            "  build:  Compile",
+            "Note: this is a sentence: with colons:",
+            "LS(1)    User Commands    LS(1)",
+        ]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+        assert!(detect_sections(&lines).is_empty());
+    }
+
+    #[test]
+    fn toggle_fold_hides_section_body() {
+        let mut pager = sectioned_pager();
+        pager.scroll = 4;
+        pager.toggle_fold();
+
+        assert_eq!(pager.folds.get(&3), Some(&true));
+        assert_eq!(pager.scroll, 3);
+        let text = row_text(&pager);
+        assert_eq!(text[3], "OPTIONS");
+        assert_eq!(text[4], "  ↓ OPTIONS (3 lines hidden)");
+        assert_eq!(text[5], "");
+        assert_eq!(text[6], "Examples:");
+
+        pager.toggle_fold();
+        assert_eq!(pager.rows().len(), pager.content.len());
+    }
+
+    #[test]
+    fn fold_all_and_unfold_all() {
+        let mut pager = sectioned_pager();
+        pager.fold_all();
+        let text = row_text(&pager);
+        assert!(text.contains(&"  ↓ NAME (1 line hidden)".to_string()));
+        assert!(text.contains(&"  ↓ Examples (1 line hidden)".to_string()));
+
+        pager.unfold_all();
+        assert!(pager.folds.is_empty());
+        assert_eq!(pager.rows().len(), pager.content.len());
+    }
+
+    #[test]
+    fn scrolling_skips_folded_lines() {
+        let mut pager = sectioned_pager();
+        pager.scroll = 3;
+        pager.toggle_fold();
+
+        // OPTIONS heading -> placeholder -> blank line after the section
+        pager.scroll_down(1);
+        assert_eq!(row_of(&pager.rows(), pager.scroll), 4);
+        pager.scroll_down(1);
+        assert_eq!(pager.scroll, 7);
+        pager.scroll_up(2);
+        assert_eq!(pager.scroll, 3);
+    }
+
+    #[test]
+    fn next_match_unfolds_hidden_match() {
+        let mut pager = sectioned_pager();
+        pager.fold_all();
+        pager.set_search("brief");
+        pager.next_match();
+
+        assert_eq!(pager.scroll, 5);
+        assert!(!pager.is_folded(3));
+    }
+
+    // ========================================
+    // Diff tests
+    // ========================================
+
+    fn diff_of(a: &str, b: &str) -> Vec<DiffLine> {
+        Pager::new(a.to_string())
+            .diff(&Pager::new(b.to_string()))
+            .lines
+    }
+
+    #[test]
+    fn diff_identical_is_all_same() {
+        let lines = diff_of("a\nb", "a\nb");
+        assert_eq!(
+            lines,
+            vec![
+                DiffLine::Same("a".to_string()),
+                DiffLine::Same("b".to_string())
+            ]
+        );
+    }
+
+    #[test]
+    fn diff_marks_added_and_removed() {
+        let lines = diff_of("usage\n-v verbose\n-q quiet", "usage\n-q quiet\nSEE ALSO");
+        assert_eq!(
+            lines,
+            vec![
+                DiffLine::Same("usage".to_string()),
+                DiffLine::Removed("-v verbose".to_string()),
+                DiffLine::Same("-q quiet".to_string()),
+                DiffLine::Added("SEE ALSO".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn diff_ignores_indentation() {
+        let lines = diff_of("  -v  verbose", "       -v  verbose");
+        assert_eq!(lines, vec![DiffLine::Same("  -v  verbose".to_string())]);
+    }
+
+    #[test]
+    fn diff_counts_and_clamps() {
+        let mut diff = Pager::new("a\nb".to_string()).diff(&Pager::new("b\nc\nd".to_string()));
+        assert_eq!(diff.counts(), (2, 1));
+        diff.scroll_down(100);
+        diff.clamp_scroll(2);
+        assert_eq!(diff.scroll, 2);
+    }
+
+    // ========================================
+    // Smart wrap tests
+    // ========================================
+
+    fn line_text(line: &Line) -> String {
+        line.spans.iter().map(|s| s.content.as_ref()).collect()
+    }
+
+    #[test]
+    fn smart_wrap_indents_continuations() {
+        let text = format!("    {}", "word ".repeat(30).trim_end());
+        assert!(text.len() > 80);
+
+        let rows = smart_wrap(Line::raw(text), 80);
+        assert!(rows.len() > 1);
+        for row in &rows {
+            let row = line_text(row);
+            assert!(row.len() <= 80, "row too long: {row:?}");
+            assert!(row.starts_with("    word"), "bad indent: {row:?}");
+        }
+    }
+
+    #[test]
+    fn smart_wrap_keeps_all_words() {
+        let words: Vec<String> = (0..40).map(|i| format!("w{i}")).collect();
+        let text = format!("    --flag  {}", words.join(" "));
+
+        let rows = smart_wrap(Line::raw(text.clone()), 80);
+        let rejoined: Vec<String> = rows
+            .iter()
+            .flat_map(|r| {
+                line_text(r)
+                    .split_whitespace()
+                    .map(str::to_string)
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+        let original: Vec<&str> = text.split_whitespace().collect();
+        assert_eq!(rejoined, original);
+    }
+
+    #[test]
+    fn smart_wrap_leaves_unindented_and_short_lines() {
+        let long = "x ".repeat(60);
+        assert_eq!(smart_wrap(Line::raw(long), 80).len(), 1);
+        assert_eq!(smart_wrap(Line::raw("    short"), 80).len(), 1);
+    }
+
+    #[test]
+    fn smart_wrap_hard_breaks_long_words() {
+        let text = format!("    {}", "x".repeat(100));
+        let rows = smart_wrap(Line::raw(text), 80);
+        assert_eq!(line_text(&rows[0]).len(), 80);
+        assert_eq!(line_text(&rows[1]), format!("    {}", "x".repeat(24)));
+    }
+
+    #[test]
+    fn smart_wrap_preserves_span_styles() {
+        let bold = Style::default().add_modifier(Modifier::BOLD);
+        let line = Line::from(vec![
+            Span::raw("    "),
+            Span::styled("match", bold),
+            Span::raw(format!(" {}", "filler ".repeat(20))),
+        ]);
+        let rows = smart_wrap(line, 80);
+        assert!(
+            rows[0]
+                .spans
+                .iter()
+                .any(|s| s.content == "match" && s.style == bold)
+        );
+    }
+
+    // ========================================
+    // Man decoration tests
+    // ========================================
+
+    fn span_modifiers(spans: &[Span]) -> Vec<(String, Modifier)> {
+        spans
+            .iter()
+            .map(|s| (s.content.to_string(), s.style.add_modifier))
+            .collect()
+    }
+
+    #[test]
+    fn man_overstrikes_become_bold_and_underline() {
+        let spans = parse_man_decorations("N\x08NA\x08AM\x08ME\x08E  _\x08f_\x08i_\x08l_\x08e");
+        assert_eq!(
+            span_modifiers(&spans),
+            vec![
+                ("NAME".to_string(), Modifier::BOLD),
+                ("  ".to_string(), Modifier::empty()),
+                ("file".to_string(), Modifier::UNDERLINED),
+            ]
+        );
+    }
+
+    #[test]
+    fn man_bold_underscore_and_bold_underline() {
+        let spans = parse_man_decorations("_\x08_ _\x08x\x08x");
+        assert_eq!(
+            span_modifiers(&spans),
+            vec![
+                ("_".to_string(), Modifier::BOLD),
+                (" ".to_string(), Modifier::empty()),
+                ("x".to_string(), Modifier::BOLD | Modifier::UNDERLINED),
+            ]
+        );
+    }
+
+    #[test]
+    fn man_ansi_sequences_set_bold_and_underline() {
+        let spans = parse_man_decorations("\x1b[1mls\x1b[0m [\x1b[4mFILE\x1b[24m]");
+        assert_eq!(
+            span_modifiers(&spans),
+            vec![
+                ("ls".to_string(), Modifier::BOLD),
+                (" [".to_string(), Modifier::empty()),
+                ("FILE".to_string(), Modifier::UNDERLINED),
+                ("]".to_string(), Modifier::empty()),
+            ]
+        );
+    }
+
+    #[test]
+    fn man_decorations_keep_stripped_text() {
+        let raw = "+\x08o item \x1b[1mbold\x1b[0m _\x08u";
+        let text: String = parse_man_decorations(raw)
+            .iter()
+            .map(|s| s.content.as_ref())
+            .collect();
+        assert_eq!(text, strip_man_formatting(raw));
+    }
+
+    #[test]
+    fn man_formatting_aligns_with_cleaned_content() {
+        let raw = "noise\nN\x08NA\x08AM\x08ME\x08E\n       ls - list\n_\x08x";
+        let pager = Pager::new("NAME\n       ls - list\nx".to_string());
+        let formatting = match_man_formatting(&pager.content, raw);
+
+        assert_eq!(formatting.len(), 2);
+        assert_eq!(formatting[&0], "N\x08NA\x08AM\x08ME\x08E");
+        assert_eq!(formatting[&2], "_\x08x");
+    }
+
+    #[test]
+    fn man_pages_render_bold_headings() {
+        let mut pager = Pager::new("NAME\n       ls".to_string());
+        let formatting =
+            match_man_formatting(&pager.content, "N\x08NA\x08AM\x08ME\x08E\n       ls");
+        pager.set_man_formatting(formatting);
+        let area = Rect::new(0, 0, 20, 4);
+
+        let mut buf = Buffer::empty(area);
+        PagerWidget::new(&pager, "ls", 0, ContentSource::Man).render(area, &mut buf);
+        assert!(buf[(0, 0)].modifier.contains(Modifier::BOLD));
+        assert!(!buf[(7, 1)].modifier.contains(Modifier::BOLD));
+
+        // Other sources show the text plainly
+        let mut buf = Buffer::empty(area);
+        PagerWidget::new(&pager, "ls", 0, ContentSource::Help).render(area, &mut buf);
+        assert!(!buf[(0, 0)].modifier.contains(Modifier::BOLD));
+    }
+
+    // ========================================
+    // Tab expansion tests
+    // ========================================
+
+    #[test]
+    fn expand_tabs_at_line_start() {
+        assert_eq!(expand_tabs("\tfoo", 4), "    foo");
+    }
+
+    #[test]
+    fn expand_tabs_aligns_to_tab_stops() {
+        assert_eq!(expand_tabs("a\tb", 4), "a   b");
+        assert_eq!(expand_tabs("abc\tb", 4), "abc b");
+        assert_eq!(expand_tabs("abcd\tb", 4), "abcd    b");
+        assert_eq!(expand_tabs("ab\t\tc", 4), "ab      c");
+    }
+
+    #[test]
+    fn expand_tabs_custom_width() {
+        assert_eq!(expand_tabs("a\tb", 8), "a       b");
+        assert_eq!(expand_tabs("a\tb", 2), "a b");
+    }
+
+    #[test]
+    fn expand_tabs_without_tabs_is_unchanged() {
+        assert!(matches!(expand_tabs("plain", 4), Cow::Borrowed("plain")));
+        assert_eq!(expand_tabs("a\tb", 0), "a\tb");
+    }
+
+    #[test]
+    fn pager_expand_tabs_keeps_line_count() {
+        let pager = Pager::new("name\tdesc\n\tindented".to_string());
+        let expanded = pager.expand_tabs(4);
+        assert_eq!(expanded.len(), pager.content.len());
+        assert_eq!(expanded[0], "name    desc");
+        assert_eq!(expanded[1], "    indented");
+    }
+
+    // ========================================
+    // Search context tests
+    // ========================================
+
+    #[test]
+    fn context_disabled_by_default() {
+        let mut pager = numbered_pager(20);
+        pager.set_search("line 10");
+        assert!(pager.context_lines.is_empty());
+        assert!(!pager.showing_context());
+    }
+
+    #[test]
+    fn context_includes_neighborhood_of_matches() {
+        let mut pager = numbered_pager(20).with_search_context(2);
+        pager.set_search("line 10");
+        let mut context: Vec<usize> = pager.context_lines.iter().copied().collect();
+        context.sort();
+        assert_eq!(context, vec![8, 9, 11, 12]);
+        assert!(pager.showing_context());
+    }
+
+    #[test]
+    fn context_clamped_at_document_start() {
+        let mut pager = numbered_pager(20).with_search_context(3);
+        pager.set_search("line 0");
+        let mut context: Vec<usize> = pager.context_lines.iter().copied().collect();
+        context.sort();
+        assert_eq!(context, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn context_view_collapses_gaps() {
+        let mut pager = numbered_pager(20).with_search_context(1);
+        pager.set_search("line 5");
+        let lines = context_view(&pager, 10, 4, |_, l| Line::raw(l.to_string()));
+        let text: Vec<String> = lines.iter().map(|l| l.to_string()).collect();
+        assert_eq!(text, vec!["...", "line 4", "line 5", "line 6", "..."]);
+    }
+
+    // ========================================
+    // text_range tests
+    // ========================================
+
+    #[test]
+    fn text_range_joins_lines() {
+        let pager = numbered_pager(10);
+        assert_eq!(pager.text_range(2, 4), "line 2\nline 3\nline 4");
+    }
+
+    #[test]
+    fn text_range_clamps_end() {
+        let pager = numbered_pager(3);
+        // Content includes trailing padding lines; end past them is clamped
+        assert!(pager.text_range(2, 1000).starts_with("line 2"));
+    }
+
+    #[test]
+    fn text_range_start_past_end_is_empty() {
+        let pager = numbered_pager(3);
+        assert_eq!(pager.text_range(1000, 1001), "");
+    }
+
+    #[test]
+    fn case_sensitive_search_matches_exact_case() {
+        let content = "Usage: tool\nusage is simple\nUSAGE".to_string();
+        let mut pager = Pager::new(content.clone());
+        pager.set_search("usage");
+        assert_eq!(pager.search_matches, vec![0, 1, 2]);
+
+        let mut pager = Pager::new(content).with_case_sensitive(true);
+        pager.set_search("usage");
+        assert_eq!(pager.search_matches, vec![1]);
+    }
+
+    #[test]
+    fn case_sensitive_highlight_skips_other_cases() {
+        let line = highlight_line("Usage and usage", "usage", true, true, false);
+        let highlighted: Vec<&str> = line
+            .spans
+            .iter()
+            .filter(|span| span.style.bg.is_some())
+            .map(|span| span.content.as_ref())
+            .collect();
+        assert_eq!(highlighted, vec!["usage"]);
+        assert_eq!(line.to_string(), "Usage and usage");
+    }
+
+    #[test]
+    fn clear_search_clears_context() {
+        let mut pager = numbered_pager(20).with_search_context(2);
+        pager.set_search("line 10");
+        pager.clear_search();
+        assert!(pager.context_lines.is_empty());
+    }
+
+    // ========================================
+    // scrollbar_thumb tests
+    // ========================================
+
+    #[test]
+    fn thumb_fills_track_when_content_fits() {
+        assert_eq!(scrollbar_thumb(10, 20, 0, 20), (0, 20));
+    }
+
+    #[test]
+    fn thumb_proportional_to_viewport() {
+        // 100 lines, 20 visible, 20-row track: thumb is 4 rows
+        assert_eq!(scrollbar_thumb(100, 20, 0, 20), (0, 4));
+        assert_eq!(scrollbar_thumb(100, 20, 50, 20), (10, 4));
+    }
+
+    #[test]
+    fn thumb_stays_within_track_at_bottom() {
+        let (start, len) = scrollbar_thumb(100, 20, 80, 20);
+        assert!(start + len <= 20);
+    }
+
+    #[test]
+    fn thumb_has_minimum_length() {
+        let (_, len) = scrollbar_thumb(10_000, 20, 0, 20);
+        assert_eq!(len, 1);
+    }
+}
@@ -1,8 +1,10 @@
 use regex::Regex;
+use serde::Serialize;
 
-use crate::config::Config;
+use crate::config::{Config, SubcommandPattern};
+use crate::toolpacks::DedupeStrategy;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct Subcommand {
     pub name: String,
     pub description: Option<String>,
@@ -10,12 +12,143 @@ pub struct Subcommand {
     pub label: Option<String>,
     /// Custom invoke command for discovered items (e.g., "git help {name}")
     pub invoke_command: Option<String>,
+    /// Documentation URL, for items whose tool pack invoke is a web link
+    pub url: Option<String>,
+    /// Other names listed with this one, e.g. `b` for cargo's `build, b`
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub aliases: Vec<String>,
+    /// How this item is matched against existing entries when merging
+    #[serde(skip)]
+    pub dedupe_strategy: DedupeStrategy,
+    /// How sure the parser is that this is a real subcommand, from 0 to 1
+    pub confidence: f32,
 }
 
-pub fn parse_subcommands(help_text: &str, config: &Config) -> Vec<Subcommand> {
+/// Confidence of an entry found only by the aggressive fallback parser
+pub const AGGRESSIVE_CONFIDENCE: f32 = 0.5;
+/// Confidence of an aggressive entry that also has a `<base>-<name>` man page
+pub const MAN_CONFIRMED_CONFIDENCE: f32 = 0.8;
+
+impl Subcommand {
+    /// Name as shown in the finder, with any aliases: `build (b)`
+    pub fn display_name(&self) -> String {
+        if self.aliases.is_empty() {
+            self.name.clone()
+        } else {
+            format!("{} ({})", self.name, self.aliases.join(", "))
+        }
+    }
+}
+
+/// Split a captured name like `build, b` or `build|b` into the name and its aliases
+fn split_aliases(raw: &str) -> (String, Vec<String>) {
+    let mut parts = raw
+        .split([',', '|'])
+        .map(str::trim)
+        .filter(|part| !part.is_empty())
+        .map(str::to_string);
+    let name = parts.next().unwrap_or_default();
+    (name, parts.collect())
+}
+
+/// Which parsing strategy produced the subcommand list
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ParseMethod {
+    /// The configured subcommand pattern at this index matched first
+    Pattern(usize),
+    GitStyle,
+    Aggressive,
+    #[default]
+    None,
+}
+
+impl ParseMethod {
+    /// Short name for the status bar
+    pub fn label(self) -> String {
+        match self {
+            ParseMethod::Pattern(i) => format!("pattern {}", i + 1),
+            ParseMethod::GitStyle => "git-style".to_string(),
+            ParseMethod::Aggressive => "aggressive".to_string(),
+            ParseMethod::None => "none".to_string(),
+        }
+    }
+}
+
+/// How a help page was parsed, for tuning custom patterns
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ParseStats {
+    /// Configured patterns with valid regexes that were run
+    pub patterns_tried: u8,
+    pub match_method: ParseMethod,
+    pub items_before_dedup: usize,
+    pub items_after_dedup: usize,
+}
+
+pub fn parse_subcommands(help_text: &str, config: &Config) -> (Vec<Subcommand>, ParseStats) {
+    parse_with_patterns(help_text, &config.subcommand_patterns)
+}
+
+/// Parse help for a specific tool, honoring its `[tools.<name>]` pattern overrides
+pub fn parse_tool_subcommands(
+    tool: &str,
+    help_text: &str,
+    config: &Config,
+) -> (Vec<Subcommand>, ParseStats) {
+    parse_with_patterns(help_text, &config.patterns_for(tool))
+}
+
+fn parse_with_patterns(
+    help_text: &str,
+    patterns: &[SubcommandPattern],
+) -> (Vec<Subcommand>, ParseStats) {
+    let mut stats = ParseStats::default();
+    let mut found = parse_configured_patterns(help_text, patterns, &mut stats);
+
+    // Try git-style parsing if we found nothing
+    if found.is_empty() {
+        found = parse_git_style(help_text);
+        stats.match_method = ParseMethod::GitStyle;
+    }
+
+    // Relax the indent for git-style variants (e.g. `hub`) that use 2-4 spaces
+    if found.is_empty() {
+        found = parse_git_style_with_indent(help_text, 2, 4);
+    }
+
+    // Try aggressive pattern if still nothing
+    if found.is_empty() {
+        found = parse_aggressive(help_text);
+        stats.match_method = ParseMethod::Aggressive;
+    }
+
+    if found.is_empty() {
+        stats.match_method = ParseMethod::None;
+    }
+
+    stats.items_before_dedup = found.len();
+    let subcommands = dedupe_by_name(found);
+    stats.items_after_dedup = subcommands.len();
+    (subcommands, stats)
+}
+
+/// Keep the first entry for each name
+fn dedupe_by_name(items: Vec<Subcommand>) -> Vec<Subcommand> {
+    let mut seen = std::collections::HashSet::new();
+    items
+        .into_iter()
+        .filter(|s| seen.insert(s.name.clone()))
+        .collect()
+}
+
+/// Run each configured pattern, recording which one matched first in `stats`
+fn parse_configured_patterns(
+    help_text: &str,
+    patterns: &[SubcommandPattern],
+    stats: &mut ParseStats,
+) -> Vec<Subcommand> {
     let mut subcommands = Vec::new();
 
-    for pattern in &config.subcommand_patterns {
+    for (index, pattern) in patterns.iter().enumerate() {
         let section_re = match Regex::new(&pattern.section) {
             Ok(re) => re,
             Err(_) => continue,
@@ -26,6 +159,9 @@ pub fn parse_subcommands(help_text: &str, config: &Config) -> Vec<Subcommand> {
             Err(_) => continue,
         };
 
+        stats.patterns_tried = stats.patterns_tried.saturating_add(1);
+        let found_before = subcommands.len();
+
         let mut in_section = false;
         let mut blank_line_count = 0;
 
@@ -56,36 +192,31 @@ pub fn parse_subcommands(help_text: &str, config: &Config) -> Vec<Subcommand> {
                 if let Some(captures) = entry_re.captures(line)
                     && let Some(name_match) = captures.get(1)
                 {
-                    let name = name_match.as_str().to_string();
+                    let (name, aliases) = split_aliases(name_match.as_str());
                     let description = captures.get(2).map(|m| m.as_str().trim().to_string());
 
                     // Skip if this looks like a flag rather than a subcommand
-                    if name.starts_with('-') {
+                    if name.is_empty() || name.starts_with('-') {
                         continue;
                     }
 
-                    // Avoid duplicates
-                    if !subcommands.iter().any(|s: &Subcommand| s.name == name) {
-                        subcommands.push(Subcommand {
-                            name,
-                            description,
-                            label: None,
-                            invoke_command: None,
-                        });
-                    }
+                    subcommands.push(Subcommand {
+                        name,
+                        description,
+                        label: None,
+                        invoke_command: None,
+                        url: None,
+                        aliases,
+                        dedupe_strategy: DedupeStrategy::Name,
+                        confidence: 1.0,
+                    });
                 }
             }
         }
-    }
 
-    // Try git-style parsing if we found nothing
-    if subcommands.is_empty() {
-        subcommands = parse_git_style(help_text);
-    }
-
-    // Try aggressive pattern if still nothing
-    if subcommands.is_empty() {
-        subcommands = parse_aggressive(help_text);
+        if subcommands.len() > found_before && stats.match_method == ParseMethod::None {
+            stats.match_method = ParseMethod::Pattern(index);
+        }
     }
 
     subcommands
@@ -95,10 +226,23 @@ pub fn parse_subcommands(help_text: &str, config: &Config) -> Vec<Subcommand> {
 /// - Section headers are non-indented descriptive text (possibly with parenthetical)
 /// - Commands are 3-space indented: `   cmd      Description`
 fn parse_git_style(help_text: &str) -> Vec<Subcommand> {
+    parse_git_style_with_indent(help_text, 3, 3)
+}
+
+/// Git-style parsing with entries indented by `min_indent..=max_indent` spaces
+fn parse_git_style_with_indent(
+    help_text: &str,
+    min_indent: usize,
+    max_indent: usize,
+) -> Vec<Subcommand> {
     let mut subcommands = Vec::new();
 
-    // Git uses exactly 3 spaces, then command, then 2+ spaces, then description
-    let entry_re = Regex::new(r"^   ([a-z][\w-]*)\s{2,}(.+)$").unwrap();
+    // Indent, then command, then 2+ spaces, then description. Names start
+    // with any lowercase letter, not just ASCII (`\w` is already Unicode).
+    let entry_re = Regex::new(&format!(
+        r"^ {{{min_indent},{max_indent}}}(\p{{Ll}}[\w-]*)\s{{2,}}(.+)$"
+    ))
+    .unwrap();
 
     // Track if we're past the usage block and into command listings
     let mut past_usage = false;
@@ -151,14 +295,16 @@ fn parse_git_style(help_text: &str) -> Vec<Subcommand> {
             let name = name_match.as_str().to_string();
             let description = captures.get(2).map(|m| m.as_str().trim().to_string());
 
-            if !subcommands.iter().any(|s: &Subcommand| s.name == name) {
-                subcommands.push(Subcommand {
-                    name,
-                    description,
-                    label: None,
-                    invoke_command: None,
-                });
-            }
+            subcommands.push(Subcommand {
+                name,
+                description,
+                label: None,
+                invoke_command: None,
+                url: None,
+                aliases: Vec::new(),
+                dedupe_strategy: DedupeStrategy::Name,
+                confidence: 1.0,
+            });
         }
     }
 
@@ -169,7 +315,7 @@ fn parse_aggressive(help_text: &str) -> Vec<Subcommand> {
     let mut subcommands = Vec::new();
 
     // Look for common patterns like "  command    Description" or "  command:   Description"
-    let entry_re = Regex::new(r"^\s{2,6}([a-z][\w-]*):?\s{2,}(.*)$").unwrap();
+    let entry_re = Regex::new(r"^\s{2,6}(\p{Ll}[\w-]*):?\s{2,}(.*)$").unwrap();
 
     let mut in_likely_section = false;
 
@@ -211,14 +357,16 @@ fn parse_aggressive(help_text: &str) -> Vec<Subcommand> {
                 let name = name_match.as_str().to_string();
                 let description = captures.get(2).map(|m| m.as_str().trim().to_string());
 
-                if !name.starts_with('-')
-                    && !subcommands.iter().any(|s: &Subcommand| s.name == name)
-                {
+                if !name.starts_with('-') {
                     subcommands.push(Subcommand {
                         name,
                         description,
                         label: None,
                         invoke_command: None,
+                        url: None,
+                        aliases: Vec::new(),
+                        dedupe_strategy: DedupeStrategy::Name,
+                        confidence: AGGRESSIVE_CONFIDENCE,
                     });
                 }
             }
@@ -228,28 +376,47 @@ fn parse_aggressive(help_text: &str) -> Vec<Subcommand> {
     subcommands
 }
 
+/// Raise aggressive guesses that `man -k` also found as `<base>-<name>` pages
+pub fn confirm_with_man_pages(
+    subcommands: &mut [Subcommand],
+    base_cmd: &str,
+    man_pages: &[Subcommand],
+) {
+    for sub in subcommands
+        .iter_mut()
+        .filter(|s| s.confidence < MAN_CONFIRMED_CONFIDENCE)
+    {
+        let page = format!("{}-{}", base_cmd, sub.name);
+        if man_pages.iter().any(|p| p.name == page) {
+            sub.confidence = MAN_CONFIRMED_CONFIDENCE;
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::config::SubcommandPattern;
+    use crate::config::ToolConfig;
 
     fn test_config() -> Config {
-        let mut config = Config::default();
-        config.subcommand_patterns = vec![
-            SubcommandPattern {
-                section: r"(?im)^(commands?|subcommands?|available\s+commands?):?\s*$".to_string(),
-                entry: r"^\s{2,4}([\w][\w-]*)\s+(.*)$".to_string(),
-            },
-            SubcommandPattern {
-                section: r"(?im)^(usage|options):?\s*$".to_string(),
-                entry: r"^\s{2,4}([\w][\w-]*)\s{2,}(.*)$".to_string(),
-            },
-            SubcommandPattern {
-                section: r"(?i)^\w+\s+COMMANDS?\s*$".to_string(),
-                entry: r"^\s{2}([\w][\w-]*):\s+(.*)$".to_string(),
-            },
-        ];
-        config
+        Config {
+            subcommand_patterns: vec![
+                SubcommandPattern {
+                    section: r"(?im)^(commands?|subcommands?|available\s+commands?):?\s*$"
+                        .to_string(),
+                    entry: r"^\s{2,4}([\w][\w-]*(?:(?:,\s*|\|)[\w][\w-]*)*)\s+(.*)$".to_string(),
+                },
+                SubcommandPattern {
+                    section: r"(?im)^(usage|options):?\s*$".to_string(),
+                    entry: r"^\s{2,4}([\w][\w-]*(?:(?:,\s*|\|)[\w][\w-]*)*)\s{2,}(.*)$".to_string(),
+                },
+                SubcommandPattern {
+                    section: r"(?i)^\w+\s+COMMANDS?\s*$".to_string(),
+                    entry: r"^\s{2}([\w][\w-]*):\s+(.*)$".to_string(),
+                },
+            ],
+            ..Default::default()
+        }
     }
 
     // ========================================
@@ -265,7 +432,7 @@ Commands:
   clean    Remove build artifacts
 "#;
         let config = test_config();
-        let subs = parse_subcommands(help, &config);
+        let (subs, _) = parse_subcommands(help, &config);
         assert_eq!(subs.len(), 3);
         assert_eq!(subs[0].name, "build");
         assert_eq!(subs[0].description.as_deref(), Some("Compile the project"));
@@ -281,7 +448,7 @@ COMMANDS:
   deploy   Deploy to production
 "#;
         let config = test_config();
-        let subs = parse_subcommands(help, &config);
+        let (subs, _) = parse_subcommands(help, &config);
         assert_eq!(subs.len(), 2);
         assert_eq!(subs[0].name, "init");
         assert_eq!(subs[1].name, "deploy");
@@ -299,7 +466,7 @@ This is some other text that should be ignored.
   notacmd  This should not be parsed
 "#;
         let config = test_config();
-        let subs = parse_subcommands(help, &config);
+        let (subs, _) = parse_subcommands(help, &config);
         assert_eq!(subs.len(), 2);
         assert_eq!(subs[0].name, "first");
         assert_eq!(subs[1].name, "second");
@@ -315,7 +482,7 @@ Options:
   -v       Verbose mode
 "#;
         let config = test_config();
-        let subs = parse_subcommands(help, &config);
+        let (subs, _) = parse_subcommands(help, &config);
         assert_eq!(subs.len(), 2);
         assert!(!subs.iter().any(|s| s.name == "v"));
     }
@@ -330,7 +497,7 @@ Commands:
   build    Build the project
 "#;
         let config = test_config();
-        let subs = parse_subcommands(help, &config);
+        let (subs, _) = parse_subcommands(help, &config);
         assert_eq!(subs.len(), 2);
         assert!(subs.iter().any(|s| s.name == "run"));
         assert!(subs.iter().any(|s| s.name == "build"));
@@ -348,11 +515,157 @@ Subcommands:
   test     Run tests
 "#;
         let config = test_config();
-        let subs = parse_subcommands(help, &config);
+        let (subs, _) = parse_subcommands(help, &config);
         let build_count = subs.iter().filter(|s| s.name == "build").count();
         assert_eq!(build_count, 1);
     }
 
+    #[test]
+    fn parse_tool_uses_per_tool_patterns() {
+        let help = r#"
+VERBS
+  * fetch -- Fetch things
+  * store -- Store things
+"#;
+        let mut config = test_config();
+        config.tools.insert(
+            "odd".to_string(),
+            ToolConfig {
+                help_flags: Vec::new(),
+                subcommand_patterns: Some(vec![SubcommandPattern {
+                    section: r"^VERBS$".to_string(),
+                    entry: r"^\s+\* (\w+) -- (.*)$".to_string(),
+                }]),
+                env: Default::default(),
+                strip_patterns: Vec::new(),
+                strip_prefix_lines: None,
+                discover: Vec::new(),
+                replace_discover: false,
+            },
+        );
+
+        let (subs, _) = parse_tool_subcommands("odd", help, &config);
+        let names: Vec<&str> = subs.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["fetch", "store"]);
+    }
+
+    // ========================================
+    // Alias tests
+    // ========================================
+
+    #[test]
+    fn split_aliases_handles_commas_and_pipes() {
+        assert_eq!(split_aliases("build"), ("build".to_string(), vec![]));
+        assert_eq!(
+            split_aliases("build, b"),
+            ("build".to_string(), vec!["b".to_string()])
+        );
+        assert_eq!(
+            split_aliases("remove|rm|r"),
+            (
+                "remove".to_string(),
+                vec!["rm".to_string(), "r".to_string()]
+            )
+        );
+    }
+
+    #[test]
+    fn parse_non_ascii_subcommand_names() {
+        let help = "Commands:\n  büild    Compile the project\n  prüfen, p   Run the tests\n";
+        let (subs, _) = parse_subcommands(help, &test_config());
+        let names: Vec<&str> = subs.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["büild", "prüfen"]);
+        assert_eq!(subs[0].description.as_deref(), Some("Compile the project"));
+        assert_eq!(subs[1].aliases, vec!["p"]);
+    }
+
+    #[test]
+    fn parse_comma_aliases() {
+        let help =
+            "Commands:\n    build, b    Compile the package\n    clean       Remove artifacts\n";
+        let (subs, _) = parse_subcommands(help, &test_config());
+        assert_eq!(subs.len(), 2);
+        assert_eq!(subs[0].name, "build");
+        assert_eq!(subs[0].aliases, vec!["b"]);
+        assert_eq!(subs[0].description.as_deref(), Some("Compile the package"));
+        assert!(subs[1].aliases.is_empty());
+    }
+
+    #[test]
+    fn parse_pipe_aliases() {
+        let help = "Commands:\n  build|b       Build the project\n";
+        let (subs, _) = parse_subcommands(help, &test_config());
+        assert_eq!(subs[0].name, "build");
+        assert_eq!(subs[0].aliases, vec!["b"]);
+    }
+
+    #[test]
+    fn parse_cargo_aliases_from_fixture() {
+        let help = include_str!("../tests/fixtures/cargo_help.txt");
+        let (subs, _) = parse_subcommands(help, &test_config());
+        let build = subs.iter().find(|s| s.name == "build").unwrap();
+        assert_eq!(build.aliases, vec!["b"]);
+        assert_eq!(build.display_name(), "build (b)");
+        let clean = subs.iter().find(|s| s.name == "clean").unwrap();
+        assert_eq!(clean.display_name(), "clean");
+    }
+
+    // ========================================
+    // ParseStats tests
+    // ========================================
+
+    #[test]
+    fn stats_report_pattern_and_dedup_counts() {
+        let help = r#"
+Commands:
+  build    Compile the project
+
+Subcommands:
+  build    Build (duplicate)
+  test     Run tests
+"#;
+        let config = test_config();
+        let (_, stats) = parse_subcommands(help, &config);
+        assert_eq!(stats.patterns_tried, 3);
+        assert_eq!(stats.match_method, ParseMethod::Pattern(0));
+        assert_eq!(stats.items_before_dedup, 3);
+        assert_eq!(stats.items_after_dedup, 2);
+    }
+
+    #[test]
+    fn stats_report_git_style_fallback() {
+        let help = r#"usage: git [--version]
+
+start a working area
+   clone     Clone a repository
+   init      Create an empty repository
+"#;
+        let (_, stats) = parse_subcommands(help, &test_config());
+        assert_eq!(stats.match_method, ParseMethod::GitStyle);
+        assert_eq!(stats.items_after_dedup, 2);
+    }
+
+    #[test]
+    fn stats_report_none_when_nothing_found() {
+        let (subs, stats) = parse_subcommands("just some prose", &test_config());
+        assert!(subs.is_empty());
+        assert_eq!(stats.match_method, ParseMethod::None);
+        assert_eq!(stats.items_before_dedup, 0);
+    }
+
+    #[test]
+    fn stats_skip_invalid_patterns() {
+        let config = Config {
+            subcommand_patterns: vec![SubcommandPattern {
+                section: "(".to_string(),
+                entry: ".*".to_string(),
+            }],
+            ..Default::default()
+        };
+        let (_, stats) = parse_subcommands("", &config);
+        assert_eq!(stats.patterns_tried, 0);
+    }
+
     // ========================================
     // parse_git_style tests
     // ========================================
@@ -438,6 +751,50 @@ section header
         assert_eq!(subs[0].name, "valid");
     }
 
+    #[test]
+    fn parse_git_style_with_relaxed_indent() {
+        let help = r#"
+usage: hub [options] <command>
+
+common commands
+  clone      Clone a repository
+    fork     Fork a repository
+     toodeep  Five spaces is not an entry
+"#;
+        let subs = parse_git_style_with_indent(help, 2, 4);
+        let names: Vec<&str> = subs.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["clone", "fork"]);
+    }
+
+    #[test]
+    fn parse_git_style_non_ascii_names() {
+        let help = r#"
+usage: outil [options] <command>
+
+commandes principales
+   éditer     Modifier un fichier
+   büild      Construire le projet
+"#;
+        let subs = parse_git_style(help);
+        let names: Vec<&str> = subs.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["éditer", "büild"]);
+    }
+
+    #[test]
+    fn parse_falls_back_to_relaxed_git_style() {
+        let help = r#"
+usage: hub [options] <command>
+
+common things
+  clone  Clone a repository
+  fork   Fork a repository
+"#;
+        let config = test_config();
+        let (subs, _) = parse_subcommands(help, &config);
+        assert!(subs.iter().any(|s| s.name == "clone"));
+        assert!(subs.iter().any(|s| s.name == "fork"));
+    }
+
     // ========================================
     // parse_aggressive tests
     // ========================================
@@ -455,6 +812,15 @@ Available commands:
         assert!(subs.iter().any(|s| s.name == "run"));
     }
 
+    #[test]
+    fn parse_aggressive_non_ascii_names() {
+        let help =
+            "Available commands:\n  übersetzen   Translate text\n  ändern:      Change text\n";
+        let subs = parse_aggressive(help);
+        let names: Vec<&str> = subs.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["übersetzen", "ändern"]);
+    }
+
     #[test]
     fn parse_aggressive_handles_colon_style() {
         let help = r#"
@@ -496,6 +862,43 @@ Available commands:
         );
     }
 
+    fn man_page(name: &str) -> Subcommand {
+        Subcommand {
+            name: name.to_string(),
+            description: None,
+            label: Some("Man Pages".to_string()),
+            invoke_command: Some(format!("man {}", name)),
+            url: None,
+            aliases: Vec::new(),
+            dedupe_strategy: DedupeStrategy::Name,
+            confidence: 1.0,
+        }
+    }
+
+    #[test]
+    fn man_pages_confirm_aggressive_entries() {
+        let help = r#"
+Available commands:
+  init      Initialize project
+  run       Run the app
+"#;
+        let mut subs = parse_aggressive(help);
+        assert!(subs.iter().all(|s| s.confidence == AGGRESSIVE_CONFIDENCE));
+
+        confirm_with_man_pages(&mut subs, "tool", &[man_page("tool-init"), man_page("run")]);
+        let confidence = |name: &str| subs.iter().find(|s| s.name == name).unwrap().confidence;
+        assert_eq!(confidence("init"), MAN_CONFIRMED_CONFIDENCE);
+        assert_eq!(confidence("run"), AGGRESSIVE_CONFIDENCE);
+    }
+
+    #[test]
+    fn pattern_entries_are_fully_confident() {
+        let config = test_config();
+        let (subs, _) = parse_subcommands("Commands:\n  build    Compile\n", &config);
+        assert!(!subs.is_empty());
+        assert!(subs.iter().all(|s| s.confidence == 1.0));
+    }
+
     // ========================================
     // Edge cases
     // ========================================
@@ -503,7 +906,7 @@ Available commands:
     #[test]
     fn parse_empty_help_text() {
         let config = test_config();
-        let subs = parse_subcommands("", &config);
+        let (subs, _) = parse_subcommands("", &config);
         assert!(subs.is_empty());
     }
 
@@ -516,7 +919,7 @@ Options:
   -d, --debug    Enable debug mode
 "#;
         let config = test_config();
-        let subs = parse_subcommands(help, &config);
+        let (subs, _) = parse_subcommands(help, &config);
         assert!(subs.is_empty());
     }
 
@@ -524,11 +927,9 @@ Options:
     fn parse_real_cargo_help() {
         let help = include_str!("../tests/fixtures/cargo_help.txt");
         let config = test_config();
-        let subs = parse_subcommands(help, &config);
+        let (subs, _) = parse_subcommands(help, &config);
 
         // Cargo uses "Commands:" section with 4-space indent and aliases like "build, b"
-        // Our patterns capture the first word before comma/space
-        // Note: Cargo format "    build, b    Description" - aggressive parser handles this
         assert!(
             !subs.is_empty(),
             "Expected to parse some commands from cargo help"
@@ -543,7 +944,7 @@ Options:
     fn parse_real_gh_help() {
         let help = include_str!("../tests/fixtures/gh_help.txt");
         let config = test_config();
-        let subs = parse_subcommands(help, &config);
+        let (subs, _) = parse_subcommands(help, &config);
 
         // gh uses "CORE COMMANDS" style with colon entries
         assert!(subs.iter().any(|s| s.name == "auth"));
@@ -563,7 +964,7 @@ main commands
    bar      Do bar things
 "#;
         let config = test_config();
-        let subs = parse_subcommands(help, &config);
+        let (subs, _) = parse_subcommands(help, &config);
         assert!(subs.iter().any(|s| s.name == "foo"));
         assert!(subs.iter().any(|s| s.name == "bar"));
     }
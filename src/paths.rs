@@ -0,0 +1,378 @@
+use anyhow::{Result, anyhow};
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+const APP_DIR: &str = "helpv";
+
+/// Locations of helpv's config, data, and cache directories, resolved per the
+/// XDG Base Directory spec on every platform.
+#[derive(Debug, Clone, Default)]
+pub struct Paths {
+    /// `$XDG_CONFIG_HOME/helpv`, falling back to `~/.config/helpv`
+    pub config_dir: PathBuf,
+    /// `$XDG_DATA_HOME/helpv`, falling back to `~/.local/share/helpv`
+    pub data_dir: PathBuf,
+    /// `$XDG_CACHE_HOME/helpv`, falling back to `~/.cache/helpv`
+    pub cache_dir: PathBuf,
+    /// Config file chosen with `--config` or `$HELPV_CONFIG` instead of
+    /// `config_dir/config.toml`
+    pub config_override: Option<PathBuf>,
+    /// Home directory the other paths were resolved against, for expanding `~`
+    pub home: PathBuf,
+}
+
+impl Paths {
+    /// Resolve paths from the process environment
+    pub fn from_env() -> Self {
+        let home = dirs::home_dir().unwrap_or_else(|| PathBuf::from("."));
+        Self::resolve(&home, |var| std::env::var(var).ok())
+    }
+
+    /// Resolve paths using `lookup` to read environment variables, including
+    /// `HELPV_CONFIG`
+    pub fn resolve(home: &Path, lookup: impl Fn(&str) -> Option<String>) -> Self {
+        let base = |var: &str, fallback: &[&str]| -> PathBuf {
+            // The spec says relative values are invalid and must be ignored
+            match lookup(var).map(PathBuf::from) {
+                Some(dir) if dir.is_absolute() => dir,
+                _ => fallback.iter().fold(home.to_path_buf(), |p, c| p.join(c)),
+            }
+        };
+
+        Self {
+            config_dir: base("XDG_CONFIG_HOME", &[".config"]).join(APP_DIR),
+            data_dir: base("XDG_DATA_HOME", &[".local", "share"]).join(APP_DIR),
+            cache_dir: base("XDG_CACHE_HOME", &[".cache"]).join(APP_DIR),
+            config_override: lookup("HELPV_CONFIG")
+                .filter(|path| !path.is_empty())
+                .map(|path| expand_tilde(&path, home)),
+            home: home.to_path_buf(),
+        }
+    }
+
+    /// Use `path` as the config file, taking precedence over `$HELPV_CONFIG`
+    pub fn with_config_file(mut self, path: &Path) -> Self {
+        self.config_override = Some(expand_tilde(&path.to_string_lossy(), &self.home));
+        self
+    }
+
+    pub fn config_file(&self) -> PathBuf {
+        match self.config_override {
+            Some(ref path) => path.clone(),
+            None => self.config_dir.join("config.toml"),
+        }
+    }
+
+    /// Where `Ctrl-e` writes a session script, e.g. `session_20240101-093000.sh`
+    pub fn session_file(&self, time: SystemTime) -> PathBuf {
+        self.data_dir
+            .join(format!("session_{}.sh", format_timestamp(time)))
+    }
+
+    /// Where a crash report from `time` is written
+    pub fn crash_file(&self, time: SystemTime) -> PathBuf {
+        self.data_dir
+            .join(format!("crash_{}.json", format_timestamp(time)))
+    }
+
+    /// Saved crash reports, oldest first
+    pub fn crash_reports(&self) -> Vec<PathBuf> {
+        let Ok(entries) = std::fs::read_dir(&self.data_dir) else {
+            return Vec::new();
+        };
+        let mut reports: Vec<PathBuf> = entries
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| {
+                path.file_name()
+                    .and_then(|name| name.to_str())
+                    .is_some_and(|name| name.starts_with("crash_") && name.ends_with(".json"))
+            })
+            .collect();
+        // Timestamped names sort chronologically
+        reports.sort();
+        reports
+    }
+
+    /// The newest crash report if it hasn't been announced at startup yet.
+    /// Its name is recorded in `crash_seen` so the next launch stays quiet.
+    pub fn take_new_crash_report(&self) -> Option<PathBuf> {
+        let newest = self.crash_reports().pop()?;
+        let name = newest.file_name()?.to_str()?.to_string();
+        let marker = self.data_dir.join("crash_seen");
+        // Timestamped names sort chronologically
+        if std::fs::read_to_string(&marker).is_ok_and(|seen| seen.trim() >= name.as_str()) {
+            return None;
+        }
+        // Best effort: an unwritable marker only means the notice repeats
+        let _ = std::fs::write(&marker, &name);
+        Some(newest)
+    }
+
+    /// Where `helpv --restore NAME` finds the snapshot saved as NAME
+    pub fn snapshot_file(&self, name: &str) -> PathBuf {
+        self.data_dir
+            .join("snapshots")
+            .join(format!("{}.json", name))
+    }
+
+    /// Where the command switcher's recent commands persist between sessions.
+    /// `None` without a data directory (`Paths::default()`), so nothing is
+    /// written to the working directory.
+    pub fn command_history_file(&self) -> Option<PathBuf> {
+        (!self.data_dir.as_os_str().is_empty()).then(|| self.data_dir.join("command_history.json"))
+    }
+
+    /// Where `--debug` logs app state transitions, one JSON object per line
+    pub fn debug_log_file(&self) -> PathBuf {
+        self.data_dir.join("debug.jsonl")
+    }
+
+    /// Directory holding user tool pack overrides
+    pub fn tools_dir(&self) -> PathBuf {
+        self.config_dir.join("tools")
+    }
+
+    /// The user tool pack file for `tool`: an existing `.json` file, otherwise
+    /// the `.toml` one (which may not exist yet)
+    pub fn toolpack_file(&self, tool: &str) -> PathBuf {
+        let json = self.tools_dir().join(format!("{}.json", tool));
+        if json.exists() {
+            json
+        } else {
+            self.tools_dir().join(format!("{}.toml", tool))
+        }
+    }
+}
+
+/// Names of snapshots and tool packs become file names, so they can't be
+/// paths. `kind` names what `name` is for the error.
+pub fn check_file_name(kind: &str, name: &str) -> Result<()> {
+    if name.is_empty() || name.starts_with('.') || name.contains(['/', '\\']) {
+        return Err(anyhow!("Invalid {} name '{}'", kind, name));
+    }
+    Ok(())
+}
+
+/// `path` with a leading `~` replaced by `home`
+fn expand_tilde(path: &str, home: &Path) -> PathBuf {
+    match path.strip_prefix('~') {
+        Some("") => home.to_path_buf(),
+        Some(rest) if rest.starts_with('/') => home.join(&rest[1..]),
+        _ => PathBuf::from(path),
+    }
+}
+
+/// `path` with the home directory shortened to `~`
+pub fn tilde_path(path: &Path) -> String {
+    match dirs::home_dir().and_then(|home| path.strip_prefix(home).ok().map(Path::to_path_buf)) {
+        Some(rest) => format!("~/{}", rest.display()),
+        None => path.display().to_string(),
+    }
+}
+
+/// UTC `YYYYMMDD-HHMMSS` for file names
+fn format_timestamp(time: SystemTime) -> String {
+    let secs = time
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs());
+    let (days, rem) = (secs / 86_400, secs % 86_400);
+
+    // Civil date from days since 1970-01-01 (Howard Hinnant's algorithm)
+    let z = days as i64 + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    format!(
+        "{:04}{:02}{:02}-{:02}{:02}{:02}",
+        year,
+        month,
+        day,
+        rem / 3_600,
+        rem % 3_600 / 60,
+        rem % 60
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn resolve_with(vars: &[(&str, &Path)]) -> Paths {
+        let vars: HashMap<String, String> = vars
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string_lossy().into_owned()))
+            .collect();
+        Paths::resolve(Path::new("/home/user"), |var| vars.get(var).cloned())
+    }
+
+    #[test]
+    fn falls_back_to_home_directories() {
+        let paths = resolve_with(&[]);
+        assert_eq!(paths.config_dir, PathBuf::from("/home/user/.config/helpv"));
+        assert_eq!(
+            paths.data_dir,
+            PathBuf::from("/home/user/.local/share/helpv")
+        );
+        assert_eq!(paths.cache_dir, PathBuf::from("/home/user/.cache/helpv"));
+    }
+
+    #[test]
+    fn uses_xdg_variables_when_set() {
+        let tmp = std::env::temp_dir();
+        let config = tmp.join("xdg-config");
+        let data = tmp.join("xdg-data");
+        let cache = tmp.join("xdg-cache");
+        let paths = resolve_with(&[
+            ("XDG_CONFIG_HOME", &config),
+            ("XDG_DATA_HOME", &data),
+            ("XDG_CACHE_HOME", &cache),
+        ]);
+
+        assert_eq!(paths.config_dir, config.join("helpv"));
+        assert_eq!(paths.data_dir, data.join("helpv"));
+        assert_eq!(paths.cache_dir, cache.join("helpv"));
+        assert_eq!(
+            paths.config_file(),
+            config.join("helpv").join("config.toml")
+        );
+        assert_eq!(paths.tools_dir(), config.join("helpv").join("tools"));
+    }
+
+    #[test]
+    fn helpv_config_overrides_config_file() {
+        let paths = resolve_with(&[("HELPV_CONFIG", Path::new("/etc/helpv.toml"))]);
+        assert_eq!(paths.config_file(), PathBuf::from("/etc/helpv.toml"));
+        // Other files still live under the XDG directories
+        assert_eq!(
+            paths.tools_dir(),
+            PathBuf::from("/home/user/.config/helpv/tools")
+        );
+
+        let paths = resolve_with(&[("HELPV_CONFIG", Path::new("~/helpv/alt.toml"))]);
+        assert_eq!(
+            paths.config_file(),
+            PathBuf::from("/home/user/helpv/alt.toml")
+        );
+
+        let paths = resolve_with(&[("HELPV_CONFIG", Path::new(""))]);
+        assert_eq!(paths.config_override, None);
+    }
+
+    #[test]
+    fn config_flag_takes_precedence_over_env() {
+        let paths = resolve_with(&[("HELPV_CONFIG", Path::new("/etc/helpv.toml"))])
+            .with_config_file(Path::new("/tmp/cli.toml"));
+        assert_eq!(paths.config_file(), PathBuf::from("/tmp/cli.toml"));
+    }
+
+    #[test]
+    fn config_flag_expands_tilde_against_resolved_home() {
+        let paths = resolve_with(&[]).with_config_file(Path::new("~/alt.toml"));
+        assert_eq!(paths.config_file(), PathBuf::from("/home/user/alt.toml"));
+    }
+
+    #[test]
+    fn expand_tilde_only_expands_leading_home() {
+        let home = Path::new("/home/user");
+        assert_eq!(expand_tilde("~", home), PathBuf::from("/home/user"));
+        assert_eq!(
+            expand_tilde("~/a.toml", home),
+            PathBuf::from("/home/user/a.toml")
+        );
+        assert_eq!(
+            expand_tilde("~bob/a.toml", home),
+            PathBuf::from("~bob/a.toml")
+        );
+        assert_eq!(
+            expand_tilde("rel/a.toml", home),
+            PathBuf::from("rel/a.toml")
+        );
+    }
+
+    #[test]
+    fn command_history_file_needs_a_data_dir() {
+        assert_eq!(Paths::default().command_history_file(), None);
+        assert_eq!(
+            resolve_with(&[]).command_history_file(),
+            Some(PathBuf::from(
+                "/home/user/.local/share/helpv/command_history.json"
+            ))
+        );
+    }
+
+    #[test]
+    fn format_timestamp_is_utc_date_and_time() {
+        let time = |secs| SystemTime::UNIX_EPOCH + std::time::Duration::from_secs(secs);
+        assert_eq!(format_timestamp(time(0)), "19700101-000000");
+        assert_eq!(format_timestamp(time(1_704_067_200)), "20240101-000000");
+        // Leap day
+        assert_eq!(format_timestamp(time(1_709_217_045)), "20240229-143045");
+    }
+
+    #[test]
+    fn crash_reports_lists_only_crash_files_in_order() {
+        let dir = std::env::temp_dir().join(format!("helpv-crash-{}", std::process::id()));
+        let paths = Paths {
+            data_dir: dir.clone(),
+            ..Paths::default()
+        };
+        assert!(paths.crash_reports().is_empty());
+
+        std::fs::create_dir_all(&dir).unwrap();
+        let newer =
+            paths.crash_file(SystemTime::UNIX_EPOCH + std::time::Duration::from_secs(86_400));
+        let older = paths.crash_file(SystemTime::UNIX_EPOCH);
+        for path in [&newer, &older, &paths.session_file(SystemTime::UNIX_EPOCH)] {
+            std::fs::write(path, "{}").unwrap();
+        }
+
+        let reports = paths.crash_reports();
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(reports, vec![older, newer]);
+        assert!(reports[0].ends_with("crash_19700101-000000.json"));
+    }
+
+    #[test]
+    fn new_crash_report_is_announced_once() {
+        let dir = std::env::temp_dir().join(format!("helpv-crash-seen-{}", std::process::id()));
+        let paths = Paths {
+            data_dir: dir.clone(),
+            ..Paths::default()
+        };
+        std::fs::create_dir_all(&dir).unwrap();
+        let first = paths.crash_file(SystemTime::UNIX_EPOCH);
+        std::fs::write(&first, "{}").unwrap();
+
+        assert_eq!(paths.take_new_crash_report(), Some(first));
+        assert_eq!(paths.take_new_crash_report(), None);
+
+        let second = paths.crash_file(SystemTime::UNIX_EPOCH + std::time::Duration::from_secs(60));
+        std::fs::write(&second, "{}").unwrap();
+        let announced = paths.take_new_crash_report();
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(announced, Some(second));
+    }
+
+    #[test]
+    fn ignores_relative_xdg_values() {
+        let paths = resolve_with(&[("XDG_CONFIG_HOME", Path::new("relative/dir"))]);
+        assert_eq!(paths.config_dir, PathBuf::from("/home/user/.config/helpv"));
+    }
+
+    #[test]
+    fn ignores_empty_xdg_values() {
+        let paths = resolve_with(&[("XDG_DATA_HOME", Path::new(""))]);
+        assert_eq!(
+            paths.data_dir,
+            PathBuf::from("/home/user/.local/share/helpv")
+        );
+    }
+}
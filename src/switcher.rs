@@ -68,7 +68,8 @@ impl CommandSwitcher {
         }
 
         // Sort by score (highest first)
-        self.filtered.sort_by(|a, b| b.0.cmp(&a.0));
+        self.filtered
+            .sort_by_key(|&(score, _)| std::cmp::Reverse(score));
     }
 
     pub fn move_up(&mut self) {
@@ -88,6 +89,19 @@ impl CommandSwitcher {
         }
     }
 
+    /// Drop a command from the list and re-filter
+    pub fn remove(&mut self, cmd: &str) {
+        self.history.retain(|c| c != cmd);
+        self.update_filtered();
+        self.selected = self.selected.min(self.filtered.len().saturating_sub(1));
+    }
+
+    /// The command on the `index`th visible row
+    pub fn command_at(&self, index: usize) -> Option<String> {
+        let &(_, idx) = self.filtered.get(index)?;
+        Some(self.history[idx].clone())
+    }
+
     pub fn selected_command(&self) -> Option<String> {
         // If we have filtered results, return the selected one
         if let Some((_, idx)) = self.filtered.get(self.selected) {
@@ -128,6 +142,22 @@ impl CommandSwitcher {
                 self.move_down();
                 SwitcherAction::None
             }
+            KeyCode::Char('d') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                // Only history entries can be deleted, not a typed new command
+                match self.filtered.get(self.selected) {
+                    Some(&(_, idx)) => SwitcherAction::Delete(self.history[idx].clone()),
+                    None => SwitcherAction::None,
+                }
+            }
+            // Alt-1..Alt-9 pick the row showing that number
+            KeyCode::Char(c @ '1'..='9') if key.modifiers.contains(KeyModifiers::ALT) => {
+                let index = c as usize - '1' as usize;
+                if index < self.filtered.len() {
+                    SwitcherAction::SelectIndex(index)
+                } else {
+                    SwitcherAction::None
+                }
+            }
             KeyCode::Backspace => {
                 self.pop_char();
                 SwitcherAction::None
@@ -151,8 +181,15 @@ pub enum SwitcherAction {
     None,
     Close,
     Select(String),
+    /// Open the command on this visible row (Alt-1..Alt-9)
+    SelectIndex(usize),
+    /// Remove this command from the history
+    Delete(String),
 }
 
+/// Columns taken by a row's `[N]` shortcut hint and the space after it
+const SHORTCUT_HINT_WIDTH: usize = 4;
+
 pub struct SwitcherWidget<'a> {
     switcher: &'a CommandSwitcher,
 }
@@ -250,7 +287,9 @@ impl Widget for SwitcherWidget<'_> {
                     Style::default().fg(Color::White)
                 };
 
-                let mut line = if is_selected { "▶ " } else { "  " }.to_string();
+                // Leave room for the `[N]` hint drawn over the start
+                let mut line = " ".repeat(SHORTCUT_HINT_WIDTH);
+                line.push_str(if is_selected { "▶ " } else { "  " });
                 line.push_str(cmd);
 
                 // Truncate if too long
@@ -266,6 +305,11 @@ impl Widget for SwitcherWidget<'_> {
 
                 let span = Span::styled(line, style);
                 buf.set_span(inner.x, y, &span, inner.width);
+
+                if i < 9 {
+                    let hint = Span::styled(format!("[{}]", i + 1), style.fg(Color::DarkGray));
+                    buf.set_span(inner.x, y, &hint, inner.width);
+                }
             }
         }
 
@@ -279,3 +323,96 @@ impl Widget for SwitcherWidget<'_> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn switcher(cmds: &[&str]) -> CommandSwitcher {
+        CommandSwitcher::new(cmds.iter().map(|c| c.to_string()).collect())
+    }
+
+    fn ctrl(c: char) -> KeyEvent {
+        KeyEvent::new(KeyCode::Char(c), KeyModifiers::CONTROL)
+    }
+
+    fn alt(c: char) -> KeyEvent {
+        KeyEvent::new(KeyCode::Char(c), KeyModifiers::ALT)
+    }
+
+    // ========================================
+    // Shortcut tests
+    // ========================================
+
+    #[test]
+    fn alt_digit_selects_visible_row() {
+        let mut s = switcher(&["git", "cargo", "docker"]);
+        assert_eq!(s.handle_key(alt('2')), SwitcherAction::SelectIndex(1));
+        assert_eq!(s.command_at(1).as_deref(), Some("cargo"));
+    }
+
+    #[test]
+    fn alt_digit_counts_filtered_rows() {
+        let mut s = switcher(&["git", "cargo", "docker"]);
+        for c in "dock".chars() {
+            s.push_char(c);
+        }
+        assert_eq!(s.handle_key(alt('1')), SwitcherAction::SelectIndex(0));
+        assert_eq!(s.command_at(0).as_deref(), Some("docker"));
+        assert_eq!(s.handle_key(alt('2')), SwitcherAction::None);
+    }
+
+    #[test]
+    fn plain_digit_is_typed() {
+        let mut s = switcher(&["git"]);
+        let plain = KeyEvent::new(KeyCode::Char('1'), KeyModifiers::NONE);
+        assert_eq!(s.handle_key(plain), SwitcherAction::None);
+        assert_eq!(s.query, "1");
+    }
+
+    #[test]
+    fn rows_show_shortcut_hints() {
+        let s = switcher(&["git", "cargo"]);
+        let area = Rect::new(0, 0, 60, 20);
+        let mut buf = Buffer::empty(area);
+        SwitcherWidget::new(&s).render(area, &mut buf);
+        let rows: Vec<String> = (0..area.height)
+            .map(|y| (0..area.width).map(|x| buf[(x, y)].symbol()).collect())
+            .collect();
+        assert!(rows.iter().any(|r| r.contains("[1] ▶ git")));
+        assert!(rows.iter().any(|r| r.contains("[2]   cargo")));
+    }
+
+    // ========================================
+    // Delete tests
+    // ========================================
+
+    #[test]
+    fn ctrl_d_deletes_selected_entry() {
+        let mut s = switcher(&["git", "cargo", "docker"]);
+        s.move_down();
+        assert_eq!(
+            s.handle_key(ctrl('d')),
+            SwitcherAction::Delete("cargo".to_string())
+        );
+    }
+
+    #[test]
+    fn ctrl_d_ignores_typed_new_command() {
+        let mut s = switcher(&["git"]);
+        for c in "zzz".chars() {
+            s.push_char(c);
+        }
+        assert_eq!(s.handle_key(ctrl('d')), SwitcherAction::None);
+    }
+
+    #[test]
+    fn remove_refilters_and_clamps_selection() {
+        let mut s = switcher(&["git", "cargo", "docker"]);
+        s.move_down();
+        s.move_down();
+        s.remove("docker");
+        assert_eq!(s.filtered_count(), 2);
+        assert_eq!(s.selected_command().as_deref(), Some("cargo"));
+    }
+}
@@ -0,0 +1,4 @@
+//! Text helpers for reuse outside helpv, e.g. by completion or documentation
+//! tools that read `--help` and man output themselves
+
+pub use crate::fetcher::{looks_like_help, strip_man_formatting};
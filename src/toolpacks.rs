@@ -1,12 +1,22 @@
 use anyhow::Result;
 use regex::Regex;
 use serde::Deserialize;
+use std::borrow::Cow;
 use std::collections::HashMap;
-use std::process::Command;
+use std::path::Path;
+use std::process::Output;
+use std::sync::mpsc;
+use std::time::Duration;
+
+use crate::fetcher::child_command;
+use crate::paths::Paths;
 
 /// Embedded default tool packs
 const DEFAULT_TOOLPACKS: &str = include_str!("toolpacks.toml");
 
+/// How long `ToolPack::verify` lets each discovery command run
+const VERIFY_TIMEOUT: Duration = Duration::from_secs(5);
+
 #[derive(Debug, Clone, Deserialize, Default)]
 pub struct ToolPacks {
     #[serde(flatten)]
@@ -20,13 +30,68 @@ pub struct ToolPack {
     pub help: Vec<String>,
 
     /// Commands to try for subcommand help
-    /// Use {base} for base command, {sub} for subcommand
+    /// Use {base} for base command, {sub} for subcommand, {sub_0}, {sub_1}, ...
+    /// for one level of a nested subcommand
     #[serde(default)]
     pub subcommand: Vec<String>,
 
     /// Additional discovery sources
     #[serde(default)]
     pub discover: Vec<DiscoverySource>,
+
+    /// Which item to keep when discovery sources return duplicates
+    #[serde(default)]
+    pub merge_strategy: MergeStrategy,
+
+    /// Command whose output the examples key appends to the page; tools
+    /// without one have no examples. Use {base} for base command, {name}
+    /// for "examples"
+    #[serde(default)]
+    pub examples: Option<String>,
+
+    /// Help sources to try for this tool, in order ("help", "man", "tldr",
+    /// "info"). Empty uses the global `help_flag_order`.
+    #[serde(default)]
+    pub help_order: Vec<String>,
+}
+
+/// Base help commands for tools without a pack, or whose pack sets no `help`
+const GENERIC_HELP_COMMANDS: &[&str] = &["{cmd} --help", "{cmd} -h"];
+
+/// Subcommand help commands for tools without a pack, or whose pack sets no
+/// `subcommand`
+const GENERIC_SUBCOMMAND_COMMANDS: &[&str] = &["{cmd} --help", "{base} help {sub}", "{cmd} -h"];
+
+/// The pack used for tools that have none: generic help commands and no
+/// discovery sources
+impl Default for ToolPack {
+    fn default() -> Self {
+        Self {
+            help: to_strings(GENERIC_HELP_COMMANDS),
+            subcommand: to_strings(GENERIC_SUBCOMMAND_COMMANDS),
+            discover: Vec::new(),
+            merge_strategy: MergeStrategy::default(),
+            examples: None,
+            help_order: Vec::new(),
+        }
+    }
+}
+
+fn to_strings(items: &[&str]) -> Vec<String> {
+    items.iter().map(|item| item.to_string()).collect()
+}
+
+/// Which of two duplicate discovered items survives
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MergeStrategy {
+    /// Keep the item from the earliest source
+    #[default]
+    First,
+    /// Keep the item from the latest source
+    Last,
+    /// Keep the item with the longest description
+    HighestScore,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -48,6 +113,62 @@ pub struct DiscoverySource {
     /// Optional section header pattern - only parse after matching this
     #[serde(default)]
     pub section: Option<String>,
+
+    /// Optional pattern that ends the section (the matching line is not parsed);
+    /// without it, parsing runs to the end of the output
+    #[serde(default)]
+    pub section_stop: Option<String>,
+
+    /// End the section after this many consecutive blank lines
+    #[serde(default)]
+    pub section_stop_blank_lines: Option<usize>,
+
+    /// What makes two items duplicates: "name" (default), "invoke", or "name+invoke"
+    #[serde(default)]
+    pub dedupe_key: Option<String>,
+}
+
+/// How a discovered item is compared against existing items when merging
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DedupeStrategy {
+    /// Duplicate if another item has the same name
+    #[default]
+    Name,
+    /// Duplicate if another item runs the same invoke command
+    Invoke,
+    /// Duplicate only if both name and invoke command match
+    NameAndInvoke,
+}
+
+impl DedupeStrategy {
+    /// Parse a `dedupe_key` value, falling back to name-based deduplication
+    pub fn from_key(key: Option<&str>) -> Self {
+        match key {
+            Some("invoke") => Self::Invoke,
+            Some("name+invoke") => Self::NameAndInvoke,
+            _ => Self::Name,
+        }
+    }
+
+    /// Whether an item counts as a duplicate of an existing one. Invoke commands
+    /// are compared with `{name}` already substituted.
+    pub fn is_duplicate(
+        self,
+        (name, invoke): (&str, Option<&str>),
+        (existing_name, existing_invoke): (&str, Option<&str>),
+    ) -> bool {
+        let same_invoke = || {
+            let resolved = invoke.map(|cmd| cmd.replace("{name}", name));
+            resolved.is_some()
+                && resolved == existing_invoke.map(|cmd| cmd.replace("{name}", existing_name))
+        };
+
+        match self {
+            Self::Name => name == existing_name,
+            Self::Invoke => same_invoke(),
+            Self::NameAndInvoke => name == existing_name && same_invoke(),
+        }
+    }
 }
 
 /// An item discovered from a discovery source
@@ -57,47 +178,78 @@ pub struct DiscoveredItem {
     pub description: Option<String>,
     pub label: String,
     pub invoke_template: String,
+    pub dedupe_strategy: DedupeStrategy,
 }
 
 impl ToolPacks {
-    pub fn load() -> Result<Self> {
+    /// Load the built-in packs and the user's. Files that fail to load are
+    /// skipped, with a message for each in the returned warnings.
+    pub fn load(paths: &Paths) -> Result<(Self, Vec<String>)> {
         // Load embedded defaults
-        let mut packs: ToolPacks = toml::from_str(DEFAULT_TOOLPACKS)?;
-
-        // Load user overrides from ~/.config/helpv/tools/*.toml
-        if let Some(config_dir) = dirs::config_dir() {
-            let tools_dir = config_dir.join("helpv").join("tools");
-            if tools_dir.exists()
-                && let Ok(entries) = std::fs::read_dir(&tools_dir)
-            {
-                for entry in entries.flatten() {
-                    let path = entry.path();
-                    if path.extension().map(|e| e == "toml").unwrap_or(false)
-                        && let Ok(content) = std::fs::read_to_string(&path)
-                        && let Ok(user_packs) = toml::from_str::<ToolPacks>(&content)
-                    {
-                        // User packs override defaults
-                        for (name, pack) in user_packs.tools {
-                            packs.tools.insert(name, pack);
-                        }
+        let mut packs = Self::load_toml(DEFAULT_TOOLPACKS)?;
+        let mut warnings = Vec::new();
+
+        // Load user overrides from ~/.config/helpv/tools/*.{toml,json}
+        let tools_dir = paths.tools_dir();
+        if tools_dir.exists()
+            && let Ok(entries) = std::fs::read_dir(&tools_dir)
+        {
+            for entry in entries.flatten() {
+                let path = entry.path();
+                let parse = match path.extension().and_then(|e| e.to_str()) {
+                    Some("toml") => Self::load_toml,
+                    Some("json") => Self::load_json,
+                    _ => continue,
+                };
+                // A broken file shouldn't take the other packs down with it
+                let user_packs = match std::fs::read_to_string(&path)
+                    .map_err(anyhow::Error::from)
+                    .and_then(|content| parse(&content))
+                {
+                    Ok(user_packs) => user_packs,
+                    Err(e) => {
+                        warnings.push(format!("skipping tool pack {}: {}", path.display(), e));
+                        continue;
                     }
+                };
+                // User packs override defaults
+                for (name, pack) in user_packs.tools {
+                    packs.tools.insert(name, pack);
                 }
             }
         }
 
-        Ok(packs)
+        Ok((packs, warnings))
+    }
+
+    /// Parse tool packs written in TOML, the format of the built-in packs
+    pub fn load_toml(content: &str) -> Result<ToolPacks> {
+        Ok(toml::from_str(content)?)
+    }
+
+    /// Parse tool packs written in JSON, with the same schema as the TOML files
+    pub fn load_json(content: &str) -> Result<ToolPacks> {
+        Ok(serde_json::from_str(content)?)
     }
 
     pub fn get(&self, tool: &str) -> Option<&ToolPack> {
         self.tools.get(tool)
     }
+
+    /// The pack for `tool`, or the generic `ToolPack::default()` when it has none
+    pub fn get_or_default(&self, tool: &str) -> Cow<'_, ToolPack> {
+        match self.tools.get(tool) {
+            Some(pack) => Cow::Borrowed(pack),
+            None => Cow::Owned(ToolPack::default()),
+        }
+    }
 }
 
 impl ToolPack {
     /// Get help flags for fetching base help
     pub fn get_help_commands(&self) -> Vec<String> {
         if self.help.is_empty() {
-            vec!["{cmd} --help".to_string(), "{cmd} -h".to_string()]
+            to_strings(GENERIC_HELP_COMMANDS)
         } else {
             self.help.clone()
         }
@@ -106,13 +258,13 @@ impl ToolPack {
     /// Get help flags for fetching subcommand help
     pub fn get_subcommand_commands(&self) -> Vec<String> {
         if self.subcommand.is_empty() {
-            vec!["{cmd} --help".to_string(), "{base} help {sub}".to_string()]
+            to_strings(GENERIC_SUBCOMMAND_COMMANDS)
         } else {
             self.subcommand.clone()
         }
     }
 
-    /// Run all discovery sources and collect items
+    /// Run all discovery sources and collect items, without duplicates
     pub fn discover_items(&self, base_cmd: &str) -> Vec<DiscoveredItem> {
         let mut items = Vec::new();
 
@@ -122,36 +274,165 @@ impl ToolPack {
             }
         }
 
-        items
+        dedupe_items(items, self.merge_strategy)
+    }
+
+    /// Check each discovery source: its command is in PATH, its patterns
+    /// compile, and it finds at least one item. Returns one warning per problem.
+    pub fn verify(&self, base_cmd: &str) -> Vec<String> {
+        let mut warnings = Vec::new();
+
+        for source in &self.discover {
+            let warn = |msg: String| format!("[{}] {}", source.label, msg);
+
+            let cmd_str = source.run.replace("{base}", base_cmd);
+            let parts: Vec<&str> = cmd_str.split_whitespace().collect();
+            let Some(program) = parts.first() else {
+                warnings.push(warn("run command is empty".to_string()));
+                continue;
+            };
+            let found = is_in_path(program);
+            if !found {
+                warnings.push(warn(format!("'{}' not found in PATH", program)));
+            }
+
+            if let Err(e) = Regex::new(&source.pattern) {
+                warnings.push(warn(format!("pattern does not compile: {}", e)));
+                continue;
+            }
+            if let Some(ref section) = source.section
+                && let Err(e) = Regex::new(section)
+            {
+                warnings.push(warn(format!("section does not compile: {}", e)));
+                continue;
+            }
+            if let Some(ref stop) = source.section_stop
+                && let Err(e) = Regex::new(stop)
+            {
+                warnings.push(warn(format!("section_stop does not compile: {}", e)));
+                continue;
+            }
+            if !found {
+                continue;
+            }
+
+            match run_with_timeout(&parts, VERIFY_TIMEOUT) {
+                Some(output) if output.status.success() => {
+                    let text = String::from_utf8_lossy(&output.stdout);
+                    if source
+                        .parse_items(&text)
+                        .is_ok_and(|items| items.is_empty())
+                    {
+                        warnings.push(warn(format!("'{}' produced no items", cmd_str)));
+                    }
+                }
+                Some(output) => {
+                    warnings.push(warn(format!("'{}' exited with {}", cmd_str, output.status)))
+                }
+                None => warnings.push(warn(format!(
+                    "'{}' did not finish within {}s",
+                    cmd_str,
+                    VERIFY_TIMEOUT.as_secs()
+                ))),
+            }
+        }
+
+        warnings
+    }
+}
+
+/// Whether `program` names an executable file, directly or via `PATH`
+fn is_in_path(program: &str) -> bool {
+    if program.contains(std::path::MAIN_SEPARATOR) {
+        return Path::new(program).is_file();
+    }
+    std::env::var_os("PATH")
+        .is_some_and(|path| std::env::split_paths(&path).any(|dir| dir.join(program).is_file()))
+}
+
+/// Run a command, giving up (and leaving it to finish in the background)
+/// after `timeout`. `None` if it failed to start or timed out.
+fn run_with_timeout(parts: &[&str], timeout: Duration) -> Option<Output> {
+    let (tx, rx) = mpsc::channel();
+    let program = parts[0].to_string();
+    let args: Vec<String> = parts[1..].iter().map(|p| p.to_string()).collect();
+    std::thread::spawn(move || {
+        let _ = tx.send(child_command(program).args(args).output());
+    });
+    rx.recv_timeout(timeout).ok()?.ok()
+}
+
+impl DiscoveredItem {
+    fn is_duplicate_of(&self, existing: &DiscoveredItem) -> bool {
+        self.dedupe_strategy.is_duplicate(
+            (&self.name, Some(&self.invoke_template)),
+            (&existing.name, Some(&existing.invoke_template)),
+        )
+    }
+
+    /// Ranking for `MergeStrategy::HighestScore`
+    fn score(&self) -> usize {
+        self.description.as_ref().map_or(0, |d| d.trim().len())
     }
 }
 
+/// Collapse duplicates, keeping the first occurrence's position and choosing
+/// which item fills it by `strategy`
+fn dedupe_items(items: Vec<DiscoveredItem>, strategy: MergeStrategy) -> Vec<DiscoveredItem> {
+    let mut kept: Vec<DiscoveredItem> = Vec::new();
+
+    for item in items {
+        let Some(i) = kept.iter().position(|k| item.is_duplicate_of(k)) else {
+            kept.push(item);
+            continue;
+        };
+        let replace = match strategy {
+            MergeStrategy::First => false,
+            MergeStrategy::Last => true,
+            MergeStrategy::HighestScore => item.score() > kept[i].score(),
+        };
+        if replace {
+            kept[i] = item;
+        }
+    }
+
+    kept
+}
+
 impl DiscoverySource {
     /// Run this discovery source and extract items
     pub fn run_discovery(&self, base_cmd: &str) -> Result<Vec<DiscoveredItem>> {
-        let mut items = Vec::new();
-
         // Build and run the command
         let cmd_str = self.run.replace("{base}", base_cmd);
         let parts: Vec<&str> = cmd_str.split_whitespace().collect();
         if parts.is_empty() {
-            return Ok(items);
+            return Ok(Vec::new());
         }
 
-        let output = Command::new(parts[0]).args(&parts[1..]).output()?;
+        let output = child_command(parts[0]).args(&parts[1..]).output()?;
 
         if !output.status.success() {
-            return Ok(items);
+            return Ok(Vec::new());
         }
 
         let text = String::from_utf8_lossy(&output.stdout);
+        self.parse_items(&text)
+    }
+
+    /// Extract items from a discovery command's output
+    fn parse_items(&self, text: &str) -> Result<Vec<DiscoveredItem>> {
+        let mut items = Vec::new();
 
         // Compile patterns
         let entry_re = Regex::new(&self.pattern)?;
         let section_re = self.section.as_ref().and_then(|s| Regex::new(s).ok());
+        let stop_re = self.section_stop.as_ref().and_then(|s| Regex::new(s).ok());
+
+        let dedupe_strategy = DedupeStrategy::from_key(self.dedupe_key.as_deref());
 
         // Parse the output
         let mut in_section = section_re.is_none(); // If no section pattern, parse everything
+        let mut blank_run = 0;
 
         for line in text.lines() {
             // Check for section header
@@ -159,6 +440,7 @@ impl DiscoverySource {
                 && re.is_match(line)
             {
                 in_section = true;
+                blank_run = 0;
                 continue;
             }
 
@@ -166,6 +448,22 @@ impl DiscoverySource {
                 continue;
             }
 
+            if stop_re.as_ref().is_some_and(|re| re.is_match(line)) {
+                in_section = false;
+                continue;
+            }
+            if line.trim().is_empty() {
+                blank_run += 1;
+                if self
+                    .section_stop_blank_lines
+                    .is_some_and(|limit| blank_run >= limit)
+                {
+                    in_section = false;
+                }
+                continue;
+            }
+            blank_run = 0;
+
             // Try to match entry
             if let Some(caps) = entry_re.captures(line)
                 && let Some(name_match) = caps.get(1)
@@ -183,6 +481,7 @@ impl DiscoverySource {
                     description,
                     label: self.label.clone(),
                     invoke_template: self.invoke.clone(),
+                    dedupe_strategy,
                 });
             }
         }
@@ -190,3 +489,325 @@ impl DiscoverySource {
         Ok(items)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dedupe_strategy_from_key() {
+        assert_eq!(DedupeStrategy::from_key(None), DedupeStrategy::Name);
+        assert_eq!(DedupeStrategy::from_key(Some("name")), DedupeStrategy::Name);
+        assert_eq!(
+            DedupeStrategy::from_key(Some("invoke")),
+            DedupeStrategy::Invoke
+        );
+        assert_eq!(
+            DedupeStrategy::from_key(Some("name+invoke")),
+            DedupeStrategy::NameAndInvoke
+        );
+        assert_eq!(
+            DedupeStrategy::from_key(Some("bogus")),
+            DedupeStrategy::Name
+        );
+    }
+
+    fn item(name: &str, description: &str, label: &str) -> DiscoveredItem {
+        DiscoveredItem {
+            name: name.to_string(),
+            description: Some(description.to_string()),
+            label: label.to_string(),
+            invoke_template: "git help {name}".to_string(),
+            dedupe_strategy: DedupeStrategy::Name,
+        }
+    }
+
+    fn labels(items: &[DiscoveredItem]) -> Vec<(&str, &str)> {
+        items
+            .iter()
+            .map(|i| (i.name.as_str(), i.label.as_str()))
+            .collect()
+    }
+
+    fn sample_items() -> Vec<DiscoveredItem> {
+        vec![
+            item("commit", "Record changes", "All Commands"),
+            item("log", "Show logs", "All Commands"),
+            item("commit", "Record changes to the repository", "Man Pages"),
+        ]
+    }
+
+    #[test]
+    fn dedupe_keeps_first_by_default() {
+        let kept = dedupe_items(sample_items(), MergeStrategy::First);
+        assert_eq!(
+            labels(&kept),
+            vec![("commit", "All Commands"), ("log", "All Commands")]
+        );
+    }
+
+    #[test]
+    fn dedupe_keeps_last() {
+        let kept = dedupe_items(sample_items(), MergeStrategy::Last);
+        assert_eq!(
+            labels(&kept),
+            vec![("commit", "Man Pages"), ("log", "All Commands")]
+        );
+    }
+
+    #[test]
+    fn dedupe_keeps_highest_score() {
+        let mut items = sample_items();
+        items.push(item("log", "", "Man Pages"));
+        let kept = dedupe_items(items, MergeStrategy::HighestScore);
+        assert_eq!(
+            labels(&kept),
+            vec![("commit", "Man Pages"), ("log", "All Commands")]
+        );
+    }
+
+    #[test]
+    fn dedupe_respects_dedupe_strategy() {
+        let mut other = item("commit", "Record changes", "Guides");
+        other.invoke_template = "man git-{name}".to_string();
+        other.dedupe_strategy = DedupeStrategy::NameAndInvoke;
+        let kept = dedupe_items(
+            vec![item("commit", "Record changes", "All Commands"), other],
+            MergeStrategy::First,
+        );
+        assert_eq!(kept.len(), 2);
+    }
+
+    #[test]
+    fn merge_strategy_parses_from_toml() {
+        let packs: ToolPacks = toml::from_str(
+            r#"
+[mytool]
+merge_strategy = "highest_score"
+"#,
+        )
+        .unwrap();
+        assert_eq!(
+            packs.get("mytool").unwrap().merge_strategy,
+            MergeStrategy::HighestScore
+        );
+    }
+
+    #[test]
+    fn embedded_toolpacks_parse() {
+        let packs: ToolPacks = toml::from_str(DEFAULT_TOOLPACKS).unwrap();
+        assert!(packs.get("git").is_some());
+    }
+
+    // ========================================
+    // Default pack tests
+    // ========================================
+
+    #[test]
+    fn get_or_default_borrows_known_packs() {
+        let mut packs = ToolPacks::default();
+        packs.tools.insert(
+            "mytool".to_string(),
+            ToolPack {
+                help: vec!["mytool help".to_string()],
+                ..Default::default()
+            },
+        );
+
+        let pack = packs.get_or_default("mytool");
+        assert!(matches!(pack, Cow::Borrowed(_)));
+        assert_eq!(pack.get_help_commands(), vec!["mytool help"]);
+    }
+
+    #[test]
+    fn get_or_default_gives_generic_pack_for_unknown_tools() {
+        let packs = ToolPacks::default();
+        let pack = packs.get_or_default("unknown-tool");
+        assert!(matches!(pack, Cow::Owned(_)));
+        assert_eq!(pack.get_help_commands(), vec!["{cmd} --help", "{cmd} -h"]);
+        assert_eq!(
+            pack.get_subcommand_commands(),
+            vec!["{cmd} --help", "{base} help {sub}", "{cmd} -h"]
+        );
+        assert!(pack.discover.is_empty());
+        assert!(pack.examples.is_none());
+    }
+
+    #[test]
+    fn empty_pack_commands_fall_back_to_generic() {
+        let pack = pack(&[]);
+        assert_eq!(pack.get_help_commands(), ToolPack::default().help);
+        assert_eq!(
+            pack.get_subcommand_commands(),
+            ToolPack::default().subcommand
+        );
+    }
+
+    // ========================================
+    // verify tests
+    // ========================================
+
+    fn pack(sources: &[(&str, &str)]) -> ToolPack {
+        ToolPack {
+            help: Vec::new(),
+            subcommand: Vec::new(),
+            discover: sources
+                .iter()
+                .map(|(run, pattern)| DiscoverySource {
+                    label: "Test".to_string(),
+                    run: run.to_string(),
+                    pattern: pattern.to_string(),
+                    invoke: "{base} {name}".to_string(),
+                    section: None,
+                    section_stop: None,
+                    section_stop_blank_lines: None,
+                    dedupe_key: None,
+                })
+                .collect(),
+            merge_strategy: MergeStrategy::First,
+            examples: None,
+            help_order: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn verify_passes_working_source() {
+        let pack = pack(&[("printf {base}", r"^(\w+)")]);
+        assert_eq!(pack.verify("alpha"), Vec::<String>::new());
+    }
+
+    #[test]
+    fn verify_reports_missing_command() {
+        let warnings = pack(&[("helpv-no-such-tool list", r"(\w+)")]).verify("x");
+        assert_eq!(
+            warnings,
+            vec!["[Test] 'helpv-no-such-tool' not found in PATH"]
+        );
+    }
+
+    #[test]
+    fn verify_reports_bad_pattern() {
+        let warnings = pack(&[("printf x", r"(\w+")]).verify("x");
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].starts_with("[Test] pattern does not compile"));
+    }
+
+    #[test]
+    fn verify_reports_no_items() {
+        let warnings = pack(&[("printf {base}", r"^(\d+)$")]).verify("alpha");
+        assert_eq!(warnings, vec!["[Test] 'printf alpha' produced no items"]);
+    }
+
+    // ========================================
+    // Section tests
+    // ========================================
+
+    const SECTIONED_HELP: &str = "COMMANDS\n  build  Build it\n  run    Run it\n\n  test   Test it\nOPTIONS\n  verbose  Say more\n";
+
+    fn section_source(stop: Option<&str>, stop_blank_lines: Option<usize>) -> DiscoverySource {
+        let mut source = pack(&[("true", r"^  (\w+)\s+(.*)$")]).discover.remove(0);
+        source.section = Some("^COMMANDS".to_string());
+        source.section_stop = stop.map(str::to_string);
+        source.section_stop_blank_lines = stop_blank_lines;
+        source
+    }
+
+    fn item_names(source: &DiscoverySource) -> Vec<String> {
+        let items = source.parse_items(SECTIONED_HELP).unwrap();
+        items.into_iter().map(|item| item.name).collect()
+    }
+
+    #[test]
+    fn section_without_stop_runs_to_end() {
+        assert_eq!(
+            item_names(&section_source(None, None)),
+            vec!["build", "run", "test", "verbose"]
+        );
+    }
+
+    #[test]
+    fn section_stop_pattern_ends_section() {
+        assert_eq!(
+            item_names(&section_source(Some("^OPTIONS"), None)),
+            vec!["build", "run", "test"]
+        );
+    }
+
+    #[test]
+    fn section_stop_blank_lines_ends_section() {
+        assert_eq!(
+            item_names(&section_source(None, Some(1))),
+            vec!["build", "run"]
+        );
+        assert_eq!(
+            item_names(&section_source(None, Some(2))),
+            vec!["build", "run", "test", "verbose"]
+        );
+    }
+
+    #[test]
+    fn verify_reports_bad_section_stop() {
+        let mut pack = pack(&[("printf x", r"(\w+)")]);
+        pack.discover[0].section_stop = Some("(".to_string());
+        let warnings = pack.verify("x");
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].starts_with("[Test] section_stop does not compile"));
+    }
+
+    // ========================================
+    // JSON pack tests
+    // ========================================
+
+    #[test]
+    fn load_json_matches_toml_schema() {
+        let packs = ToolPacks::load_json(include_str!("../tests/fixtures/toolpack.json")).unwrap();
+        let pack = packs.get("mytool").unwrap();
+        assert_eq!(pack.help, vec!["mytool --help"]);
+        assert_eq!(pack.subcommand, vec!["mytool help {sub}"]);
+        assert_eq!(pack.merge_strategy, MergeStrategy::HighestScore);
+
+        let source = &pack.discover[0];
+        assert_eq!(source.label, "Plugins");
+        assert_eq!(source.pattern, r"^  (\S+)\s+(.*)$");
+        assert_eq!(source.section.as_deref(), Some("^Installed plugins"));
+        assert_eq!(source.dedupe_key.as_deref(), Some("name+invoke"));
+    }
+
+    #[test]
+    fn load_json_rejects_invalid_json() {
+        assert!(ToolPacks::load_json("{ not json").is_err());
+    }
+
+    #[test]
+    fn load_skips_broken_files_and_keeps_others() {
+        let dir = std::env::temp_dir().join(format!("helpv-packs-{}", std::process::id()));
+        let paths = Paths {
+            config_dir: dir.clone(),
+            ..Paths::default()
+        };
+        let tools_dir = paths.tools_dir();
+        std::fs::create_dir_all(&tools_dir).unwrap();
+        std::fs::write(
+            tools_dir.join("mytool.json"),
+            include_str!("../tests/fixtures/toolpack.json"),
+        )
+        .unwrap();
+        std::fs::write(tools_dir.join("broken.json"), "{").unwrap();
+        std::fs::write(tools_dir.join("broken.toml"), "[[[").unwrap();
+        std::fs::write(
+            tools_dir.join("other.toml"),
+            "[othertool]\nhelp = [\"othertool -h\"]\n",
+        )
+        .unwrap();
+
+        let (packs, warnings) = ToolPacks::load(&paths).unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert!(packs.get("mytool").is_some());
+        assert_eq!(warnings.len(), 2);
+        assert!(warnings.iter().all(|w| w.contains("broken.")));
+        assert_eq!(packs.get("othertool").unwrap().help, vec!["othertool -h"]);
+        // Built-ins are still there
+        assert!(packs.get("git").is_some());
+    }
+}
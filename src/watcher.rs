@@ -0,0 +1,96 @@
+use anyhow::Result;
+use std::path::Path;
+use std::sync::mpsc;
+
+/// Sent when the watched config file changes on disk
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ConfigReload;
+
+/// Watches the config file and reports changes to the main thread
+pub struct ConfigWatcher {
+    #[cfg(feature = "watch-config")]
+    _watcher: notify::RecommendedWatcher,
+    receiver: mpsc::Receiver<ConfigReload>,
+}
+
+impl ConfigWatcher {
+    /// Start watching `config_file`. The parent directory is watched rather than
+    /// the file itself so editors that save by renaming a temp file are caught.
+    #[cfg(feature = "watch-config")]
+    pub fn new(config_file: &Path) -> Result<Self> {
+        use notify::{RecursiveMode, Watcher};
+
+        let dir = config_file
+            .parent()
+            .ok_or_else(|| anyhow::anyhow!("config path has no parent directory"))?;
+        let file_name = config_file.file_name().map(|n| n.to_os_string());
+
+        let (sender, receiver) = mpsc::channel();
+        let mut watcher =
+            notify::recommended_watcher(move |res: notify::Result<notify::Event>| {
+                let Ok(event) = res else {
+                    return;
+                };
+                let touches_config = event
+                    .paths
+                    .iter()
+                    .any(|p| p.file_name().map(|n| n.to_os_string()) == file_name);
+                if touches_config && (event.kind.is_modify() || event.kind.is_create()) {
+                    let _ = sender.send(ConfigReload);
+                }
+            })?;
+        watcher.watch(dir, RecursiveMode::NonRecursive)?;
+
+        Ok(Self {
+            _watcher: watcher,
+            receiver,
+        })
+    }
+
+    #[cfg(not(feature = "watch-config"))]
+    pub fn new(_config_file: &Path) -> Result<Self> {
+        anyhow::bail!("helpv was built without the watch-config feature")
+    }
+
+    /// Whether the config changed since the last poll. Drains queued events so a
+    /// burst of writes from one save triggers a single reload.
+    pub fn poll(&self) -> bool {
+        self.receiver.try_iter().count() > 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(not(feature = "watch-config"))]
+    #[test]
+    fn new_fails_without_feature() {
+        assert!(ConfigWatcher::new(Path::new("/tmp/helpv/config.toml")).is_err());
+    }
+
+    #[cfg(feature = "watch-config")]
+    #[test]
+    fn poll_reports_config_writes() {
+        use std::time::{Duration, Instant};
+
+        let dir = std::env::temp_dir().join(format!("helpv-watch-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let config_file = dir.join("config.toml");
+        std::fs::write(&config_file, "show_scrollbar = true\n").unwrap();
+
+        let watcher = ConfigWatcher::new(&config_file).unwrap();
+        assert!(!watcher.poll());
+
+        std::fs::write(&config_file, "show_scrollbar = false\n").unwrap();
+        let deadline = Instant::now() + Duration::from_secs(5);
+        let mut reloaded = false;
+        while !reloaded && Instant::now() < deadline {
+            std::thread::sleep(Duration::from_millis(20));
+            reloaded = watcher.poll();
+        }
+
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert!(reloaded);
+    }
+}